@@ -1,8 +1,12 @@
+use crate::features::environments::AgentConnectionState;
 use app_core::actor::traits::Message;
+use app_core::rpc_bind;
 use std::sync::Arc;
+use std::time::Duration;
 
 use uniproc_protocol::{
-    LinuxDockerContainerInfo, LinuxEnvironmentInfo, LinuxMachineStats, LinuxProcessStats,
+    Hello, LinuxDockerContainerInfo, LinuxEnvironmentInfo, LinuxMachineStats, LinuxProcessStats,
+    LinuxRequest, LinuxResponse,
 };
 use uuid::Uuid;
 
@@ -17,16 +21,184 @@ pub struct RemoteScanResult {
     pub machine: LinuxMachineStats,
     pub environments: Vec<LinuxEnvironmentInfo>,
     pub docker_containers: Vec<LinuxDockerContainerInfo>,
+    // Offset into a session recording; rates of replayed reports follow it, not the wall clock.
+    pub recorded_at: Option<Duration>,
 }
 impl Message for RemoteScanResult {}
 
+// Published by the process list once it has taken in a report of the schema, used or skipped.
+// Streaming agents hold the next report back until then.
+#[derive(Clone, Debug)]
+pub struct ReportConsumed {
+    pub schema_id: &'static str,
+}
+impl Message for ReportConsumed {}
+
+// Highest agent protocol this build speaks, and the oldest it still accepts.
+// Protocol 2 added the hello request, the fd, cpu time, thread and page fault counters and
+// the process state, which changed the report layout.
+pub const PROTOCOL_VERSION: u32 = 2;
+pub const MIN_PROTOCOL_VERSION: u32 = 2;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AgentCapabilities {
+    pub streaming: bool,
+    pub process_control: bool,
+    pub per_transport_net: bool,
+    pub docker: bool,
+}
+
+// What an agent reports about itself when a connection is established.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AgentHello {
+    pub protocol_version: u32,
+    pub capabilities: AgentCapabilities,
+}
+
+impl AgentHello {
+    // Agents that predate the handshake speak protocol 1: polled reports with docker data
+    // and process control, but no streaming or per-transport network counters.
+    pub fn legacy() -> Self {
+        Self {
+            protocol_version: 1,
+            capabilities: AgentCapabilities {
+                streaming: false,
+                process_control: true,
+                per_transport_net: false,
+                docker: true,
+            },
+        }
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        (MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&self.protocol_version)
+    }
+}
+
+impl From<Hello> for AgentHello {
+    fn from(hello: Hello) -> Self {
+        Self {
+            protocol_version: hello.protocol_version,
+            capabilities: AgentCapabilities {
+                streaming: hello.streaming,
+                process_control: hello.process_control,
+                per_transport_net: hello.per_transport_net,
+                docker: hello.docker,
+            },
+        }
+    }
+}
+
+// Rolling connection statistics of one agent backend, republished on every ping tick.
+// Timings are `None` until the window holds a sample.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AgentHealth {
+    pub backend: &'static str,
+    pub state: AgentConnectionState,
+    pub ping_p50_ms: Option<u32>,
+    pub ping_p95_ms: Option<u32>,
+    pub ping_p99_ms: Option<u32>,
+    pub scan_p50_ms: Option<u32>,
+    pub scan_p95_ms: Option<u32>,
+    // Mean report size; transports that hide frame sizes leave it unknown.
+    pub scan_bytes: Option<u64>,
+    pub scans: u64,
+    pub scan_failures: u32,
+    pub malformed_reports: u32,
+    pub reconnects: u32,
+}
+impl Message for AgentHealth {}
+
+// Drops the current connection attempt or session of one backend and connects again, from any
+// state. `backend` is the backend's name, e.g. "WSL".
+#[derive(Clone, Debug)]
+pub struct Reconnect {
+    pub backend: &'static str,
+}
+impl Message for Reconnect {}
+
+// Drives a session replay; ignored unless the replay backend is installed.
+#[derive(Clone, Copy, Debug)]
+pub enum ReplayControl {
+    Pause,
+    Resume,
+    Seek(Duration),
+    SetSpeed(f64),
+}
+impl Message for ReplayControl {}
+
+// Where a session replay stands; published on every replay scan and after each control.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReplayStatus {
+    pub position: Duration,
+    pub length: Duration,
+    pub paused: bool,
+    pub speed: f64,
+}
+impl Message for ReplayStatus {}
+
+#[derive(Clone, Debug)]
+pub struct LinuxActionRequest {
+    pub schema_id: &'static str,
+    request_bytes: Arc<[u8]>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LinuxActionResponse {
+    response_bytes: Result<Arc<[u8]>, String>,
+}
+
+rpc_bind! {
+    LinuxActionRequest => LinuxActionResponse;
+}
+
+impl LinuxActionRequest {
+    pub fn new(schema_id: &'static str, request: LinuxRequest) -> Self {
+        let request_bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&request)
+            .expect("LinuxActionRequest should serialize");
+
+        Self {
+            schema_id,
+            request_bytes: Arc::<[u8]>::from(request_bytes.into_boxed_slice()),
+        }
+    }
+
+    pub fn decode_request(&self) -> Result<LinuxRequest, rkyv::rancor::Error> {
+        rkyv::from_bytes::<LinuxRequest, rkyv::rancor::Error>(&self.request_bytes)
+    }
+}
+
+impl LinuxActionResponse {
+    pub fn new(response: &LinuxResponse) -> Self {
+        let response_bytes = rkyv::to_bytes::<rkyv::rancor::Error>(response)
+            .expect("LinuxActionResponse should serialize");
+
+        Self {
+            response_bytes: Ok(Arc::<[u8]>::from(response_bytes.into_boxed_slice())),
+        }
+    }
+
+    // The agent could not be reached, as opposed to an error reported by the agent itself.
+    pub fn failed(error: impl Into<String>) -> Self {
+        Self {
+            response_bytes: Err(error.into()),
+        }
+    }
+
+    pub fn decode_response(&self) -> Result<LinuxResponse, String> {
+        let bytes = self.response_bytes.as_ref().map_err(Clone::clone)?;
+        rkyv::from_bytes::<LinuxResponse, rkyv::rancor::Error>(bytes).map_err(|e| e.to_string())
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "windows")] {
 
         use uniproc_protocol::{WindowsReport, WindowsRequest, WindowsResponse};
 
+        // The second field is the offset into a session recording, as on `RemoteScanResult`.
         #[derive(Clone)]
-        pub struct WindowsReportMessage(pub WindowsReport);
+        pub struct WindowsReportMessage(pub WindowsReport, pub Option<Duration>);
 
         impl Message for WindowsReportMessage {}
 
@@ -39,7 +211,7 @@ cfg_if::cfg_if! {
         #[derive(Clone, Debug)]
         pub struct WindowsActionResponse {
             pub correlation_id: Uuid,
-            response_bytes: Arc<[u8]>,
+            response_bytes: Result<Arc<[u8]>, String>,
         }
 
         impl Message for WindowsActionRequest {}
@@ -68,12 +240,22 @@ cfg_if::cfg_if! {
 
                 Self {
                     correlation_id,
-                    response_bytes: Arc::<[u8]>::from(response_bytes.into_boxed_slice()),
+                    response_bytes: Ok(Arc::<[u8]>::from(response_bytes.into_boxed_slice())),
+                }
+            }
+
+            // The agent could not be reached, as opposed to an error reported by the agent itself.
+            pub fn failed(correlation_id: Uuid, error: impl Into<String>) -> Self {
+                Self {
+                    correlation_id,
+                    response_bytes: Err(error.into()),
                 }
             }
 
-            pub fn decode_response(&self) -> Result<WindowsResponse, rkyv::rancor::Error> {
-                rkyv::from_bytes::<WindowsResponse, rkyv::rancor::Error>(&self.response_bytes)
+            pub fn decode_response(&self) -> Result<WindowsResponse, String> {
+                let bytes = self.response_bytes.as_ref().map_err(Clone::clone)?;
+                rkyv::from_bytes::<WindowsResponse, rkyv::rancor::Error>(bytes)
+                    .map_err(|e| e.to_string())
             }
         }
 
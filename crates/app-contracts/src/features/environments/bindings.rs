@@ -6,4 +6,24 @@ pub trait UiEnvironmentsBindings: 'static {
     fn on_install_agent<F>(&self, handler: F)
     where
         F: Fn(String) + 'static;
+
+    #[tracing(target = "agent")]
+    fn on_replay_pause<F>(&self, handler: F)
+    where
+        F: Fn(bool) + 'static;
+
+    #[tracing(target = "agent")]
+    fn on_replay_seek<F>(&self, handler: F)
+    where
+        F: Fn(f32) + 'static;
+
+    #[tracing(target = "agent")]
+    fn on_replay_speed<F>(&self, handler: F)
+    where
+        F: Fn(f32) + 'static;
+
+    #[tracing(target = "agent")]
+    fn on_reconnect_agent<F>(&self, handler: F)
+    where
+        F: Fn() + 'static;
 }
@@ -1,3 +1,4 @@
+use crate::features::agents::AgentCapabilities;
 use app_core::actor::traits::Message;
 use ogurpchik::codecs::base::HasAllocator;
 use ogurpchik::codecs::base::MessageCodec;
@@ -27,14 +28,21 @@ pub struct WslDistroDto {
     pub is_installed: bool,
     pub is_running: bool,
     pub latency_ms: i32,
+    pub agent_outdated: bool,
+    pub agent_gave_up: bool,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum AgentConnectionState {
     Disconnected,
     Connecting,
+    Handshaking,
     Connected,
-    WaitingRetry { delay_secs: u64 },
+    WaitingRetry { delay_ms: u64 },
+    // The agent answered with a protocol this build cannot talk to; it needs an update.
+    Incompatible { agent_version: u32 },
+    // The retry policy ran out of attempts; only a manual reconnect leaves this state.
+    GaveUp { attempts: u32 },
 }
 
 cfg_if::cfg_if! {
@@ -45,6 +53,8 @@ cfg_if::cfg_if! {
         pub struct WslAgentRuntimeEvent {
             pub state: AgentConnectionState,
             pub latency_ms: Option<i32>,
+            // Known once the handshake has completed.
+            pub capabilities: Option<AgentCapabilities>,
         }
         impl Message for WslAgentRuntimeEvent {}
 
@@ -52,13 +62,17 @@ cfg_if::cfg_if! {
         pub struct WindowsAgentRuntimeEvent {
             pub state: AgentConnectionState,
             pub latency_ms: Option<i32>,
+            // Known once the handshake has completed.
+            pub capabilities: Option<AgentCapabilities>,
         }
         impl Message for WindowsAgentRuntimeEvent {}
     } else {
-        #[derive(Clone)]
+        #[derive(Clone, Debug)]
         pub struct LinuxAgentRuntimeEvent {
             pub state: AgentConnectionState,
             pub latency_ms: Option<i32>,
+            // Known once the handshake has completed.
+            pub capabilities: Option<AgentCapabilities>,
         }
         impl Message for LinuxAgentRuntimeEvent {}
     }
@@ -1,6 +1,7 @@
 use macros::slint_port;
 
 use super::model::WslDistroDto;
+use crate::features::agents::{AgentHealth, ReplayStatus};
 
 #[slint_port(global = "EnvironmentsFeatureGlobal")]
 pub trait UiEnvironmentsPort: 'static {
@@ -8,6 +9,10 @@ pub trait UiEnvironmentsPort: 'static {
     fn set_host_icon_by_key(&self, icon_key: &str);
     #[manual]
     fn set_wsl_distros(&self, distros: Vec<WslDistroDto>);
+    #[manual]
+    fn set_replay_status(&self, status: ReplayStatus);
+    #[manual]
+    fn set_agent_health(&self, backends: Vec<AgentHealth>);
     fn set_host_name(&self, name: String);
     fn set_selected_env(&self, name: String);
     fn set_has_wsl(&self, has_wsl: bool);
@@ -18,6 +18,7 @@ pub trait L10nPort: Clone + 'static {
     fn set_services_pid(&self, value: String);
     fn set_services_properties(&self, value: String);
     fn set_services_restart(&self, value: String);
+    fn set_services_search_placeholder(&self, value: String);
     fn set_services_service_name(&self, value: String);
     fn set_services_start(&self, value: String);
     fn set_services_stop(&self, value: String);
@@ -13,14 +13,71 @@ pub trait UiProcessesBindings: 'static {
     where
         F: Fn(SharedString) + 'static;
 
+    #[tracing(target = "tree")]
     fn on_terminate<F>(&self, handler: F)
+    where
+        F: Fn(bool) + 'static;
+
+    fn on_suspend<F>(&self, handler: F)
+    where
+        F: Fn() + 'static;
+
+    fn on_resume<F>(&self, handler: F)
+    where
+        F: Fn() + 'static;
+
+    #[tracing(target = "source,pid")]
+    fn on_open_details<F>(&self, handler: F)
+    where
+        F: Fn(SharedString, i32) + 'static;
+
+    #[tracing(target = "source,pid")]
+    fn on_toggle_pin<F>(&self, handler: F)
+    where
+        F: Fn(SharedString, i32) + 'static;
+
+    #[tracing(target = "nice")]
+    fn on_set_priority<F>(&self, handler: F)
+    where
+        F: Fn(i32) + 'static;
+
+    #[tracing(target = "cpus")]
+    fn on_set_affinity<F>(&self, handler: F)
+    where
+        F: Fn(SharedString) + 'static;
+
+    #[tracing(target = "path")]
+    fn on_export<F>(&self, handler: F)
+    where
+        F: Fn(SharedString) + 'static;
+
+    #[tracing(target = "path")]
+    fn on_toggle_recording<F>(&self, handler: F)
+    where
+        F: Fn(SharedString) + 'static;
+
+    fn on_clear_alerts<F>(&self, handler: F)
+    where
+        F: Fn() + 'static;
+
+    #[tracing(target = "query")]
+    fn on_activity_filter_changed<F>(&self, handler: F)
+    where
+        F: Fn(SharedString) + 'static;
+
+    fn on_toggle_scope<F>(&self, handler: F)
     where
         F: Fn() + 'static;
 
-    #[tracing(target = "pid,idx")]
+    #[tracing(target = "source,pid,idx")]
     fn on_select_process<F>(&self, handler: F)
     where
-        F: Fn(i32, i32) + 'static;
+        F: Fn(SharedString, i32, i32) + 'static;
+
+    #[tracing(target = "source,pid,idx,range")]
+    fn on_extend_selection<F>(&self, handler: F)
+    where
+        F: Fn(SharedString, i32, i32, bool) + 'static;
 
     #[tracing(target = "start,count")]
     fn on_rows_viewport_changed<F>(&self, handler: F)
@@ -35,4 +92,19 @@ pub trait UiProcessesBindings: 'static {
     fn on_group_clicked<F>(&self, handler: F)
     where
         F: Fn() + 'static;
+
+    #[tracing(target = "query")]
+    fn on_filter_changed<F>(&self, handler: F)
+    where
+        F: Fn(SharedString) + 'static;
+
+    #[tracing(target = "id")]
+    fn on_toggle_column<F>(&self, handler: F)
+    where
+        F: Fn(SharedString) + 'static;
+
+    #[tracing(target = "id,delta")]
+    fn on_move_column<F>(&self, handler: F)
+    where
+        F: Fn(SharedString, i32) + 'static;
 }
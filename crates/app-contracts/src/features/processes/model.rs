@@ -1,4 +1,8 @@
+use app_core::actor::traits::Message;
 use slint::{Image, SharedString};
+use std::time::SystemTime;
+
+pub const DETAILS_WINDOW_KEY: &str = "process-details";
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct FieldDefDto {
@@ -18,6 +22,13 @@ pub struct FieldMetadata {
     pub is_metric: bool,
 }
 
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnChoiceDto {
+    pub id: SharedString,
+    pub label: SharedString,
+    pub visible: bool,
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct ProcessFieldDto {
     pub id: SharedString,
@@ -26,31 +37,148 @@ pub struct ProcessFieldDto {
     pub threshold: f32,
 }
 
+// Pids are only unique within one source, so rows are identified by both.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ProcessKey {
+    pub schema_id: &'static str,
+    pub pid: u32,
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct ProcessNodeDto {
     pub pid: u32,
+    pub schema_id: &'static str,
     pub name: SharedString,
     pub parent_pid: u32,
     pub exe_path: SharedString,
+    pub cmdline: SharedString,
     #[cfg(windows)]
     pub package_name: Option<SharedString>,
     pub fields: Vec<ProcessFieldDto>,
 }
 
+impl ProcessNodeDto {
+    pub fn key(&self) -> ProcessKey {
+        ProcessKey {
+            schema_id: self.schema_id,
+            pid: self.pid,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct ProcessEntryVm {
     pub pid: i32,
+    pub schema_id: &'static str,
     pub name: SharedString,
     pub icon: Image,
     pub depth: i32,
     pub has_children: bool,
     pub is_expanded: bool,
     pub is_dead: bool,
+    pub is_selected: bool,
+    // The pin pattern this row matched, if any.
+    pub pinned_by: Option<SharedString>,
+    // Set when the process has been a zombie or in D state for too long.
+    pub stuck: Option<SharedString>,
     pub fields: Vec<ProcessFieldDto>,
 }
 
+impl ProcessEntryVm {
+    pub fn key(&self) -> ProcessKey {
+        ProcessKey {
+            schema_id: self.schema_id,
+            pid: self.pid as u32,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MetricSeriesDto {
+    pub id: SharedString,
+    pub label: SharedString,
+    pub samples: Vec<f32>,
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct ProcessGroupVm {
     pub parent: ProcessEntryVm,
     pub children: Vec<ProcessEntryVm>,
 }
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProcessThreadDto {
+    pub tid: u32,
+    pub state: SharedString,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProcessDetailsDto {
+    pub pid: u32,
+    pub name: SharedString,
+    pub exe_path: SharedString,
+    pub cmdline: SharedString,
+    pub cwd: SharedString,
+    pub user: SharedString,
+    pub started: SharedString,
+    pub environment: Vec<SharedString>,
+    pub threads: Vec<ProcessThreadDto>,
+    pub open_files: Vec<SharedString>,
+    pub error: Option<SharedString>,
+}
+
+// Published whenever a user-defined alert rule fires for a process.
+#[derive(Clone, Debug)]
+pub struct AlertRaised {
+    pub rule: SharedString,
+    pub pid: u32,
+    pub schema_id: &'static str,
+    pub process_name: SharedString,
+    pub message: SharedString,
+    pub raised_at: SystemTime,
+}
+impl Message for AlertRaised {}
+
+// Published when a pid shows up in a source's snapshot. `previous_exe` is set when the pid was
+// already listed under another executable, i.e. after an exec or a pid reuse between scans.
+#[derive(Clone, Debug)]
+pub struct ProcessStarted {
+    pub pid: u32,
+    pub schema_id: &'static str,
+    pub name: SharedString,
+    pub exe_path: SharedString,
+    pub previous_exe: Option<SharedString>,
+    pub at: SystemTime,
+}
+impl Message for ProcessStarted {}
+
+// Published when a pid is missing from the next snapshot of its source.
+#[derive(Clone, Debug)]
+pub struct ProcessExited {
+    pub pid: u32,
+    pub schema_id: &'static str,
+    pub name: SharedString,
+    pub exe_path: SharedString,
+    pub at: SystemTime,
+}
+impl Message for ProcessExited {}
+
+#[derive(Clone, Debug)]
+pub enum ProcessActivity {
+    Started(ProcessStarted),
+    Exited(ProcessExited),
+}
+
+impl ProcessActivity {
+    pub fn matches(&self, query: &str) -> bool {
+        let (pid, name, exe_path) = match self {
+            Self::Started(e) => (e.pid, &e.name, &e.exe_path),
+            Self::Exited(e) => (e.pid, &e.name, &e.exe_path),
+        };
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || pid.to_string() == query
+            || name.to_lowercase().contains(&query)
+            || exe_path.to_lowercase().contains(&query)
+    }
+}
@@ -1,8 +1,22 @@
+use framework::native_windows::slint_factory::SlintWindowRegistry;
 use macros::slint_port;
 use slint::SharedString;
 use std::fmt::Debug;
 
-use super::model::{FieldDefDto, FieldMetadata, ProcessEntryVm};
+use super::model::{
+    AlertRaised, ColumnChoiceDto, FieldDefDto, FieldMetadata, MetricSeriesDto, ProcessActivity,
+    ProcessDetailsDto, ProcessEntryVm,
+};
+
+pub trait ProcessesWindowRegister {
+    fn register(&self, registry: &SlintWindowRegistry);
+}
+
+#[slint_port(global = "ProcessesFeatureGlobal")]
+pub trait UiProcessDetailsPort {
+    #[manual]
+    fn set_process_details(&self, details: ProcessDetailsDto);
+}
 
 #[slint_port(global = "ProcessesFeatureGlobal")]
 pub trait UiProcessesPort: Debug + 'static {
@@ -15,15 +29,33 @@ pub trait UiProcessesPort: Debug + 'static {
     #[manual]
     fn set_column_defs(&self, defs: Vec<FieldDefDto>);
     #[manual]
+    fn set_column_choices(&self, choices: Vec<ColumnChoiceDto>);
+    #[manual]
     fn get_selected_pid(&self) -> i32;
     #[manual]
+    fn get_selected_source(&self) -> SharedString;
+    #[manual]
     fn set_sort_state(&self, field: SharedString, descending: bool);
     #[manual]
     fn set_total_processes_count(&self, count: usize);
+    #[manual]
+    fn set_header_history(&self, series: Vec<MetricSeriesDto>);
+    #[manual]
+    fn set_selected_history(&self, series: Vec<MetricSeriesDto>);
+    #[manual]
+    fn set_alerts(&self, alerts: Vec<AlertRaised>);
+    #[manual]
+    fn set_activity(&self, activity: Vec<ProcessActivity>);
     fn set_empty_state_visible(&self, visible: bool);
     fn set_empty_state_title(&self, title: SharedString);
     fn set_empty_state_message(&self, message: SharedString);
     fn set_is_grouped(&self, is_grouped: bool);
+    fn set_tree_mode(&self, mode: SharedString);
     fn set_selected_pid(&self, pid: i32);
+    fn set_selected_source(&self, source: SharedString);
     fn set_selected_name(&self, name: SharedString);
+    fn set_action_status(&self, status: SharedString);
+    fn set_is_recording(&self, is_recording: bool);
+    fn set_is_scoped(&self, is_scoped: bool);
+    fn set_stuck_count(&self, count: i32);
 }
@@ -10,12 +10,24 @@ pub trait UiServicesBindings: 'static {
     fn on_service_action<F>(&self, handler: F)
     where
         F: Fn(SharedString, ServiceActionKind) + 'static;
+    #[manual]
+    #[tracing(target = "kind")]
+    fn on_selection_action<F>(&self, handler: F)
+    where
+        F: Fn(ServiceActionKind) + 'static;
     fn on_sort_by<F>(&self, handler: F)
+    where
+        F: Fn(SharedString) + 'static;
+    #[tracing(target = "query")]
+    fn on_filter_changed<F>(&self, handler: F)
     where
         F: Fn(SharedString) + 'static;
     fn on_select_service<F>(&self, handler: F)
     where
         F: Fn(SharedString, i32) + 'static;
+    fn on_extend_selection<F>(&self, handler: F)
+    where
+        F: Fn(SharedString, i32, bool) + 'static;
     fn on_rows_viewport_changed<F>(&self, handler: F)
     where
         F: Fn(i32, i32) + 'static;
@@ -28,4 +40,8 @@ pub trait UiServicesBindings: 'static {
     fn on_open_properties_window<F>(&self, handler: F)
     where
         F: Fn(ServiceEntryVm) + 'static;
+    #[tracing(target = "path")]
+    fn on_export<F>(&self, handler: F)
+    where
+        F: Fn(SharedString) + 'static;
 }
@@ -12,6 +12,7 @@ pub struct ServiceEntryVm {
     pub status: SharedString,
     pub group: SharedString,
     pub description: SharedString,
+    pub is_selected: bool,
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -33,6 +34,7 @@ impl From<ServiceEntryDto> for ServiceEntryVm {
             description: entry.description.clone().into(),
             group: entry.group.clone().into(),
             display_name: entry.display_name.clone().into(),
+            is_selected: false,
         }
     }
 }
@@ -29,4 +29,5 @@ pub trait UiServicesPort: Debug + UiServiceDetailsPort + 'static {
     fn set_current_sort(&self, field: SharedString);
     fn set_current_sort_descending(&self, descending: bool);
     fn set_total_services_count(&self, total_services_count: usize);
+    fn set_action_status(&self, status: SharedString);
 }
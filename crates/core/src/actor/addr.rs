@@ -117,6 +117,26 @@ impl<A: 'static> Addr<A> {
         move |arg1, arg2| addr.do_send(f(arg1, arg2))
     }
 
+    pub fn handler_with3<M, T1, T2, T3, F>(&self, f: F) -> impl Fn(T1, T2, T3) + 'static
+    where
+        F: Fn(T1, T2, T3) -> M + 'static,
+        M: Message,
+        A: Handler<M>,
+    {
+        let addr = self.clone();
+        move |arg1, arg2, arg3| addr.do_send(f(arg1, arg2, arg3))
+    }
+
+    pub fn handler_with4<M, T1, T2, T3, T4, F>(&self, f: F) -> impl Fn(T1, T2, T3, T4) + 'static
+    where
+        F: Fn(T1, T2, T3, T4) -> M + 'static,
+        M: Message,
+        A: Handler<M>,
+    {
+        let addr = self.clone();
+        move |arg1, arg2, arg3, arg4| addr.do_send(f(arg1, arg2, arg3, arg4))
+    }
+
     pub fn send<M>(&self, msg: M)
     where
         M: Message,
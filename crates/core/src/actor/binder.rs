@@ -49,6 +49,32 @@ impl<'p, A: 'static, P> UiBinder<'p, A, P> {
         self
     }
 
+    pub fn on3<M, T1: 'static, T2: 'static, T3: 'static>(
+        self,
+        reg: impl FnOnce(&P, Box<dyn Fn(T1, T2, T3) + 'static>),
+        ctor: impl Fn(T1, T2, T3) -> M + 'static,
+    ) -> Self
+    where
+        M: Message,
+        A: Handler<M>,
+    {
+        reg(self.port, Box::new(self.addr.handler_with3(ctor)));
+        self
+    }
+
+    pub fn on4<M, T1: 'static, T2: 'static, T3: 'static, T4: 'static>(
+        self,
+        reg: impl FnOnce(&P, Box<dyn Fn(T1, T2, T3, T4) + 'static>),
+        ctor: impl Fn(T1, T2, T3, T4) -> M + 'static,
+    ) -> Self
+    where
+        M: Message,
+        A: Handler<M>,
+    {
+        reg(self.port, Box::new(self.addr.handler_with4(ctor)));
+        self
+    }
+
     pub fn raw(self, f: impl FnOnce(&Addr<A>, &P)) -> Self {
         f(&self.addr, self.port);
         self
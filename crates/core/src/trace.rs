@@ -238,6 +238,41 @@ where
     ))
 }
 
+pub fn format_ui_target_3<A, B, C>(first: &A, second: &B, third: &C) -> Option<String>
+where
+    A: Debug + ?Sized,
+    B: Debug + ?Sized,
+    C: Debug + ?Sized,
+{
+    Some(format!(
+        "{} | {} | {}",
+        format_target_part(first),
+        format_target_part(second),
+        format_target_part(third)
+    ))
+}
+
+pub fn format_ui_target_4<A, B, C, D>(
+    first: &A,
+    second: &B,
+    third: &C,
+    fourth: &D,
+) -> Option<String>
+where
+    A: Debug + ?Sized,
+    B: Debug + ?Sized,
+    C: Debug + ?Sized,
+    D: Debug + ?Sized,
+{
+    Some(format!(
+        "{} | {} | {} | {}",
+        format_target_part(first),
+        format_target_part(second),
+        format_target_part(third),
+        format_target_part(fourth)
+    ))
+}
+
 pub struct MetaGuard {
     prev: Option<DispatchMeta>,
 }
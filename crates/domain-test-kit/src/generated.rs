@@ -7,3 +7,7 @@ include!(concat!(env!("OUT_DIR"), "/generated_stubs.rs"));
 impl ServicesWindowRegister for ServicesUiStub {
     fn register(&self, registry: &SlintWindowRegistry) {}
 }
+
+impl ProcessesWindowRegister for ProcessesUiStub {
+    fn register(&self, registry: &SlintWindowRegistry) {}
+}
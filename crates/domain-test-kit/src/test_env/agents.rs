@@ -0,0 +1,140 @@
+use crate::utils::{FeatureHarness, stabilize_ui};
+use app_contracts::features::agents::{AgentHealth, RemoteScanResult, ReportConsumed, ScanTick};
+use app_contracts::features::environments::AgentConnectionState;
+use app_core::actor::ManagedActor;
+use app_core::actor::addr::Addr;
+use app_core::actor::event_bus::EventBus;
+use domain_agents::features::agents::providers::mock::{
+    MockAgent, MockAgentFeature, MockRuntimeEvent,
+};
+use domain_agents::features::agents::session::RecordedEvent;
+use framework::feature::{AppFeature, AppFeatureInitContext};
+use framework::lifecycle_tracker::FeatureLifecycle;
+use macros::{actor_manifest, handler};
+use std::sync::{Arc, Mutex};
+use uniproc_protocol::LinuxMachineStats;
+
+// Records what the mock agent publishes: connection states, reports and its latest health.
+// Stands in for the process list, so it also acknowledges the reports it takes in.
+#[derive(Clone, Default)]
+pub struct AgentProbe {
+    states: Arc<Mutex<Vec<AgentConnectionState>>>,
+    reports: Arc<Mutex<Vec<RemoteScanResult>>>,
+    health: Arc<Mutex<Option<AgentHealth>>>,
+    // Set while the probe plays a UI that has fallen behind; holds the unacknowledged schemas.
+    held: Arc<Mutex<Option<Vec<&'static str>>>>,
+}
+
+impl AgentProbe {
+    pub fn states(&self) -> Vec<AgentConnectionState> {
+        self.states.lock().unwrap().clone()
+    }
+
+    pub fn last_state(&self) -> Option<AgentConnectionState> {
+        self.states.lock().unwrap().last().copied()
+    }
+
+    pub fn reports(&self) -> Vec<RemoteScanResult> {
+        self.reports.lock().unwrap().clone()
+    }
+
+    pub fn health(&self) -> Option<AgentHealth> {
+        self.health.lock().unwrap().clone()
+    }
+
+    // Stops acknowledging reports until `consume` is called.
+    pub fn hold_reports(&self) {
+        self.held.lock().unwrap().get_or_insert_with(Vec::new);
+    }
+
+    // Acknowledges the reports held so far and keeps holding the ones after them.
+    pub fn consume(&self) {
+        let held = self
+            .held
+            .lock()
+            .unwrap()
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default();
+        for schema_id in held {
+            EventBus::publish(ReportConsumed { schema_id });
+        }
+        stabilize_ui();
+    }
+}
+
+pub struct AgentProbeActor {
+    probe: AgentProbe,
+}
+
+#[actor_manifest]
+impl ManagedActor for AgentProbeActor {
+    type Bus = bus!(MockRuntimeEvent, RemoteScanResult, AgentHealth);
+    type Handlers = handlers!(@MockRuntimeEvent, @RemoteScanResult, @AgentHealth);
+}
+
+#[handler]
+fn record_state(this: &mut AgentProbeActor, msg: MockRuntimeEvent) {
+    // Every ping republishes the state; only changes are kept.
+    let mut states = this.probe.states.lock().unwrap();
+    if states.last() != Some(&msg.state) {
+        states.push(msg.state);
+    }
+}
+
+#[handler]
+fn record_report(this: &mut AgentProbeActor, msg: RemoteScanResult) {
+    let schema_id = msg.schema_id;
+    this.probe.reports.lock().unwrap().push(msg);
+    match this.probe.held.lock().unwrap().as_mut() {
+        Some(held) => held.push(schema_id),
+        None => EventBus::publish(ReportConsumed { schema_id }),
+    }
+}
+
+#[handler]
+fn record_health(this: &mut AgentProbeActor, msg: AgentHealth) {
+    *this.probe.health.lock().unwrap() = Some(msg);
+}
+
+pub struct AgentProbeFeature(pub AgentProbe);
+
+impl AppFeature for AgentProbeFeature {
+    fn install(self, ctx: &mut AppFeatureInitContext) -> anyhow::Result<()> {
+        let _ = Addr::new_managed(
+            AgentProbeActor { probe: self.0 },
+            ctx.token.clone(),
+            &FeatureLifecycle::new(),
+        );
+        Ok(())
+    }
+}
+
+impl FeatureHarness {
+    // Needs the settings feature; the probe goes first so it sees the initial state.
+    pub fn mock_agent(self, agent: &MockAgent) -> anyhow::Result<(Self, AgentProbe)> {
+        let probe = AgentProbe::default();
+        let h = self
+            .app_feature(AgentProbeFeature(probe.clone()))?
+            .app_feature(MockAgentFeature {
+                agent: agent.clone(),
+            })?;
+        Ok((h, probe))
+    }
+}
+
+// A report without processes; tests tell reports apart by their schema id.
+pub fn empty_report(schema_id: &str) -> RecordedEvent {
+    RecordedEvent::Remote {
+        schema_id: schema_id.to_owned(),
+        processes: Vec::new(),
+        machine: LinuxMachineStats::default(),
+        environments: Vec::new(),
+        docker_containers: Vec::new(),
+    }
+}
+
+pub fn scan_tick() {
+    EventBus::publish(ScanTick);
+    stabilize_ui();
+}
@@ -1 +1,3 @@
+pub mod agents;
 pub mod navigation;
+pub mod processes;
@@ -0,0 +1,85 @@
+use crate::generated::ProcessesUiStub;
+use crate::utils::{DomainTestWindow, FeatureHarness};
+use app_contracts::features::processes::{ProcessExited, ProcessStarted};
+use app_core::actor::ManagedActor;
+use app_core::actor::addr::Addr;
+use domain_processes::features::processes::ProcessFeature;
+use framework::feature::{AppFeature, AppFeatureInitContext};
+use framework::lifecycle_tracker::FeatureLifecycle;
+use macros::{actor_manifest, handler};
+use slint::{ComponentHandle, SharedString};
+use std::sync::{Arc, Mutex};
+
+// Records the activity the snapshot pipeline diffs out of consecutive scans.
+#[derive(Clone, Default)]
+pub struct ActivityProbe {
+    started: Arc<Mutex<Vec<ProcessStarted>>>,
+    exited: Arc<Mutex<Vec<ProcessExited>>>,
+}
+
+impl ActivityProbe {
+    pub fn started(&self) -> Vec<ProcessStarted> {
+        self.started.lock().unwrap().clone()
+    }
+
+    pub fn exited(&self) -> Vec<ProcessExited> {
+        self.exited.lock().unwrap().clone()
+    }
+}
+
+pub struct ActivityProbeActor {
+    probe: ActivityProbe,
+}
+
+#[actor_manifest]
+impl ManagedActor for ActivityProbeActor {
+    type Bus = bus!(ProcessStarted, ProcessExited);
+    type Handlers = handlers!(@ProcessStarted, @ProcessExited);
+}
+
+#[handler]
+fn record_started(this: &mut ActivityProbeActor, msg: ProcessStarted) {
+    this.probe.started.lock().unwrap().push(msg);
+}
+
+#[handler]
+fn record_exited(this: &mut ActivityProbeActor, msg: ProcessExited) {
+    this.probe.exited.lock().unwrap().push(msg);
+}
+
+pub struct ActivityProbeFeature(pub ActivityProbe);
+
+impl AppFeature for ActivityProbeFeature {
+    fn install(self, ctx: &mut AppFeatureInitContext) -> anyhow::Result<()> {
+        let _ = Addr::new_managed(
+            ActivityProbeActor { probe: self.0 },
+            ctx.token.clone(),
+            &FeatureLifecycle::new(),
+        );
+        Ok(())
+    }
+}
+
+impl FeatureHarness {
+    // Needs the settings, page status and window manager features; spawns the test window.
+    pub fn processes(self, port: &ProcessesUiStub) -> anyhow::Result<(Self, ActivityProbe)> {
+        port.on_get_selected_pid(|| -1);
+        port.on_get_selected_source(SharedString::default);
+
+        let probe = ActivityProbe::default();
+        let port = port.clone();
+        let mut h = self
+            .app_feature(ActivityProbeFeature(probe.clone()))?
+            .window_feature(move || {
+                let port = port.clone();
+                ProcessFeature::new(move |_: &DomainTestWindow| port.clone())
+            });
+
+        let ui_handle = h.0.as_ref().unwrap().ui().clone_strong();
+        h.0.as_mut()
+            .unwrap()
+            .spawn_window(ui_handle)
+            .expect("Failed to spawn window");
+        Ok((h, probe))
+    }
+}
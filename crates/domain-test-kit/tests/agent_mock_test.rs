@@ -0,0 +1,330 @@
+use app_contracts::features::agents::{AgentHello, PROTOCOL_VERSION, Reconnect};
+use app_contracts::features::environments::AgentConnectionState::*;
+use app_core::actor::event_bus::EventBus;
+use domain_agents::features::agents::providers::mock::MockAgent;
+use domain_test_kit::test_env::agents::{AgentProbe, empty_report, scan_tick};
+use domain_test_kit::utils::{
+    FeatureHarness, TestTrace, pump_ui, stabilize_ui, temp_settings_path,
+};
+use rstest::{fixture, rstest};
+use serial_test::serial;
+
+#[fixture]
+fn h() -> FeatureHarness {
+    let temp_path = temp_settings_path();
+
+    FeatureHarness::new(temp_path.clone())
+        .app_feature(domain::features::settings::SettingsFeature::with_path(
+            temp_path,
+        ))
+        .unwrap()
+}
+
+#[rstest]
+#[serial]
+fn test_agent_retries_refused_connect_and_recovers_from_drop(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let agent = MockAgent::new();
+    agent.fail_connects(1);
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    stabilize_ui();
+
+    let states = probe.states();
+    assert!(
+        matches!(
+            states[..],
+            [
+                Disconnected,
+                Connecting,
+                WaitingRetry { .. },
+                Connecting,
+                Handshaking,
+                Connected
+            ]
+        ),
+        "A refused connect MUST be retried, got {states:?}"
+    );
+    assert_eq!(agent.stats().connects, 2);
+
+    agent.drop_connection();
+    pump_ui(2000);
+
+    assert!(
+        probe
+            .states()
+            .ends_with(&[Connected, Disconnected, Connecting, Handshaking, Connected]),
+        "A failed ping MUST reconnect, got {:?}",
+        probe.states()
+    );
+}
+
+#[rstest]
+#[serial]
+fn test_malformed_report_is_dropped_without_losing_connection(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let agent = MockAgent::new();
+    agent.push_malformed(b"not a report");
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    stabilize_ui();
+    TestTrace::clear();
+
+    scan_tick();
+
+    assert!(TestTrace::contains("malformed report"));
+    assert!(probe.reports().is_empty());
+    assert_eq!(agent.stats().scans, 1);
+    assert_eq!(probe.last_state(), Some(Connected));
+}
+
+#[rstest]
+#[serial]
+fn test_empty_and_truncated_frames_count_as_malformed(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let agent = MockAgent::new();
+    agent.push_malformed(&[]);
+    agent.push_malformed(&[0xff; 3]);
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    stabilize_ui();
+
+    scan_tick();
+    scan_tick();
+    pump_ui(2000);
+
+    let health = probe
+        .health()
+        .expect("The mock agent MUST publish its health");
+    assert_eq!((health.scans, health.malformed_reports), (2, 2));
+    assert_eq!(health.scan_failures, 0);
+    assert!(probe.reports().is_empty());
+    assert_eq!(probe.last_state(), Some(Connected));
+}
+
+fn schemas(probe: &AgentProbe) -> Vec<&'static str> {
+    probe.reports().iter().map(|r| r.schema_id).collect()
+}
+
+#[rstest]
+#[serial]
+fn test_streamed_reports_wait_for_the_ui_and_keep_their_order(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let agent = MockAgent::new();
+    agent.enable_streaming();
+    agent.push(&empty_report("first")).unwrap();
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    probe.hold_reports();
+    stabilize_ui();
+
+    agent.push(&empty_report("second")).unwrap();
+    stabilize_ui();
+    assert_eq!(
+        schemas(&probe),
+        ["first"],
+        "A report MUST wait until the UI has taken in the previous one"
+    );
+
+    probe.consume();
+    agent.push(&empty_report("third")).unwrap();
+    probe.consume();
+
+    assert_eq!(schemas(&probe), ["first", "second", "third"]);
+    assert_eq!(
+        agent.stats().scans,
+        0,
+        "A streaming agent MUST NOT be polled"
+    );
+}
+
+#[rstest]
+#[serial]
+fn test_streamed_reports_behind_a_slow_ui_are_coalesced(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let agent = MockAgent::new();
+    agent.enable_streaming();
+    agent.push(&empty_report("first")).unwrap();
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    probe.hold_reports();
+    stabilize_ui();
+
+    for schema in ["second", "third", "fourth"] {
+        agent.push(&empty_report(schema)).unwrap();
+    }
+    probe.consume();
+
+    assert_eq!(
+        schemas(&probe),
+        ["first", "fourth"],
+        "Only the latest report queued behind the UI MUST be published"
+    );
+
+    pump_ui(2000);
+    let health = probe
+        .health()
+        .expect("The mock agent MUST publish its health");
+    assert_eq!(health.scans, 4, "Skipped reports MUST still be counted");
+    assert!(health.scan_bytes.is_some());
+    assert_eq!(health.scan_p50_ms, None);
+}
+
+#[rstest]
+#[serial]
+fn test_ended_stream_falls_back_to_polling(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let agent = MockAgent::new();
+    agent.enable_streaming();
+    agent.push(&empty_report("streamed")).unwrap();
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    probe.hold_reports();
+    stabilize_ui();
+
+    agent.end_stream();
+    probe.consume();
+    agent.push(&empty_report("polled")).unwrap();
+    scan_tick();
+
+    assert_eq!(schemas(&probe), ["streamed", "polled"]);
+    assert_eq!(agent.stats().scans, 1);
+    assert_eq!(probe.last_state(), Some(Connected));
+}
+
+#[rstest]
+#[serial]
+fn test_health_counts_malformed_reports_and_reconnects(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let agent = MockAgent::new();
+    agent.push_malformed(b"not a report");
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    stabilize_ui();
+
+    scan_tick();
+    agent.drop_connection();
+    pump_ui(2000);
+
+    let health = probe
+        .health()
+        .expect("The mock agent MUST publish its health");
+    assert_eq!(health.backend, "Mock");
+    assert_eq!((health.scans, health.malformed_reports), (1, 1));
+    assert_eq!(health.reconnects, 1);
+    assert!(health.ping_p50_ms.is_some());
+}
+
+#[rstest]
+#[serial]
+fn test_incompatible_agent_waits_for_update(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let agent = MockAgent::new();
+    let mut hello = AgentHello::legacy();
+    hello.protocol_version = PROTOCOL_VERSION + 1;
+    agent.set_hello(hello);
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    stabilize_ui();
+
+    assert_eq!(
+        probe.last_state(),
+        Some(Incompatible {
+            agent_version: PROTOCOL_VERSION + 1
+        })
+    );
+    assert_eq!(
+        agent.stats().connects,
+        1,
+        "An outdated agent MUST NOT be retried"
+    );
+
+    agent.set_hello(AgentHello {
+        protocol_version: PROTOCOL_VERSION,
+        ..AgentHello::legacy()
+    });
+    EventBus::publish(Reconnect { backend: "Mock" });
+    stabilize_ui();
+
+    assert_eq!(probe.last_state(), Some(Connected));
+}
+
+#[rstest]
+#[serial]
+fn test_agent_without_handshake_must_be_updated(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let agent = MockAgent::new();
+    agent.set_hello(AgentHello::legacy());
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    stabilize_ui();
+
+    assert_eq!(
+        probe.last_state(),
+        Some(Incompatible { agent_version: 1 }),
+        "Protocol 1 reports no longer decode"
+    );
+}
+
+#[rstest]
+#[serial]
+fn test_reconnect_of_another_backend_keeps_the_session(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let agent = MockAgent::new();
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    stabilize_ui();
+
+    EventBus::publish(Reconnect { backend: "WSL" });
+    stabilize_ui();
+
+    assert_eq!(probe.last_state(), Some(Connected));
+    assert_eq!(
+        agent.stats().connects,
+        1,
+        "A reconnect of another backend MUST NOT drop this session"
+    );
+}
+
+#[rstest]
+#[serial]
+fn test_agent_gives_up_after_max_attempts_until_reconnect() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let temp_path = temp_settings_path();
+    std::fs::write(
+        &temp_path,
+        r#"{"agents":{"connect_retry":{"base-secs":0.01,"jitter":0,"max-attempts":2}}}"#,
+    )
+    .unwrap();
+    let h = FeatureHarness::new(temp_path.clone())
+        .app_feature(domain::features::settings::SettingsFeature::with_path(
+            temp_path,
+        ))
+        .unwrap();
+
+    let agent = MockAgent::new();
+    agent.fail_connects(u32::MAX);
+    let (_h, probe) = h.mock_agent(&agent).unwrap();
+    stabilize_ui();
+
+    assert_eq!(probe.last_state(), Some(GaveUp { attempts: 2 }));
+    assert_eq!(agent.stats().connects, 2);
+
+    agent.fail_connects(0);
+    EventBus::publish(Reconnect { backend: "Mock" });
+    stabilize_ui();
+
+    assert_eq!(probe.last_state(), Some(Connected));
+}
@@ -0,0 +1,82 @@
+// Drives the built-in /proc scanner, the only source that needs no agent.
+#![cfg(target_os = "linux")]
+
+use domain::features::page_status::PageStatusFeature;
+use domain::features::windows_manager::WindowManagerFeature;
+use domain_test_kit::generated::*;
+use domain_test_kit::test_env::agents::scan_tick;
+use domain_test_kit::utils::{FeatureHarness, temp_settings_path};
+use rstest::{fixture, rstest};
+use serial_test::serial;
+use std::process::Command;
+
+#[fixture]
+fn h() -> FeatureHarness {
+    let temp_path = temp_settings_path();
+
+    FeatureHarness::new(temp_path.clone())
+        .app_feature(domain::features::settings::SettingsFeature::with_path(
+            temp_path,
+        ))
+        .unwrap()
+        .app_feature(PageStatusFeature)
+        .unwrap()
+        .app_feature(WindowManagerFeature)
+        .unwrap()
+}
+
+#[rstest]
+#[serial]
+fn test_local_scan_reaches_the_process_table(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let port = ProcessesUiStub::new();
+    let (mut h, probe) = h.processes(&port).unwrap();
+
+    scan_tick();
+
+    let updates = port
+        .set_total_processes_count_call_count()
+        .stabilize(&mut h);
+    assert!(updates > 0, "A scan MUST reach the process table");
+    assert!(
+        probe.started().is_empty(),
+        "The first snapshot of a source MUST NOT be logged as activity"
+    );
+}
+
+#[rstest]
+#[serial]
+fn test_consecutive_scans_log_started_and_exited_processes(h: FeatureHarness) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let port = ProcessesUiStub::new();
+    let (_h, probe) = h.processes(&port).unwrap();
+    scan_tick();
+
+    let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+    let pid = child.id();
+    scan_tick();
+
+    assert!(
+        probe
+            .started()
+            .iter()
+            .any(|p| p.pid == pid && p.schema_id == "local"),
+        "A process between two scans MUST be logged as started"
+    );
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    scan_tick();
+
+    assert!(
+        probe
+            .exited()
+            .iter()
+            .any(|p| p.pid == pid && p.schema_id == "local"),
+        "A reaped process MUST be logged as exited"
+    );
+}
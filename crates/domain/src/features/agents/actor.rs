@@ -1,42 +1,108 @@
-use super::backend::AgentBackend;
+use super::backend::{AgentBackend, MalformedReport, ReportStream};
+use crate::agents_impl::health::HealthStats;
+use crate::agents_impl::session::RecordedEvent;
 use crate::features::agents::connection::*;
-use app_contracts::features::agents::ScanTick;
+use crate::features::agents::settings::{AgentSettings, RetryPolicy};
+use app_contracts::features::agents::{
+    AgentCapabilities, AgentHealth, AgentHello, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION, Reconnect,
+    ReportConsumed, ScanTick,
+};
 use app_contracts::features::environments::AgentConnectionState;
 use app_core::actor::event_bus::EventBus;
-use app_core::actor::{Context, Message, NoOp};
+use app_core::actor::{Context, Message};
 use app_core::messages;
 use framework::settings::ReactiveSetting;
 use macros::handler;
 use std::fmt::Debug;
-use tracing::{info, warn};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 
 messages! {
     Init,
     Ping,
     StartConnect,
-    TryConnectWithDelay(u64),
-    RetryTimerElapsed,
+    TryConnectWithDelay { delay_ms: u64, attempt: u64 },
+    RetryTimerElapsed(u64),
     ConnectionLost,
-    PingResult(Option<i32>)
+    PingResult { attempt: u64, latency_ms: Option<i32> }
 }
 
-struct ConnectResult<C>(Option<C>);
+struct ConnectResult<C> {
+    attempt: u64,
+    client: Option<C>,
+}
 impl<C: Send + 'static> Message for ConnectResult<C> {}
 
+struct HandshakeResult<C> {
+    attempt: u64,
+    client: C,
+    hello: Option<AgentHello>,
+}
+impl<C: Send + 'static> Message for HandshakeResult<C> {}
+
+struct SubscribeResult {
+    epoch: u64,
+    stream: Option<ReportStream>,
+}
+impl Message for SubscribeResult {}
+
+// The stream travels with its frame, so at most one report waits on the UI at a time.
+struct StreamFrame {
+    epoch: u64,
+    next: Option<(RecordedEvent, Vec<Option<usize>>)>,
+    stream: ReportStream,
+}
+impl Message for StreamFrame {}
+
+enum ScanOutcome {
+    Done { bytes: Option<usize> },
+    Malformed,
+    Failed,
+}
+
+struct ScanFinished {
+    elapsed: Duration,
+    outcome: ScanOutcome,
+}
+impl Message for ScanFinished {}
+
 pub struct GenericAgentActor<B: AgentBackend> {
     client: Option<B::Client>,
     connection: ConnectionMachine,
     ping_in_flight: bool,
+    capabilities: Option<AgentCapabilities>,
+    streaming: bool,
+    // Bumped per connection so frames from a stream of a dropped connection are ignored.
+    stream_epoch: u64,
+    // The source of a streamed report the UI has not taken in yet, and the stream held back
+    // until it has.
+    unconsumed: Option<(String, ReportStream)>,
+    // Bumped by Reconnect and on a lost connection, so results of an abandoned attempt or
+    // session are ignored.
+    attempt: u64,
+    health: HealthStats,
     connect_timeout_secs: ReactiveSetting<u64>,
+    connect_retry: ReactiveSetting<RetryPolicy>,
+    reconnect_retry: ReactiveSetting<RetryPolicy>,
 }
 
 impl<B: AgentBackend> GenericAgentActor<B> {
-    pub fn new(connect_timeout_secs: ReactiveSetting<u64>) -> Self {
+    pub fn new(settings: &AgentSettings) -> Self {
+        let connect_retry = settings.connect_retry();
+        let reconnect_retry = settings.reconnect_retry();
         Self {
             client: None,
-            connection: ConnectionMachine::new(),
+            connection: ConnectionMachine::new(connect_retry.get(), reconnect_retry.get()),
             ping_in_flight: false,
-            connect_timeout_secs,
+            capabilities: None,
+            streaming: false,
+            stream_epoch: 0,
+            unconsumed: None,
+            attempt: 0,
+            health: HealthStats::default(),
+            connect_timeout_secs: settings.connect_timeout_secs(),
+            connect_retry,
+            reconnect_retry,
         }
     }
 
@@ -56,24 +122,118 @@ impl<B: AgentBackend> GenericAgentActor<B> {
     }
 
     fn publish_state(&self, latency_ms: Option<i32>) {
-        let event = B::create_runtime_event(self.connection.state(), latency_ms);
+        let event = B::create_runtime_event(self.connection.state(), latency_ms, self.capabilities);
         EventBus::publish(event);
     }
 
+    fn publish_health(&self) {
+        if EventBus::has_subscribers::<AgentHealth>() {
+            EventBus::publish(self.health.snapshot(B::NAME, self.connection.state()));
+        }
+    }
+
     fn spawn_connect(&self, ctx: &Context<Self>) {
         let timeout = self.connect_timeout_secs.get().max(1);
+        let attempt = self.attempt;
         ctx.spawn_bg(async move {
-            match B::connect(timeout).await {
-                Ok(client) => ConnectResult(Some(client)),
+            let client = match B::connect(timeout).await {
+                Ok(client) => Some(client),
                 Err(err) => {
                     warn!("[{}] Connect failed: {err}", B::NAME);
-                    ConnectResult(None)
+                    None
                 }
+            };
+            ConnectResult { attempt, client }
+        });
+    }
+
+    fn spawn_handshake(&self, client: B::Client, ctx: &Context<Self>) {
+        let attempt = self.attempt;
+        ctx.spawn_bg(async move {
+            let hello = match B::handshake(&client).await {
+                Ok(hello) => Some(hello),
+                Err(err) => {
+                    warn!("[{}] Handshake failed: {err}", B::NAME);
+                    None
+                }
+            };
+            HandshakeResult {
+                attempt,
+                client,
+                hello,
+            }
+        });
+    }
+
+    fn connect_failed(&mut self, ctx: &Context<Self>) {
+        self.connection
+            .set_policies(self.connect_retry.get(), self.reconnect_retry.get());
+        if let Some(t) = self.apply(ConnectionEvent::ConnectFailed) {
+            self.client = None;
+            self.publish_state(None);
+            match t.effect {
+                TransitionEffect::ScheduleRetry { delay_ms } => {
+                    ctx.addr().send(TryConnectWithDelay {
+                        delay_ms,
+                        attempt: self.attempt,
+                    });
+                }
+                TransitionEffect::None => {
+                    warn!("[{}] Giving up after {:?}", B::NAME, t.to);
+                }
+            }
+        }
+    }
+
+    fn drop_session(&mut self) {
+        self.client = None;
+        self.ping_in_flight = false;
+        self.capabilities = None;
+        self.streaming = false;
+        self.stream_epoch += 1;
+        self.unconsumed = None;
+    }
+
+    fn spawn_subscribe(&self, client: B::Client, ctx: &Context<Self>) {
+        let epoch = self.stream_epoch;
+        ctx.spawn_bg(async move {
+            let stream = match B::subscribe(&client).await {
+                Ok(stream) => stream,
+                Err(err) => {
+                    warn!("[{}] Subscribe failed, polling instead: {err}", B::NAME);
+                    None
+                }
+            };
+            SubscribeResult { epoch, stream }
+        });
+    }
+
+    fn pump(&self, mut stream: ReportStream, ctx: &Context<Self>) {
+        let epoch = self.stream_epoch;
+        ctx.spawn_bg(async move {
+            let next = next_latest(&mut stream).await;
+            StreamFrame {
+                epoch,
+                next,
+                stream,
             }
         });
     }
 }
 
+// Waits for a report and skips the ones queued behind it; returns the latest along with the
+// frame sizes of every report read, the skipped ones first.
+async fn next_latest(stream: &mut ReportStream) -> Option<(RecordedEvent, Vec<Option<usize>>)> {
+    let first = stream.recv().await?;
+    let mut latest = first.event;
+    let mut frames = vec![first.bytes];
+    while let Ok(newer) = stream.try_recv() {
+        latest = newer.event;
+        frames.push(newer.bytes);
+    }
+    Some((latest, frames))
+}
+
 #[handler]
 fn init<B: AgentBackend>(
     this: &mut GenericAgentActor<B>,
@@ -105,34 +265,144 @@ fn on_connect_result<B: AgentBackend>(
     msg: ConnectResult<B::Client>,
     ctx: &Context<GenericAgentActor<B>>,
 ) {
-    match msg.0 {
+    if msg.attempt != this.attempt {
+        return;
+    }
+    match msg.client {
         Some(client) => {
             if this.apply(ConnectionEvent::ConnectSucceeded).is_some() {
-                info!("[{}] Connected", B::NAME);
-                this.client = Some(client);
-                this.ping_in_flight = false;
                 this.publish_state(None);
-                ctx.addr().send(Ping);
+                this.spawn_handshake(client, ctx);
             }
         }
-        None => {
-            if let Some(t) = this.apply(ConnectionEvent::ConnectFailed) {
-                this.client = None;
-                this.publish_state(None);
-                if let TransitionEffect::ScheduleRetry { delay_secs } = t.effect {
-                    ctx.addr().send(TryConnectWithDelay(delay_secs));
-                }
+        None => this.connect_failed(ctx),
+    }
+}
+
+#[handler]
+fn on_handshake_result<B: AgentBackend>(
+    this: &mut GenericAgentActor<B>,
+    msg: HandshakeResult<B::Client>,
+    ctx: &Context<GenericAgentActor<B>>,
+) {
+    if msg.attempt != this.attempt {
+        return;
+    }
+    let Some(hello) = msg.hello else {
+        this.connect_failed(ctx);
+        return;
+    };
+
+    if !hello.is_compatible() {
+        let agent_version = hello.protocol_version;
+        if this
+            .apply(ConnectionEvent::IncompatibleAgent { agent_version })
+            .is_some()
+        {
+            warn!(
+                "[{}] Agent speaks protocol {agent_version}, \
+                 this build needs {MIN_PROTOCOL_VERSION}..={PROTOCOL_VERSION}",
+                B::NAME
+            );
+            this.publish_state(None);
+        }
+        return;
+    }
+
+    if this.apply(ConnectionEvent::HandshakeSucceeded).is_some() {
+        info!(
+            "[{}] Connected, protocol {}",
+            B::NAME,
+            hello.protocol_version
+        );
+        this.client = Some(msg.client.clone());
+        this.ping_in_flight = false;
+        this.capabilities = Some(hello.capabilities);
+        this.stream_epoch += 1;
+        this.unconsumed = None;
+        this.publish_state(None);
+        if hello.capabilities.streaming {
+            this.spawn_subscribe(msg.client, ctx);
+        }
+        ctx.addr().send(Ping);
+    }
+}
+
+#[handler]
+fn on_subscribe_result<B: AgentBackend>(
+    this: &mut GenericAgentActor<B>,
+    msg: SubscribeResult,
+    ctx: &Context<GenericAgentActor<B>>,
+) {
+    if msg.epoch != this.stream_epoch {
+        return;
+    }
+    if let Some(stream) = msg.stream {
+        info!("[{}] Streaming reports", B::NAME);
+        this.streaming = true;
+        this.pump(stream, ctx);
+    }
+}
+
+#[handler]
+fn on_stream_frame<B: AgentBackend>(
+    this: &mut GenericAgentActor<B>,
+    msg: StreamFrame,
+    ctx: &Context<GenericAgentActor<B>>,
+) {
+    if msg.epoch != this.stream_epoch {
+        return;
+    }
+    match msg.next {
+        Some((report, frames)) => {
+            for bytes in &frames {
+                this.health.record_streamed(*bytes);
+            }
+            let skipped = frames.len() - 1;
+            if skipped > 0 {
+                debug!("[{}] Skipped {skipped} stale reports", B::NAME);
             }
+            let source = report.source().to_owned();
+            let consumed_later = report.has_consumers();
+            report.publish();
+            if consumed_later {
+                this.unconsumed = Some((source, msg.stream));
+            } else {
+                this.pump(msg.stream, ctx);
+            }
+        }
+        None => {
+            warn!("[{}] Report stream closed, polling instead", B::NAME);
+            this.streaming = false;
         }
     }
 }
 
+#[handler]
+fn on_report_consumed<B: AgentBackend>(
+    this: &mut GenericAgentActor<B>,
+    msg: ReportConsumed,
+    ctx: &Context<GenericAgentActor<B>>,
+) {
+    let Some((source, _)) = &this.unconsumed else {
+        return;
+    };
+    if source != msg.schema_id {
+        return;
+    }
+    if let Some((_, stream)) = this.unconsumed.take() {
+        this.pump(stream, ctx);
+    }
+}
+
 #[handler]
 fn ping<B: AgentBackend>(
     this: &mut GenericAgentActor<B>,
     _: Ping,
     ctx: &Context<GenericAgentActor<B>>,
 ) {
+    this.publish_health();
+
     let has_subs = EventBus::has_subscribers::<B::RuntimeEvent>();
     if !has_subs {
         return;
@@ -145,13 +415,18 @@ fn ping<B: AgentBackend>(
         return;
     };
     this.ping_in_flight = true;
+    let attempt = this.attempt;
     ctx.spawn_bg(async move {
-        match B::ping(&client).await {
-            Ok(ms) => PingResult(Some(ms)),
+        let latency_ms = match B::ping(&client).await {
+            Ok(ms) => Some(ms),
             Err(err) => {
                 warn!("[{}] Ping failed: {err}", B::NAME);
-                PingResult(None)
+                None
             }
+        };
+        PingResult {
+            attempt,
+            latency_ms,
         }
     });
 }
@@ -162,12 +437,15 @@ fn on_ping_result<B: AgentBackend>(
     msg: PingResult,
     ctx: &Context<GenericAgentActor<B>>,
 ) {
-    if !this.ping_in_flight {
+    if msg.attempt != this.attempt || !this.ping_in_flight {
         return;
     }
     this.ping_in_flight = false;
-    match msg.0 {
-        Some(ms) => this.publish_state(Some(ms)),
+    match msg.latency_ms {
+        Some(ms) => {
+            this.health.record_ping(ms);
+            this.publish_state(Some(ms));
+        }
         None => ctx.addr().send(ConnectionLost),
     }
 }
@@ -178,7 +456,7 @@ fn perform_scan_tick<B: AgentBackend>(
     _: ScanTick,
     ctx: &Context<GenericAgentActor<B>>,
 ) {
-    if !matches!(this.connection.state(), AgentConnectionState::Connected) {
+    if this.streaming || !matches!(this.connection.state(), AgentConnectionState::Connected) {
         return;
     }
 
@@ -188,33 +466,78 @@ fn perform_scan_tick<B: AgentBackend>(
     };
 
     ctx.spawn_bg(async move {
-        if let Err(err) = B::perform_scan(&client).await {
-            warn!("[{}] Scan failed: {err}", B::NAME);
+        let started = Instant::now();
+        let outcome = match B::perform_scan(&client).await {
+            Ok(bytes) => ScanOutcome::Done { bytes },
+            Err(err) => {
+                warn!("[{}] Scan failed: {err}", B::NAME);
+                if err.is::<MalformedReport>() {
+                    ScanOutcome::Malformed
+                } else {
+                    ScanOutcome::Failed
+                }
+            }
+        };
+        ScanFinished {
+            elapsed: started.elapsed(),
+            outcome,
         }
-        NoOp
     });
 }
 
+#[handler]
+fn on_scan_finished<B: AgentBackend>(this: &mut GenericAgentActor<B>, msg: ScanFinished) {
+    match msg.outcome {
+        ScanOutcome::Done { bytes } => this.health.record_scan(msg.elapsed, bytes),
+        ScanOutcome::Malformed => this.health.record_scan_failure(true),
+        ScanOutcome::Failed => this.health.record_scan_failure(false),
+    }
+}
+
 #[handler]
 fn schedule_retry<B: AgentBackend>(
     _: &mut GenericAgentActor<B>,
     msg: TryConnectWithDelay,
     ctx: &Context<GenericAgentActor<B>>,
 ) {
-    let secs = msg.0;
     ctx.spawn_bg(async move {
-        tokio::time::sleep(std::time::Duration::from_secs(secs)).await;
-        RetryTimerElapsed
+        tokio::time::sleep(Duration::from_millis(msg.delay_ms)).await;
+        RetryTimerElapsed(msg.attempt)
     });
 }
 
 #[handler]
 fn on_retry_elapsed<B: AgentBackend>(
-    _: &mut GenericAgentActor<B>,
-    _: RetryTimerElapsed,
+    this: &mut GenericAgentActor<B>,
+    msg: RetryTimerElapsed,
     ctx: &Context<GenericAgentActor<B>>,
 ) {
-    ctx.addr().send(StartConnect);
+    if msg.0 != this.attempt {
+        return;
+    }
+    if let Some(t) = this.apply(ConnectionEvent::RetryDelayElapsed)
+        && t.to == AgentConnectionState::Connecting
+    {
+        this.publish_state(None);
+        this.spawn_connect(ctx);
+    }
+}
+
+#[handler]
+fn reconnect<B: AgentBackend>(
+    this: &mut GenericAgentActor<B>,
+    msg: Reconnect,
+    ctx: &Context<GenericAgentActor<B>>,
+) {
+    if msg.backend != B::NAME || this.apply(ConnectionEvent::Reconnect).is_none() {
+        return;
+    }
+    info!("[{}] Reconnecting on request", B::NAME);
+    this.health.record_reconnect();
+    this.attempt += 1;
+    this.drop_session();
+    this.publish_state(None);
+    this.spawn_connect(ctx);
 }
 
 #[handler]
@@ -227,8 +550,9 @@ fn on_connection_lost<B: AgentBackend>(
         return;
     }
     warn!("[{}] Connection lost", B::NAME);
-    this.client = None;
-    this.ping_in_flight = false;
+    this.health.record_reconnect();
+    this.attempt += 1;
+    this.drop_session();
     this.publish_state(None);
     ctx.addr().send(StartConnect);
 }
@@ -247,33 +571,123 @@ mod windows {
         this: &mut GenericAgentActor<WindowsBackend>,
         msg: WindowsActionRequest,
     ) {
+        let correlation_id = msg.correlation_id;
         let Some(client) = this.client.clone() else {
-            error!("Client not initialized");
+            EventBus::publish(WindowsActionResponse::failed(
+                correlation_id,
+                "Windows agent is not connected",
+            ));
             return;
         };
 
-        let correlation_id = msg.correlation_id;
         let request = match msg.decode_request() {
             Ok(request) => request,
             Err(err) => {
                 error!("Failed to decode backend request: {:?}", err);
+                EventBus::publish(WindowsActionResponse::failed(
+                    correlation_id,
+                    format!("bad request: {err}"),
+                ));
                 return;
             }
         };
 
         tokio::spawn(async move {
-            match client.call(request).await {
+            let response = match client.call(request).await {
                 Ok(resp_data) => {
-                    if let Ok(response) = rkyv::deserialize::<WindowsResponse, rkyv::rancor::Error>(
+                    match rkyv::deserialize::<WindowsResponse, rkyv::rancor::Error>(
                         *resp_data.deref(),
                     ) {
-                        EventBus::publish(WindowsActionResponse::new(correlation_id, &response));
+                        Ok(response) => WindowsActionResponse::new(correlation_id, &response),
+                        Err(err) => WindowsActionResponse::failed(correlation_id, err.to_string()),
                     }
                 }
                 Err(e) => {
                     error!("Backend call failed: {:?}", e);
+                    WindowsActionResponse::failed(correlation_id, e.to_string())
                 }
+            };
+            EventBus::publish(response);
+        });
+    }
+}
+
+// WSL on Windows and the local agent elsewhere speak the same Linux protocol.
+mod linux_actions {
+    use super::*;
+    use app_contracts::features::agents::{LinuxActionRequest, LinuxActionResponse};
+    use app_contracts::features::environments::WslClient;
+    use app_core::actor::event_bus::RpcRequest;
+    use std::ops::Deref;
+    use uniproc_protocol::LinuxResponse;
+
+    fn forward(name: &'static str, client: Option<WslClient>, msg: RpcRequest<LinuxActionRequest>) {
+        let Some(client) = client else {
+            msg.reply(LinuxActionResponse::failed(format!(
+                "{name} agent is not connected"
+            )));
+            return;
+        };
+
+        let request = match msg.payload.decode_request() {
+            Ok(request) => request,
+            Err(err) => {
+                msg.reply(LinuxActionResponse::failed(format!("bad request: {err}")));
+                return;
             }
+        };
+
+        tokio::spawn(async move {
+            let response = match client.call(request).await {
+                Ok(resp_data) => {
+                    match rkyv::deserialize::<LinuxResponse, rkyv::rancor::Error>(
+                        *resp_data.deref(),
+                    ) {
+                        Ok(response) => LinuxActionResponse::new(&response),
+                        Err(err) => LinuxActionResponse::failed(err.to_string()),
+                    }
+                }
+                Err(err) => {
+                    warn!("[{name}] Action call failed: {err}");
+                    LinuxActionResponse::failed(err.to_string())
+                }
+            };
+            msg.reply(response);
         });
     }
+
+    #[cfg(windows)]
+    #[handler]
+    fn handle_wsl_action(
+        this: &mut GenericAgentActor<crate::agents_impl::providers::wsl::WslBackend>,
+        msg: RpcRequest<LinuxActionRequest>,
+    ) {
+        if msg.payload.schema_id == "wsl" {
+            forward("WSL", this.client.clone(), msg);
+        }
+    }
+
+    #[cfg(not(windows))]
+    #[handler]
+    fn handle_linux_action(
+        this: &mut GenericAgentActor<crate::agents_impl::providers::linux::LinuxBackend>,
+        msg: RpcRequest<LinuxActionRequest>,
+    ) {
+        if msg.payload.schema_id == "linux" {
+            forward("Linux", this.client.clone(), msg);
+        }
+    }
+}
+
+mod replay {
+    use super::*;
+    use crate::agents_impl::providers::replay::ReplayBackend;
+    use app_contracts::features::agents::ReplayControl;
+
+    #[handler]
+    fn control_replay(this: &mut GenericAgentActor<ReplayBackend>, msg: ReplayControl) {
+        if let Some(client) = &this.client {
+            client.control(msg);
+        }
+    }
 }
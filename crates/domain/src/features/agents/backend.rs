@@ -1,5 +1,31 @@
+use crate::agents_impl::session::RecordedEvent;
+use app_contracts::features::agents::{AgentCapabilities, AgentHello};
 use app_contracts::features::environments::AgentConnectionState;
 use app_core::actor::traits::Message;
+use std::fmt;
+use tokio::sync::mpsc;
+
+// Reports pushed by the agent. Agents that send deltas fold them into full reports before
+// sending, so the actor can skip stale ones when the UI falls behind.
+pub type ReportStream = mpsc::Receiver<StreamedReport>;
+
+pub struct StreamedReport {
+    pub event: RecordedEvent,
+    // Size of the frame it arrived in, if the transport exposes it.
+    pub bytes: Option<usize>,
+}
+
+// Scan error for a report that arrived but did not decode, counted apart from transport errors.
+#[derive(Debug)]
+pub struct MalformedReport(pub String);
+
+impl fmt::Display for MalformedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for MalformedReport {}
 
 pub trait AgentBackend: Send + Sync + 'static {
     type Client: Clone + Send + Sync + 'static;
@@ -8,11 +34,25 @@ pub trait AgentBackend: Send + Sync + 'static {
     const NAME: &'static str;
 
     fn connect(timeout_secs: u64) -> impl Future<Output = anyhow::Result<Self::Client>> + Send;
+    // Runs right after connect; an error counts as a failed connect and is retried.
+    fn handshake(client: &Self::Client) -> impl Future<Output = anyhow::Result<AgentHello>> + Send;
+
     fn ping(client: &Self::Client) -> impl Future<Output = anyhow::Result<i32>> + Send;
-    fn perform_scan(client: &Self::Client) -> impl Future<Output = anyhow::Result<()>> + Send;
+    // Returns the report size in bytes, or `None` if the transport does not expose it.
+    fn perform_scan(
+        client: &Self::Client,
+    ) -> impl Future<Output = anyhow::Result<Option<usize>>> + Send;
+
+    // Agents without a subscription mode are polled with `perform_scan` on every ScanTick.
+    fn subscribe(
+        _client: &Self::Client,
+    ) -> impl Future<Output = anyhow::Result<Option<ReportStream>>> + Send {
+        async { Ok(None) }
+    }
 
     fn create_runtime_event(
         state: AgentConnectionState,
         latency_ms: Option<i32>,
+        capabilities: Option<AgentCapabilities>,
     ) -> Self::RuntimeEvent;
 }
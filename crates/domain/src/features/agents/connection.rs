@@ -1,18 +1,25 @@
+use crate::features::agents::settings::RetryPolicy;
 use app_contracts::features::environments::AgentConnectionState;
+use std::hash::{BuildHasher, RandomState};
+use std::time::Instant;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ConnectionEvent {
     BeginConnect,
     ConnectSucceeded,
+    HandshakeSucceeded,
+    IncompatibleAgent { agent_version: u32 },
     ConnectFailed,
     RetryDelayElapsed,
     ConnectionLost,
+    // User-triggered; starts a fresh attempt from any state.
+    Reconnect,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum TransitionEffect {
     None,
-    ScheduleRetry { delay_secs: u64 },
+    ScheduleRetry { delay_ms: u64 },
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -32,25 +39,36 @@ pub struct InvalidTransition {
 #[derive(Debug)]
 pub struct ConnectionMachine {
     state: AgentConnectionState,
-    next_retry_delay_secs: u64,
-    max_retry_delay_secs: u64,
+    connect_policy: RetryPolicy,
+    reconnect_policy: RetryPolicy,
+    // Set while the current streak of attempts started from a dropped connection.
+    reconnecting: bool,
+    failed_attempts: u32,
 }
 
 impl Default for ConnectionMachine {
     fn default() -> Self {
-        Self::new()
+        Self::new(RetryPolicy::default(), RetryPolicy::default())
     }
 }
 
 impl ConnectionMachine {
-    pub fn new() -> Self {
+    pub fn new(connect_policy: RetryPolicy, reconnect_policy: RetryPolicy) -> Self {
         Self {
             state: AgentConnectionState::Disconnected,
-            next_retry_delay_secs: 1,
-            max_retry_delay_secs: 15,
+            connect_policy,
+            reconnect_policy,
+            reconnecting: false,
+            failed_attempts: 0,
         }
     }
 
+    // Takes effect from the next failed attempt.
+    pub fn set_policies(&mut self, connect_policy: RetryPolicy, reconnect_policy: RetryPolicy) {
+        self.connect_policy = connect_policy;
+        self.reconnect_policy = reconnect_policy;
+    }
+
     pub fn apply(&mut self, event: ConnectionEvent) -> Result<Transition, InvalidTransition> {
         let from = self.state;
 
@@ -59,24 +77,38 @@ impl ConnectionMachine {
                 (AgentConnectionState::Connecting, TransitionEffect::None)
             }
             (AgentConnectionState::Connecting, ConnectionEvent::ConnectSucceeded) => {
-                self.next_retry_delay_secs = 1;
-                (AgentConnectionState::Connected, TransitionEffect::None)
+                (AgentConnectionState::Handshaking, TransitionEffect::None)
             }
-            (AgentConnectionState::Connecting, ConnectionEvent::ConnectFailed) => {
-                let delay_secs = self.next_retry_delay_secs;
-                self.next_retry_delay_secs =
-                    (delay_secs.saturating_mul(2)).min(self.max_retry_delay_secs);
-                (
-                    AgentConnectionState::WaitingRetry { delay_secs },
-                    TransitionEffect::ScheduleRetry { delay_secs },
-                )
+            (AgentConnectionState::Handshaking, ConnectionEvent::HandshakeSucceeded) => {
+                self.failed_attempts = 0;
+                self.reconnecting = false;
+                (AgentConnectionState::Connected, TransitionEffect::None)
             }
+            (
+                AgentConnectionState::Handshaking,
+                ConnectionEvent::IncompatibleAgent { agent_version },
+            ) => (
+                AgentConnectionState::Incompatible { agent_version },
+                TransitionEffect::None,
+            ),
+            (
+                AgentConnectionState::Connecting | AgentConnectionState::Handshaking,
+                ConnectionEvent::ConnectFailed,
+            ) => self.next_retry(),
             (AgentConnectionState::WaitingRetry { .. }, ConnectionEvent::RetryDelayElapsed) => {
                 (AgentConnectionState::Connecting, TransitionEffect::None)
             }
             (AgentConnectionState::Connected, ConnectionEvent::ConnectionLost) => {
+                self.failed_attempts = 0;
+                self.reconnecting = true;
                 (AgentConnectionState::Disconnected, TransitionEffect::None)
             }
+            // Also the only way out of Incompatible and GaveUp, where nothing retries on its own.
+            (_, ConnectionEvent::Reconnect) => {
+                self.failed_attempts = 0;
+                self.reconnecting = false;
+                (AgentConnectionState::Connecting, TransitionEffect::None)
+            }
             _ => {
                 return Err(InvalidTransition {
                     state: self.state,
@@ -97,4 +129,232 @@ impl ConnectionMachine {
     pub fn state(&self) -> AgentConnectionState {
         self.state
     }
+
+    fn next_retry(&mut self) -> (AgentConnectionState, TransitionEffect) {
+        let policy = if self.reconnecting {
+            self.reconnect_policy
+        } else {
+            self.connect_policy
+        };
+        self.failed_attempts += 1;
+        if policy.gives_up_after(self.failed_attempts) {
+            return (
+                AgentConnectionState::GaveUp {
+                    attempts: self.failed_attempts,
+                },
+                TransitionEffect::None,
+            );
+        }
+
+        let delay_ms = policy
+            .delay(self.failed_attempts - 1, random_unit())
+            .as_millis() as u64;
+        (
+            AgentConnectionState::WaitingRetry { delay_ms },
+            TransitionEffect::ScheduleRetry { delay_ms },
+        )
+    }
+}
+
+fn random_unit() -> f64 {
+    let bits = RandomState::new().hash_one(Instant::now());
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            jitter: 0.0,
+            max_attempts,
+            ..RetryPolicy::default()
+        }
+    }
+
+    fn fail(machine: &mut ConnectionMachine) -> AgentConnectionState {
+        machine.apply(ConnectionEvent::ConnectFailed).unwrap().to
+    }
+
+    fn connected(machine: &mut ConnectionMachine) {
+        machine.apply(ConnectionEvent::ConnectSucceeded).unwrap();
+        machine.apply(ConnectionEvent::HandshakeSucceeded).unwrap();
+    }
+
+    fn incompatible() -> ConnectionMachine {
+        let mut machine = ConnectionMachine::default();
+        machine.apply(ConnectionEvent::BeginConnect).unwrap();
+        machine.apply(ConnectionEvent::ConnectSucceeded).unwrap();
+        machine
+            .apply(ConnectionEvent::IncompatibleAgent { agent_version: 7 })
+            .unwrap();
+        machine
+    }
+
+    #[test]
+    fn connects_through_the_handshake() {
+        let mut machine = ConnectionMachine::default();
+
+        let begin = machine.apply(ConnectionEvent::BeginConnect).unwrap();
+        assert_eq!(
+            (begin.from, begin.to),
+            (
+                AgentConnectionState::Disconnected,
+                AgentConnectionState::Connecting
+            )
+        );
+        assert_eq!(
+            machine.apply(ConnectionEvent::ConnectSucceeded).unwrap().to,
+            AgentConnectionState::Handshaking
+        );
+        let done = machine.apply(ConnectionEvent::HandshakeSucceeded).unwrap();
+        assert_eq!(done.to, AgentConnectionState::Connected);
+        assert_eq!(done.effect, TransitionEffect::None);
+    }
+
+    #[test]
+    fn failures_back_off_and_schedule_a_retry() {
+        let mut machine = ConnectionMachine::new(policy(0), policy(0));
+        machine.apply(ConnectionEvent::BeginConnect).unwrap();
+
+        let first = machine.apply(ConnectionEvent::ConnectFailed).unwrap();
+        assert_eq!(
+            first.effect,
+            TransitionEffect::ScheduleRetry { delay_ms: 1000 }
+        );
+        machine.apply(ConnectionEvent::RetryDelayElapsed).unwrap();
+        assert_eq!(
+            fail(&mut machine),
+            AgentConnectionState::WaitingRetry { delay_ms: 2000 }
+        );
+    }
+
+    #[test]
+    fn backoff_is_capped_at_max_delay() {
+        let mut machine = ConnectionMachine::new(policy(0), policy(0));
+        machine.apply(ConnectionEvent::BeginConnect).unwrap();
+
+        for _ in 0..5 {
+            fail(&mut machine);
+            machine.apply(ConnectionEvent::RetryDelayElapsed).unwrap();
+        }
+        assert_eq!(
+            fail(&mut machine),
+            AgentConnectionState::WaitingRetry { delay_ms: 15000 }
+        );
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let mut machine = ConnectionMachine::new(policy(3), policy(0));
+        machine.apply(ConnectionEvent::BeginConnect).unwrap();
+
+        fail(&mut machine);
+        machine.apply(ConnectionEvent::RetryDelayElapsed).unwrap();
+        fail(&mut machine);
+        machine.apply(ConnectionEvent::RetryDelayElapsed).unwrap();
+        let gave_up = machine.apply(ConnectionEvent::ConnectFailed).unwrap();
+
+        assert_eq!(gave_up.to, AgentConnectionState::GaveUp { attempts: 3 });
+        assert_eq!(gave_up.effect, TransitionEffect::None);
+    }
+
+    #[test]
+    fn gave_up_is_left_only_by_reconnect() {
+        let mut machine = ConnectionMachine::new(policy(1), policy(0));
+        machine.apply(ConnectionEvent::BeginConnect).unwrap();
+        fail(&mut machine);
+
+        assert!(machine.apply(ConnectionEvent::RetryDelayElapsed).is_err());
+        assert!(machine.apply(ConnectionEvent::BeginConnect).is_err());
+        assert_eq!(
+            machine.state(),
+            AgentConnectionState::GaveUp { attempts: 1 }
+        );
+
+        machine.apply(ConnectionEvent::Reconnect).unwrap();
+        assert_eq!(machine.state(), AgentConnectionState::Connecting);
+        // The attempt budget starts over.
+        assert_eq!(
+            fail(&mut machine),
+            AgentConnectionState::GaveUp { attempts: 1 }
+        );
+    }
+
+    #[test]
+    fn dropped_connection_backs_off_with_the_reconnect_policy() {
+        let lost = RetryPolicy {
+            base_secs: 0.25,
+            ..policy(0)
+        };
+        let mut machine = ConnectionMachine::new(policy(3), lost);
+        machine.apply(ConnectionEvent::BeginConnect).unwrap();
+        connected(&mut machine);
+
+        assert_eq!(
+            machine.apply(ConnectionEvent::ConnectionLost).unwrap().to,
+            AgentConnectionState::Disconnected
+        );
+        machine.apply(ConnectionEvent::BeginConnect).unwrap();
+        assert_eq!(
+            fail(&mut machine),
+            AgentConnectionState::WaitingRetry { delay_ms: 250 }
+        );
+    }
+
+    #[test]
+    fn successful_handshake_resets_the_attempt_count() {
+        let mut machine = ConnectionMachine::new(policy(2), policy(2));
+        machine.apply(ConnectionEvent::BeginConnect).unwrap();
+        fail(&mut machine);
+        machine.apply(ConnectionEvent::RetryDelayElapsed).unwrap();
+        connected(&mut machine);
+
+        machine.apply(ConnectionEvent::ConnectionLost).unwrap();
+        machine.apply(ConnectionEvent::BeginConnect).unwrap();
+        assert_eq!(
+            fail(&mut machine),
+            AgentConnectionState::WaitingRetry { delay_ms: 1000 }
+        );
+    }
+
+    #[test]
+    fn incompatible_agent_rejects_begin_connect() {
+        let mut machine = incompatible();
+
+        let err = machine.apply(ConnectionEvent::BeginConnect).unwrap_err();
+        assert_eq!(
+            err,
+            InvalidTransition {
+                state: AgentConnectionState::Incompatible { agent_version: 7 },
+                event: ConnectionEvent::BeginConnect,
+            }
+        );
+        assert_eq!(
+            machine.state(),
+            AgentConnectionState::Incompatible { agent_version: 7 }
+        );
+    }
+
+    #[test]
+    fn incompatible_agent_is_left_only_by_reconnect() {
+        let mut machine = incompatible();
+
+        assert!(machine.apply(ConnectionEvent::ConnectFailed).is_err());
+        assert!(machine.apply(ConnectionEvent::RetryDelayElapsed).is_err());
+        assert_eq!(
+            machine.apply(ConnectionEvent::Reconnect).unwrap().to,
+            AgentConnectionState::Connecting
+        );
+    }
+
+    #[test]
+    fn events_out_of_order_leave_the_state_untouched() {
+        let mut machine = ConnectionMachine::default();
+
+        assert!(machine.apply(ConnectionEvent::HandshakeSucceeded).is_err());
+        assert!(machine.apply(ConnectionEvent::ConnectionLost).is_err());
+        assert_eq!(machine.state(), AgentConnectionState::Disconnected);
+    }
 }
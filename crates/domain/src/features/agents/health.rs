@@ -0,0 +1,204 @@
+use app_contracts::features::agents::AgentHealth;
+use app_contracts::features::environments::AgentConnectionState;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+// Samples kept per series; a few minutes of pings at the default interval.
+const WINDOW: usize = 128;
+
+#[derive(Default)]
+pub struct HealthStats {
+    ping_ms: VecDeque<u32>,
+    scan_ms: VecDeque<u32>,
+    scan_bytes: VecDeque<u64>,
+    scans: u64,
+    scan_failures: u32,
+    malformed_reports: u32,
+    reconnects: u32,
+}
+
+impl HealthStats {
+    pub fn record_ping(&mut self, latency_ms: i32) {
+        push(&mut self.ping_ms, latency_ms.max(0) as u32);
+    }
+
+    pub fn record_scan(&mut self, elapsed: Duration, bytes: Option<usize>) {
+        self.scans += 1;
+        push(&mut self.scan_ms, elapsed.as_millis() as u32);
+        if let Some(bytes) = bytes {
+            push(&mut self.scan_bytes, bytes as u64);
+        }
+    }
+
+    // Pushed reports have no request to time; they only count and size the report.
+    pub fn record_streamed(&mut self, bytes: Option<usize>) {
+        self.scans += 1;
+        if let Some(bytes) = bytes {
+            push(&mut self.scan_bytes, bytes as u64);
+        }
+    }
+
+    pub fn record_scan_failure(&mut self, malformed: bool) {
+        self.scans += 1;
+        if malformed {
+            self.malformed_reports += 1;
+        } else {
+            self.scan_failures += 1;
+        }
+    }
+
+    pub fn record_reconnect(&mut self) {
+        self.reconnects += 1;
+    }
+
+    pub fn snapshot(&self, backend: &'static str, state: AgentConnectionState) -> AgentHealth {
+        let scan_bytes = (!self.scan_bytes.is_empty())
+            .then(|| self.scan_bytes.iter().sum::<u64>() / self.scan_bytes.len() as u64);
+        AgentHealth {
+            backend,
+            state,
+            ping_p50_ms: percentile(&self.ping_ms, 50),
+            ping_p95_ms: percentile(&self.ping_ms, 95),
+            ping_p99_ms: percentile(&self.ping_ms, 99),
+            scan_p50_ms: percentile(&self.scan_ms, 50),
+            scan_p95_ms: percentile(&self.scan_ms, 95),
+            scan_bytes,
+            scans: self.scans,
+            scan_failures: self.scan_failures,
+            malformed_reports: self.malformed_reports,
+            reconnects: self.reconnects,
+        }
+    }
+}
+
+fn push<T>(window: &mut VecDeque<T>, sample: T) {
+    if window.len() == WINDOW {
+        window.pop_front();
+    }
+    window.push_back(sample);
+}
+
+// Nearest-rank percentile.
+fn percentile(samples: &VecDeque<u32>, p: usize) -> Option<u32> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<u32> = samples.iter().copied().collect();
+    sorted.sort_unstable();
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    Some(sorted[rank - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(stats: &HealthStats) -> AgentHealth {
+        stats.snapshot("Mock", AgentConnectionState::Connected)
+    }
+
+    #[test]
+    fn empty_stats_report_no_percentiles() {
+        let health = health(&HealthStats::default());
+
+        assert_eq!(health.ping_p50_ms, None);
+        assert_eq!(health.scan_p95_ms, None);
+        assert_eq!(health.scan_bytes, None);
+        assert_eq!(health.scans, 0);
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let mut stats = HealthStats::default();
+        for ms in 1..=100 {
+            stats.record_ping(ms);
+        }
+
+        let health = health(&stats);
+        assert_eq!(
+            (health.ping_p50_ms, health.ping_p95_ms, health.ping_p99_ms),
+            (Some(50), Some(95), Some(99))
+        );
+    }
+
+    #[test]
+    fn single_sample_is_every_percentile() {
+        let mut stats = HealthStats::default();
+        stats.record_ping(7);
+
+        let health = health(&stats);
+        assert_eq!(
+            (health.ping_p50_ms, health.ping_p95_ms, health.ping_p99_ms),
+            (Some(7), Some(7), Some(7))
+        );
+    }
+
+    #[test]
+    fn percentiles_cover_only_the_window() {
+        let mut stats = HealthStats::default();
+        // The first samples fall out of the window.
+        for ms in [5000; 10].into_iter().chain(1..=128) {
+            stats.record_ping(ms);
+        }
+
+        let health = health(&stats);
+        assert_eq!(
+            (health.ping_p50_ms, health.ping_p95_ms, health.ping_p99_ms),
+            (Some(64), Some(122), Some(127))
+        );
+    }
+
+    #[test]
+    fn negative_ping_counts_as_zero() {
+        let mut stats = HealthStats::default();
+        stats.record_ping(-5);
+
+        assert_eq!(health(&stats).ping_p50_ms, Some(0));
+    }
+
+    #[test]
+    fn scan_bytes_average_only_sized_scans() {
+        let mut stats = HealthStats::default();
+        stats.record_scan(Duration::from_millis(40), Some(1000));
+        stats.record_scan(Duration::from_millis(60), None);
+        stats.record_scan(Duration::from_millis(50), Some(3000));
+
+        let health = health(&stats);
+        assert_eq!(
+            (health.scan_p50_ms, health.scan_p95_ms),
+            (Some(50), Some(60))
+        );
+        assert_eq!(health.scan_bytes, Some(2000));
+        assert_eq!(health.scans, 3);
+    }
+
+    #[test]
+    fn streamed_reports_count_without_timing() {
+        let mut stats = HealthStats::default();
+        stats.record_streamed(Some(500));
+        stats.record_streamed(None);
+        stats.record_scan(Duration::from_millis(30), Some(1500));
+
+        let health = health(&stats);
+        assert_eq!(health.scans, 3);
+        assert_eq!(health.scan_bytes, Some(1000));
+        assert_eq!(health.scan_p50_ms, Some(30));
+    }
+
+    #[test]
+    fn failures_are_split_by_kind_and_count_as_scans() {
+        let mut stats = HealthStats::default();
+        stats.record_scan_failure(true);
+        stats.record_scan_failure(false);
+        stats.record_scan_failure(false);
+        stats.record_reconnect();
+
+        let health = health(&stats);
+        assert_eq!(
+            (health.scans, health.malformed_reports, health.scan_failures),
+            (3, 1, 2)
+        );
+        assert_eq!(health.reconnects, 1);
+        assert_eq!(health.scan_p50_ms, None);
+    }
+}
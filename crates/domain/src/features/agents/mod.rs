@@ -1,11 +1,20 @@
 pub mod actor;
 pub mod backend;
 pub mod connection;
+pub mod health;
 pub mod providers;
+pub mod recorder;
+pub mod session;
 pub mod settings;
 
-use crate::agents_impl::providers::{windows, wsl};
+use crate::agents_impl::providers::replay::ReplayAgentFeature;
+use crate::agents_impl::recorder::SessionRecorderActor;
+use crate::agents_impl::session::SessionWriter;
+use crate::features::agents::settings::AgentSettings;
+use app_core::actor::addr::Addr;
 use framework::feature::{AppFeature, AppFeatureInitContext};
+use framework::lifecycle_tracker::FeatureLifecycle;
+use std::path::Path;
 use tracing::info;
 
 pub struct AgentsFeature;
@@ -13,13 +22,35 @@ pub struct AgentsFeature;
 impl AppFeature for AgentsFeature {
     fn install(self, ctx: &mut AppFeatureInitContext) -> anyhow::Result<()> {
         info!("Agents feature installed");
-        cfg_if::cfg_if! {
-            if #[cfg(target_os = "windows")] {
-                wsl::WslAgentFeature.install(ctx)?;
-                windows::WindowsAgentFeature.install(ctx)?;
-            } else {
-                linux::LinuxAgentFeature.install(reactor, ui, shared)?;
+        let settings = AgentSettings::new(ctx.shared)?;
+
+        let replay_path = settings.replay_path().get();
+        if replay_path.is_empty() {
+            cfg_if::cfg_if! {
+                if #[cfg(target_os = "windows")] {
+                    providers::wsl::WslAgentFeature.install(ctx)?;
+                    providers::windows::WindowsAgentFeature.install(ctx)?;
+                } else {
+                    providers::linux::LinuxAgentFeature.install(ctx)?;
+                }
+            }
+        } else {
+            ReplayAgentFeature {
+                path: replay_path.into(),
             }
+            .install(ctx)?;
+        }
+
+        // Opened after the replay is loaded, so recording over the replayed file is harmless.
+        let record_path = settings.record_path().get();
+        if !record_path.is_empty() {
+            let writer = SessionWriter::create(Path::new(&record_path))?;
+            info!("Recording agent reports to {record_path}");
+            let _ = Addr::new_managed(
+                SessionRecorderActor::new(writer),
+                ctx.token.clone(),
+                &FeatureLifecycle::new(),
+            );
         }
 
         Ok(())
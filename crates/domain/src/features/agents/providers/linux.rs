@@ -1,21 +1,24 @@
 use crate::agents_impl::actor::{GenericAgentActor, Init, Ping};
-use crate::agents_impl::backend::AgentBackend;
+use crate::agents_impl::backend::{AgentBackend, MalformedReport};
 use crate::features::agents::settings::AgentSettings;
-use app_contracts::features::agents::{RemoteScanResult, ScanTick};
-use app_contracts::features::environments::{
-    AgentClient, AgentConnectionState, WslAgentRuntimeEvent,
+use anyhow::bail;
+use app_contracts::features::agents::{
+    AgentCapabilities, AgentHello, LinuxActionRequest, Reconnect, RemoteScanResult, ReportConsumed,
+    ScanTick,
 };
-use app_core::app::Window;
-use app_core::{
-    SharedState,
-    actor::{addr::Addr, event_bus::EVENT_BUS},
-    app::Feature,
-    reactor::Reactor,
+use app_contracts::features::environments::{
+    AgentClient, AgentConnectionState, LinuxAgentRuntimeEvent,
 };
+use app_core::actor::event_bus::{EventBus, RpcRequest};
+use app_core::{actor::addr::Addr, ratelimit};
+use framework::feature::{AppFeature, AppFeatureInitContext};
+use framework::lifecycle_tracker::FeatureLifecycle;
 use ogurpchik::discovery::Scope;
 use ogurpchik::high::node::Node;
 use ogurpchik::transport::stream::adapters::uds::UdsTransport;
-use slint::ComponentHandle;
+use std::ops::Deref;
+use std::time::Instant;
+use tracing::{error, instrument, warn};
 use uniproc_protocol::{LinuxCodec, LinuxRequest, LinuxResponse};
 
 pub struct LinuxBackend;
@@ -26,73 +29,106 @@ impl AgentBackend for LinuxBackend {
     const NAME: &'static str = "Linux";
 
     async fn connect(timeout: u64) -> anyhow::Result<Self::Client> {
-        Ok(Node::new()?
+        Node::new()?
             .scope(Scope::Internal)?
             .connect::<LinuxCodec, _>(UdsTransport::temp("uniproc"))
             .wait_for("uniproc")
             .timeout(timeout)
             .start()
-            .await?)
+            .await
+    }
+
+    async fn handshake(client: &Self::Client) -> anyhow::Result<AgentHello> {
+        let resp = client.call(LinuxRequest::Hello).await?;
+        let response = rkyv::deserialize::<LinuxResponse, rkyv::rancor::Error>(*resp.deref())?;
+        match response {
+            LinuxResponse::Hello(hello) => Ok(hello.into()),
+            // Agents that predate the handshake reject a request they cannot decode.
+            LinuxResponse::Error(_) => Ok(AgentHello::legacy()),
+            other => bail!("unexpected Linux handshake response: {other:?}"),
+        }
     }
 
     async fn ping(client: &Self::Client) -> anyhow::Result<i32> {
-        let start = std::time::Instant::now();
+        let start = Instant::now();
         client.call(LinuxRequest::Ping).await?;
         Ok(start.elapsed().as_millis() as i32)
     }
 
     #[instrument(skip(client), level = "debug", fields(target = "linux"), err)]
-    async fn perform_scan(client: &Self::Client) -> anyhow::Result<()> {
+    async fn perform_scan(client: &Self::Client) -> anyhow::Result<Option<usize>> {
         let resp = client.call(LinuxRequest::GetReport).await?;
 
         let report = rkyv::deserialize::<LinuxResponse, rkyv::rancor::Error>(*resp.deref())
             .map_err(|e| {
                 error!(error = %e, "Failed to deserialize Linux response");
-                anyhow::anyhow!("Linux scan deserialization error: {}", e)
+                MalformedReport(format!("Linux scan deserialization error: {}", e))
             })?;
 
         if let LinuxResponse::Report(r) = report {
-            EVENT_BUS.with(|bus| {
-                bus.publish(RemoteScanResult {
-                    schema_id: "linux",
-                    processes: r.processes,
-                    machine: r.machine,
-                    environments: r.environments,
-                    docker_containers: r.docker_containers,
-                })
+            EventBus::publish(RemoteScanResult {
+                schema_id: "linux",
+                processes: r.processes,
+                machine: r.machine,
+                environments: r.environments,
+                docker_containers: r.docker_containers,
+                recorded_at: None,
             });
+
             ratelimit!(3600, info!("Report published to event bus"));
         } else {
             warn!(response = ?report, "Unexpected Linux response type â€” strange");
         }
 
-        Ok(())
+        Ok(Some(resp.len()))
     }
 
     fn create_runtime_event(
         state: AgentConnectionState,
         latency: Option<i32>,
+        capabilities: Option<AgentCapabilities>,
     ) -> Self::RuntimeEvent {
         LinuxAgentRuntimeEvent {
-            state: state.into(),
+            state,
             latency_ms: latency,
+            capabilities,
         }
     }
 }
 
 pub struct LinuxAgentFeature;
-impl<T: Window> Feature<T> for LinuxAgentFeature {
-    fn install(self, reactor: &mut Reactor, ui: &T, shared: &SharedState) -> anyhow::Result<()> {
-        let settings = AgentSettings::new(shared)?;
+impl AppFeature for LinuxAgentFeature {
+    fn install(self, ctx: &mut AppFeatureInitContext) -> anyhow::Result<()> {
+        let settings = AgentSettings::new(ctx.shared)?;
+
         let addr = Addr::new(
-            GenericAgentActor::<LinuxBackend>::new(settings.connect_timeout_secs()?),
-            ui.as_weak(),
+            GenericAgentActor::<LinuxBackend>::new(&settings),
+            ctx.token.clone(),
+            &FeatureLifecycle::new(),
         );
+
         let a = addr.clone();
-        reactor.add_dynamic_loop(&settings.ping_interval_ms()?, move || a.send(Ping));
-        EVENT_BUS.with(|bus| {
-            bus.subscribe::<GenericAgentActor<LinuxBackend>, ScanTick, T>(addr.clone())
-        });
+        ctx.reactor
+            .add_dynamic_loop(settings.ping_interval_ms().as_signal(), move || {
+                a.send(Ping)
+            });
+
+        EventBus::subscribe::<GenericAgentActor<LinuxBackend>, ScanTick>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        EventBus::subscribe::<GenericAgentActor<LinuxBackend>, RpcRequest<LinuxActionRequest>>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        EventBus::subscribe::<GenericAgentActor<LinuxBackend>, Reconnect>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        EventBus::subscribe::<GenericAgentActor<LinuxBackend>, ReportConsumed>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
         addr.send(Init);
         Ok(())
     }
@@ -0,0 +1,298 @@
+use crate::agents_impl::actor::{GenericAgentActor, Init, Ping};
+use crate::agents_impl::backend::{AgentBackend, MalformedReport, ReportStream, StreamedReport};
+use crate::agents_impl::session::{RecordedEvent, read_session};
+use crate::features::agents::settings::AgentSettings;
+use anyhow::{Context, bail};
+use app_contracts::features::agents::{
+    AgentCapabilities, AgentHello, PROTOCOL_VERSION, Reconnect, ReportConsumed, ScanTick,
+};
+use app_contracts::features::environments::AgentConnectionState;
+use app_core::actor::addr::Addr;
+use app_core::actor::event_bus::EventBus;
+use framework::feature::{AppFeature, AppFeatureInitContext};
+use framework::lifecycle_tracker::FeatureLifecycle;
+use rkyv::util::AlignedVec;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+// Reports are published under the same runtime event as the live agent, like the replay.
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "windows")] {
+        pub use app_contracts::features::environments::WslAgentRuntimeEvent as MockRuntimeEvent;
+    } else {
+        pub use app_contracts::features::environments::LinuxAgentRuntimeEvent as MockRuntimeEvent;
+    }
+}
+
+// Backends are stateless types, so the agent a test scripts is handed over to `connect` here.
+static AGENT: Mutex<Option<MockAgent>> = Mutex::new(None);
+
+type Generator = Box<dyn FnMut(u64) -> RecordedEvent + Send>;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MockAgentStats {
+    pub connects: u32,
+    pub pings: u32,
+    pub scans: u32,
+}
+
+#[derive(Default)]
+struct MockState {
+    // Frames are kept serialized, so every scan goes through the same decode as a recording.
+    frames: VecDeque<AlignedVec<16>>,
+    generator: Option<Generator>,
+    latency: Duration,
+    failed_connects: u32,
+    // Answered on handshake; `None` answers like an up-to-date agent with legacy capabilities.
+    hello: Option<AgentHello>,
+    streams: bool,
+    // Open while a client is subscribed; pushed reports go here instead of the queue.
+    stream: Option<mpsc::Sender<StreamedReport>>,
+    // Bumped by `drop_connection`; clients of an older connection get reset errors.
+    connection: u64,
+    stats: MockAgentStats,
+}
+
+// A scriptable in-process agent for tests of the connect, ping and scan flows.
+#[derive(Clone, Default)]
+pub struct MockAgent(Arc<Mutex<MockState>>);
+
+impl MockAgent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Queues every report of a session recording, in order.
+    pub fn from_session(path: &Path) -> anyhow::Result<Self> {
+        let agent = Self::new();
+        for frame in read_session(path)? {
+            agent.push(&frame.event)?;
+        }
+        Ok(agent)
+    }
+
+    pub fn push(&self, event: &RecordedEvent) -> anyhow::Result<()> {
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(event)?;
+        let mut state = self.0.lock().unwrap();
+        if let Some(stream) = &state.stream {
+            // A full stream drops the report, as an agent with a slow reader would.
+            let _ = stream.try_send(StreamedReport {
+                event: event.clone(),
+                bytes: Some(bytes.len()),
+            });
+            return Ok(());
+        }
+        state.frames.push_back(bytes);
+        Ok(())
+    }
+
+    // Queues a frame that fails to decode, as a truncated or mismatched payload would.
+    pub fn push_malformed(&self, bytes: &[u8]) {
+        let mut frame = AlignedVec::<16>::with_capacity(bytes.len());
+        frame.extend_from_slice(bytes);
+        self.0.lock().unwrap().frames.push_back(frame);
+    }
+
+    // Serves reports built from the scan number once the queued frames run out.
+    pub fn generate(&self, generator: impl FnMut(u64) -> RecordedEvent + Send + 'static) {
+        self.0.lock().unwrap().generator = Some(Box::new(generator));
+    }
+
+    pub fn set_latency(&self, latency: Duration) {
+        self.0.lock().unwrap().latency = latency;
+    }
+
+    pub fn fail_connects(&self, count: u32) {
+        self.0.lock().unwrap().failed_connects = count;
+    }
+
+    pub fn set_hello(&self, hello: AgentHello) {
+        self.0.lock().unwrap().hello = Some(hello);
+    }
+
+    // Clients connecting from now on subscribe instead of being polled. A pending stream read
+    // counts as a background task, so `stabilize_ui` only settles while the client waits for
+    // the UI to take a report in, or once the stream ends.
+    pub fn enable_streaming(&self) {
+        self.0.lock().unwrap().streams = true;
+    }
+
+    // Closes the report stream; the client falls back to polling.
+    pub fn end_stream(&self) {
+        self.0.lock().unwrap().stream = None;
+    }
+
+    pub fn drop_connection(&self) {
+        let mut state = self.0.lock().unwrap();
+        state.connection += 1;
+        state.stream = None;
+    }
+
+    pub fn stats(&self) -> MockAgentStats {
+        self.0.lock().unwrap().stats
+    }
+
+    fn latency(&self) -> Duration {
+        self.0.lock().unwrap().latency
+    }
+}
+
+#[derive(Clone)]
+pub struct MockClient {
+    agent: MockAgent,
+    connection: u64,
+}
+
+impl MockClient {
+    fn state(&self) -> anyhow::Result<std::sync::MutexGuard<'_, MockState>> {
+        let state = self.agent.0.lock().unwrap();
+        if state.connection != self.connection {
+            bail!("connection reset by mock agent");
+        }
+        Ok(state)
+    }
+
+    fn next_frame(&self) -> anyhow::Result<Option<AlignedVec<16>>> {
+        let mut state = self.state()?;
+        state.stats.scans += 1;
+        if let Some(frame) = state.frames.pop_front() {
+            return Ok(Some(frame));
+        }
+
+        let scan = state.stats.scans as u64;
+        let Some(generate) = state.generator.as_mut() else {
+            return Ok(None);
+        };
+        let event = generate(scan);
+        Ok(Some(rkyv::to_bytes::<rkyv::rancor::Error>(&event)?))
+    }
+}
+
+pub struct MockBackend;
+
+impl AgentBackend for MockBackend {
+    type Client = MockClient;
+    type RuntimeEvent = MockRuntimeEvent;
+    const NAME: &'static str = "Mock";
+
+    async fn connect(_timeout: u64) -> anyhow::Result<Self::Client> {
+        let agent = AGENT
+            .lock()
+            .unwrap()
+            .clone()
+            .context("no mock agent is installed")?;
+        tokio::time::sleep(agent.latency()).await;
+
+        let mut state = agent.0.lock().unwrap();
+        state.stats.connects += 1;
+        if state.failed_connects > 0 {
+            state.failed_connects -= 1;
+            bail!("mock agent refused the connection");
+        }
+        let connection = state.connection;
+        drop(state);
+
+        Ok(MockClient { agent, connection })
+    }
+
+    async fn handshake(client: &Self::Client) -> anyhow::Result<AgentHello> {
+        let state = client.state()?;
+        let mut hello = state.hello.unwrap_or(AgentHello {
+            protocol_version: PROTOCOL_VERSION,
+            ..AgentHello::legacy()
+        });
+        hello.capabilities.streaming |= state.streams;
+        Ok(hello)
+    }
+
+    async fn ping(client: &Self::Client) -> anyhow::Result<i32> {
+        let latency = client.agent.latency();
+        tokio::time::sleep(latency).await;
+        client.state()?.stats.pings += 1;
+        Ok(latency.as_millis() as i32)
+    }
+
+    async fn perform_scan(client: &Self::Client) -> anyhow::Result<Option<usize>> {
+        tokio::time::sleep(client.agent.latency()).await;
+        let Some(frame) = client.next_frame()? else {
+            return Ok(None);
+        };
+        rkyv::from_bytes::<RecordedEvent, rkyv::rancor::Error>(&frame)
+            .map_err(|e| MalformedReport(format!("mock agent sent a malformed report: {e}")))?
+            .publish();
+        Ok(Some(frame.len()))
+    }
+
+    async fn subscribe(client: &Self::Client) -> anyhow::Result<Option<ReportStream>> {
+        let mut state = client.state()?;
+        if !state.streams {
+            return Ok(None);
+        }
+        // Reports queued before the client subscribed are streamed first, as a backlog would be.
+        let (tx, rx) = mpsc::channel(32);
+        while let Some(frame) = state.frames.pop_front() {
+            if let Ok(event) = rkyv::from_bytes::<RecordedEvent, rkyv::rancor::Error>(&frame) {
+                let _ = tx.try_send(StreamedReport {
+                    event,
+                    bytes: Some(frame.len()),
+                });
+            }
+        }
+        state.stream = Some(tx);
+        Ok(Some(rx))
+    }
+
+    fn create_runtime_event(
+        state: AgentConnectionState,
+        latency: Option<i32>,
+        capabilities: Option<AgentCapabilities>,
+    ) -> Self::RuntimeEvent {
+        MockRuntimeEvent {
+            state,
+            latency_ms: latency,
+            capabilities,
+        }
+    }
+}
+
+pub struct MockAgentFeature {
+    pub agent: MockAgent,
+}
+
+impl AppFeature for MockAgentFeature {
+    fn install(self, ctx: &mut AppFeatureInitContext) -> anyhow::Result<()> {
+        let settings = AgentSettings::new(ctx.shared)?;
+        // Replaces the agent of a previous test rather than failing like the replay does.
+        *AGENT.lock().unwrap() = Some(self.agent);
+
+        let addr = Addr::new(
+            GenericAgentActor::<MockBackend>::new(&settings),
+            ctx.token.clone(),
+            &FeatureLifecycle::new(),
+        );
+
+        let a = addr.clone();
+        ctx.reactor
+            .add_dynamic_loop(settings.ping_interval_ms().as_signal(), move || {
+                a.send(Ping)
+            });
+
+        EventBus::subscribe::<GenericAgentActor<MockBackend>, ScanTick>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        EventBus::subscribe::<GenericAgentActor<MockBackend>, ReportConsumed>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        EventBus::subscribe::<GenericAgentActor<MockBackend>, Reconnect>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        addr.send(Init);
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
+#[cfg(feature = "test-utils")]
+pub mod mock;
+pub mod replay;
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "windows")] {
         pub mod wsl;
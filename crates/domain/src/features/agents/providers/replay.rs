@@ -0,0 +1,218 @@
+use crate::agents_impl::actor::{GenericAgentActor, Init, Ping};
+use crate::agents_impl::backend::AgentBackend;
+use crate::agents_impl::session::{RecordedEvent, RecordedFrame, read_session};
+use crate::features::agents::settings::AgentSettings;
+use anyhow::Context;
+use app_contracts::features::agents::{
+    AgentCapabilities, AgentHello, PROTOCOL_VERSION, ReplayControl, ReplayStatus, ScanTick,
+};
+use app_contracts::features::environments::AgentConnectionState;
+use app_core::actor::addr::Addr;
+use app_core::actor::event_bus::EventBus;
+use framework::feature::{AppFeature, AppFeatureInitContext};
+use framework::lifecycle_tracker::FeatureLifecycle;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+// The replay stands in for the agent the UI already tracks, so status indicators behave as live.
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "windows")] {
+        use app_contracts::features::environments::WslAgentRuntimeEvent as ReplayRuntimeEvent;
+    } else {
+        use app_contracts::features::environments::LinuxAgentRuntimeEvent as ReplayRuntimeEvent;
+    }
+}
+
+// Backends are stateless types, so the loaded recording is handed over to `connect` here.
+static SESSION: OnceLock<ReplayClient> = OnceLock::new();
+
+#[derive(Clone)]
+pub struct ReplayClient(Arc<Mutex<ReplayCursor>>);
+
+impl ReplayClient {
+    fn new(frames: Vec<RecordedFrame>, speed: f64) -> Self {
+        Self(Arc::new(Mutex::new(ReplayCursor {
+            frames,
+            next: 0,
+            reshow: Vec::new(),
+            position: Duration::ZERO,
+            last_tick: None,
+            paused: false,
+            speed: clamp_speed(speed),
+        })))
+    }
+
+    pub fn control(&self, control: ReplayControl) {
+        let mut cursor = self.0.lock().unwrap();
+        match control {
+            ReplayControl::Pause => cursor.paused = true,
+            ReplayControl::Resume => cursor.paused = false,
+            ReplayControl::Seek(to) => cursor.seek(to),
+            ReplayControl::SetSpeed(speed) => cursor.speed = clamp_speed(speed),
+        }
+        info!(
+            position_ms = cursor.position.as_millis() as u64,
+            paused = cursor.paused,
+            speed = cursor.speed,
+            "replay {control:?}"
+        );
+        EventBus::publish(cursor.status());
+    }
+}
+
+fn clamp_speed(speed: f64) -> f64 {
+    if speed.is_finite() {
+        speed.clamp(0.1, 100.0)
+    } else {
+        1.0
+    }
+}
+
+struct ReplayCursor {
+    frames: Vec<RecordedFrame>,
+    next: usize,
+    // Set by a seek: the latest frame of each source at the new position, shown once.
+    reshow: Vec<usize>,
+    position: Duration,
+    last_tick: Option<Instant>,
+    paused: bool,
+    speed: f64,
+}
+
+impl ReplayCursor {
+    // Frames that became due since the last call, in recorded order.
+    fn advance(&mut self, now: Instant) -> Vec<&RecordedFrame> {
+        if let Some(last) = self.last_tick.replace(now)
+            && !self.paused
+        {
+            self.position += now.duration_since(last).mul_f64(self.speed);
+        }
+
+        let start = self.next;
+        self.next += self.frames[start..].partition_point(|f| f.offset <= self.position);
+
+        let mut due = std::mem::take(&mut self.reshow);
+        due.extend(start..self.next);
+        due.into_iter().map(|idx| &self.frames[idx]).collect()
+    }
+
+    fn status(&self) -> ReplayStatus {
+        ReplayStatus {
+            position: self.position,
+            length: self.frames.last().map_or(Duration::ZERO, |f| f.offset),
+            paused: self.paused,
+            speed: self.speed,
+        }
+    }
+
+    fn seek(&mut self, to: Duration) {
+        let end = self.frames.last().map_or(Duration::ZERO, |f| f.offset);
+        self.position = to.min(end);
+        self.next = self.frames.partition_point(|f| f.offset <= self.position);
+
+        let mut latest = HashMap::new();
+        for (idx, frame) in self.frames[..self.next].iter().enumerate() {
+            latest.insert(frame.event.source(), idx);
+        }
+        self.reshow = latest.into_values().collect();
+        self.reshow.sort_unstable();
+    }
+}
+
+pub struct ReplayBackend;
+
+impl AgentBackend for ReplayBackend {
+    type Client = ReplayClient;
+    type RuntimeEvent = ReplayRuntimeEvent;
+    const NAME: &'static str = "Replay";
+
+    async fn connect(_timeout: u64) -> anyhow::Result<Self::Client> {
+        SESSION
+            .get()
+            .cloned()
+            .context("no session recording is loaded")
+    }
+
+    // Recordings are decoded by this build, so they always speak its protocol.
+    async fn handshake(_client: &Self::Client) -> anyhow::Result<AgentHello> {
+        Ok(AgentHello {
+            protocol_version: PROTOCOL_VERSION,
+            ..AgentHello::legacy()
+        })
+    }
+
+    async fn ping(_client: &Self::Client) -> anyhow::Result<i32> {
+        Ok(0)
+    }
+
+    async fn perform_scan(client: &Self::Client) -> anyhow::Result<Option<usize>> {
+        let (due, status): (Vec<(Duration, RecordedEvent)>, _) = {
+            let mut cursor = client.0.lock().unwrap();
+            let due = cursor
+                .advance(Instant::now())
+                .into_iter()
+                .map(|frame| (frame.offset, frame.event.clone()))
+                .collect();
+            (due, cursor.status())
+        };
+        for (offset, event) in due {
+            event.replay(offset);
+        }
+        EventBus::publish(status);
+        Ok(None)
+    }
+
+    fn create_runtime_event(
+        state: AgentConnectionState,
+        latency: Option<i32>,
+        capabilities: Option<AgentCapabilities>,
+    ) -> Self::RuntimeEvent {
+        ReplayRuntimeEvent {
+            state,
+            latency_ms: latency,
+            capabilities,
+        }
+    }
+}
+
+pub struct ReplayAgentFeature {
+    pub path: PathBuf,
+}
+
+impl AppFeature for ReplayAgentFeature {
+    fn install(self, ctx: &mut AppFeatureInitContext) -> anyhow::Result<()> {
+        let settings = AgentSettings::new(ctx.shared)?;
+
+        let frames = read_session(&self.path)?;
+        info!(frames = frames.len(), "Replaying {}", self.path.display());
+        SESSION
+            .set(ReplayClient::new(frames, settings.replay_speed().get()))
+            .map_err(|_| anyhow::anyhow!("a session replay is already installed"))?;
+
+        let addr = Addr::new(
+            GenericAgentActor::<ReplayBackend>::new(&settings),
+            ctx.token.clone(),
+            &FeatureLifecycle::new(),
+        );
+
+        let a = addr.clone();
+        ctx.reactor
+            .add_dynamic_loop(settings.ping_interval_ms().as_signal(), move || {
+                a.send(Ping)
+            });
+
+        EventBus::subscribe::<GenericAgentActor<ReplayBackend>, ScanTick>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        EventBus::subscribe::<GenericAgentActor<ReplayBackend>, ReplayControl>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        addr.send(Init);
+        Ok(())
+    }
+}
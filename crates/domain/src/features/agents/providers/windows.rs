@@ -1,7 +1,10 @@
 use crate::agents_impl::actor::{GenericAgentActor, Init, Ping};
-use crate::agents_impl::backend::AgentBackend;
+use crate::agents_impl::backend::{AgentBackend, MalformedReport};
 use crate::features::agents::settings::AgentSettings;
-use app_contracts::features::agents::{ScanTick, WindowsReportMessage};
+use anyhow::bail;
+use app_contracts::features::agents::{
+    AgentCapabilities, AgentHello, Reconnect, ReportConsumed, ScanTick, WindowsReportMessage,
+};
 use app_contracts::features::environments::{
     AgentClient, AgentConnectionState, WindowsAgentRuntimeEvent,
 };
@@ -35,6 +38,17 @@ impl AgentBackend for WindowsBackend {
             .await
     }
 
+    async fn handshake(client: &Self::Client) -> anyhow::Result<AgentHello> {
+        let resp = client.call(WindowsRequest::Hello).await?;
+        let response = rkyv::deserialize::<WindowsResponse, rkyv::rancor::Error>(*resp.deref())?;
+        match response {
+            WindowsResponse::Hello(hello) => Ok(hello.into()),
+            // Agents that predate the handshake reject a request they cannot decode.
+            WindowsResponse::Error(_) => Ok(AgentHello::legacy()),
+            other => bail!("unexpected Windows handshake response: {other:?}"),
+        }
+    }
+
     async fn ping(client: &Self::Client) -> anyhow::Result<i32> {
         let start = Instant::now();
         client.call(WindowsRequest::Ping).await?;
@@ -42,32 +56,34 @@ impl AgentBackend for WindowsBackend {
     }
 
     #[instrument(skip(client), level = "debug", err)]
-    async fn perform_scan(client: &Self::Client) -> anyhow::Result<()> {
+    async fn perform_scan(client: &Self::Client) -> anyhow::Result<Option<usize>> {
         let resp = client.call(WindowsRequest::GetReport).await?;
 
         let response = rkyv::deserialize::<WindowsResponse, rkyv::rancor::Error>(*resp.deref())
             .map_err(|e| {
                 error!(error = %e, "Deserialization failed");
-                anyhow::anyhow!("Failed to deserialize WindowsResponse: {}", e)
+                MalformedReport(format!("Failed to deserialize WindowsResponse: {}", e))
             })?;
 
         if let WindowsResponse::Report(r) = response {
-            EventBus::publish(WindowsReportMessage(r));
+            EventBus::publish(WindowsReportMessage(r, None));
             ratelimit!(3600, info!("Report published to event bus"));
         } else {
             warn!("Unexpected response type: {:?}", response);
         }
 
-        Ok(())
+        Ok(Some(resp.len()))
     }
 
     fn create_runtime_event(
         state: AgentConnectionState,
         latency: Option<i32>,
+        capabilities: Option<AgentCapabilities>,
     ) -> Self::RuntimeEvent {
         WindowsAgentRuntimeEvent {
             state,
             latency_ms: latency,
+            capabilities,
         }
     }
 }
@@ -78,7 +94,7 @@ impl AppFeature for WindowsAgentFeature {
         let settings = AgentSettings::new(ctx.shared)?;
 
         let addr = Addr::new(
-            GenericAgentActor::<WindowsBackend>::new(settings.connect_timeout_secs()),
+            GenericAgentActor::<WindowsBackend>::new(&settings),
             ctx.token.clone(),
             &FeatureLifecycle::new(),
         );
@@ -94,6 +110,15 @@ impl AppFeature for WindowsAgentFeature {
             &FeatureLifecycle::new(),
         );
 
+        EventBus::subscribe::<GenericAgentActor<WindowsBackend>, Reconnect>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        EventBus::subscribe::<GenericAgentActor<WindowsBackend>, ReportConsumed>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+
         addr.send(Init);
         Ok(())
     }
@@ -1,11 +1,15 @@
 use crate::agents_impl::actor::{GenericAgentActor, Init, Ping};
-use crate::agents_impl::backend::AgentBackend;
+use crate::agents_impl::backend::{AgentBackend, MalformedReport};
 use crate::features::agents::settings::AgentSettings;
-use app_contracts::features::agents::{RemoteScanResult, ScanTick};
+use anyhow::bail;
+use app_contracts::features::agents::{
+    AgentCapabilities, AgentHello, LinuxActionRequest, Reconnect, RemoteScanResult, ReportConsumed,
+    ScanTick,
+};
 use app_contracts::features::environments::{
     AgentConnectionState, WslAgentRuntimeEvent, WslClient,
 };
-use app_core::actor::event_bus::EventBus;
+use app_core::actor::event_bus::{EventBus, RpcRequest};
 use app_core::{actor::addr::Addr, ratelimit};
 use framework::feature::{AppFeature, AppFeatureInitContext};
 use framework::lifecycle_tracker::FeatureLifecycle;
@@ -34,6 +38,17 @@ impl AgentBackend for WslBackend {
             .await
     }
 
+    async fn handshake(client: &Self::Client) -> anyhow::Result<AgentHello> {
+        let resp = client.call(LinuxRequest::Hello).await?;
+        let response = rkyv::deserialize::<LinuxResponse, rkyv::rancor::Error>(*resp.deref())?;
+        match response {
+            LinuxResponse::Hello(hello) => Ok(hello.into()),
+            // Agents that predate the handshake reject a request they cannot decode.
+            LinuxResponse::Error(_) => Ok(AgentHello::legacy()),
+            other => bail!("unexpected WSL handshake response: {other:?}"),
+        }
+    }
+
     async fn ping(client: &Self::Client) -> anyhow::Result<i32> {
         let start = Instant::now();
         client.call(LinuxRequest::Ping).await?;
@@ -41,13 +56,13 @@ impl AgentBackend for WslBackend {
     }
 
     #[instrument(skip(client), level = "debug", fields(target = "wsl"), err)]
-    async fn perform_scan(client: &Self::Client) -> anyhow::Result<()> {
+    async fn perform_scan(client: &Self::Client) -> anyhow::Result<Option<usize>> {
         let resp = client.call(LinuxRequest::GetReport).await?;
 
         let report = rkyv::deserialize::<LinuxResponse, rkyv::rancor::Error>(*resp.deref())
             .map_err(|e| {
                 error!(error = %e, "Failed to deserialize WSL response");
-                anyhow::anyhow!("WSL scan deserialization error: {}", e)
+                MalformedReport(format!("WSL scan deserialization error: {}", e))
             })?;
 
         if let LinuxResponse::Report(r) = report {
@@ -57,6 +72,7 @@ impl AgentBackend for WslBackend {
                 machine: r.machine,
                 environments: r.environments,
                 docker_containers: r.docker_containers,
+                recorded_at: None,
             });
 
             ratelimit!(3600, info!("Report published to event bus"));
@@ -64,16 +80,18 @@ impl AgentBackend for WslBackend {
             warn!(response = ?report, "Unexpected WSL response type â€” strange");
         }
 
-        Ok(())
+        Ok(Some(resp.len()))
     }
 
     fn create_runtime_event(
         state: AgentConnectionState,
         latency: Option<i32>,
+        capabilities: Option<AgentCapabilities>,
     ) -> Self::RuntimeEvent {
         WslAgentRuntimeEvent {
             state: state,
             latency_ms: latency,
+            capabilities,
         }
     }
 }
@@ -84,7 +102,7 @@ impl AppFeature for WslAgentFeature {
         let settings = AgentSettings::new(ctx.shared)?;
 
         let addr = Addr::new(
-            GenericAgentActor::<WslBackend>::new(settings.connect_timeout_secs()),
+            GenericAgentActor::<WslBackend>::new(&settings),
             ctx.token.clone(),
             &FeatureLifecycle::new(),
         );
@@ -99,6 +117,18 @@ impl AppFeature for WslAgentFeature {
             addr.clone(),
             &FeatureLifecycle::new(),
         );
+        EventBus::subscribe::<GenericAgentActor<WslBackend>, RpcRequest<LinuxActionRequest>>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        EventBus::subscribe::<GenericAgentActor<WslBackend>, ReportConsumed>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
+        EventBus::subscribe::<GenericAgentActor<WslBackend>, Reconnect>(
+            addr.clone(),
+            &FeatureLifecycle::new(),
+        );
         addr.send(Init);
         Ok(())
     }
@@ -0,0 +1,43 @@
+use crate::agents_impl::session::SessionWriter;
+use app_contracts::features::agents::RemoteScanResult;
+use app_core::actor::ManagedActor;
+use macros::{actor_manifest, handler};
+
+#[cfg(target_os = "windows")]
+use app_contracts::features::agents::WindowsReportMessage;
+
+// Persists every agent report as it is published, for later replay.
+pub struct SessionRecorderActor {
+    writer: SessionWriter,
+}
+
+impl SessionRecorderActor {
+    pub fn new(writer: SessionWriter) -> Self {
+        Self { writer }
+    }
+}
+
+#[actor_manifest]
+impl ManagedActor for SessionRecorderActor {
+    type Bus = bus!(
+        RemoteScanResult,
+        #[cfg(target_os = "windows")]
+        WindowsReportMessage,
+    );
+    type Handlers = handlers!(
+        @RemoteScanResult,
+        #[cfg(target_os = "windows")]
+        @WindowsReportMessage,
+    );
+}
+
+#[handler]
+fn record_remote_scan(this: &mut SessionRecorderActor, msg: RemoteScanResult) {
+    this.writer.record(msg);
+}
+
+#[cfg(target_os = "windows")]
+#[handler]
+fn record_windows_report(this: &mut SessionRecorderActor, msg: WindowsReportMessage) {
+    this.writer.record(msg);
+}
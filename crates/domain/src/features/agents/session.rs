@@ -0,0 +1,201 @@
+use anyhow::{Context, bail};
+use app_contracts::features::agents::RemoteScanResult;
+use app_core::actor::event_bus::EventBus;
+use rkyv::util::AlignedVec;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use tracing::warn;
+use uniproc_protocol::{
+    LinuxDockerContainerInfo, LinuxEnvironmentInfo, LinuxMachineStats, LinuxProcessStats,
+};
+
+#[cfg(target_os = "windows")]
+use app_contracts::features::agents::WindowsReportMessage;
+#[cfg(target_os = "windows")]
+use uniproc_protocol::WindowsReport;
+
+// File layout: MAGIC, then frames of [offset_ms: u64 LE][len: u32 LE][rkyv RecordedEvent].
+// Bumped with the report layout; older recordings no longer decode.
+const MAGIC: &[u8; 8] = b"UPSESS02";
+
+#[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Clone)]
+pub enum RecordedEvent {
+    Remote {
+        schema_id: String,
+        processes: Vec<LinuxProcessStats>,
+        machine: LinuxMachineStats,
+        environments: Vec<LinuxEnvironmentInfo>,
+        docker_containers: Vec<LinuxDockerContainerInfo>,
+    },
+    #[cfg(target_os = "windows")]
+    Windows(WindowsReport),
+}
+
+impl RecordedEvent {
+    // Frames of the same source replace each other when replay skips ahead.
+    pub fn source(&self) -> &str {
+        match self {
+            Self::Remote { schema_id, .. } => schema_id,
+            #[cfg(target_os = "windows")]
+            Self::Windows(_) => "windows",
+        }
+    }
+
+    // Whether anything takes reports of this kind in; nobody acknowledges a report nobody reads.
+    pub fn has_consumers(&self) -> bool {
+        match self {
+            Self::Remote { .. } => EventBus::has_subscribers::<RemoteScanResult>(),
+            #[cfg(target_os = "windows")]
+            Self::Windows(_) => EventBus::has_subscribers::<WindowsReportMessage>(),
+        }
+    }
+
+    pub fn publish(self) {
+        self.publish_at(None);
+    }
+
+    // Publishes a frame of a recording, tagged with its offset.
+    pub fn replay(self, offset: Duration) {
+        self.publish_at(Some(offset));
+    }
+
+    fn publish_at(self, recorded_at: Option<Duration>) {
+        match self {
+            Self::Remote {
+                schema_id,
+                processes,
+                machine,
+                environments,
+                docker_containers,
+            } => EventBus::publish(RemoteScanResult {
+                schema_id: intern_schema_id(&schema_id),
+                processes,
+                machine,
+                environments,
+                docker_containers,
+                recorded_at,
+            }),
+            #[cfg(target_os = "windows")]
+            Self::Windows(report) => EventBus::publish(WindowsReportMessage(report, recorded_at)),
+        }
+    }
+}
+
+impl From<RemoteScanResult> for RecordedEvent {
+    fn from(msg: RemoteScanResult) -> Self {
+        Self::Remote {
+            schema_id: msg.schema_id.to_string(),
+            processes: msg.processes,
+            machine: msg.machine,
+            environments: msg.environments,
+            docker_containers: msg.docker_containers,
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl From<WindowsReportMessage> for RecordedEvent {
+    fn from(msg: WindowsReportMessage) -> Self {
+        Self::Windows(msg.0)
+    }
+}
+
+// Schema ids are compared by value downstream; only ids unknown to this build are leaked.
+fn intern_schema_id(id: &str) -> &'static str {
+    match id {
+        "wsl" => "wsl",
+        "linux" => "linux",
+        other => Box::leak(other.to_owned().into_boxed_str()),
+    }
+}
+
+pub struct RecordedFrame {
+    pub offset: Duration,
+    pub event: RecordedEvent,
+}
+
+// A recording cut short by a crash keeps every frame before the truncated one.
+pub fn read_session(path: &Path) -> anyhow::Result<Vec<RecordedFrame>> {
+    let raw = std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let Some(mut rest) = raw.strip_prefix(MAGIC.as_slice()) else {
+        bail!("{} is not a session recording", path.display());
+    };
+
+    let mut frames = Vec::new();
+    while rest.len() >= 12 {
+        let offset_ms = u64::from_le_bytes(rest[..8].try_into()?);
+        let len = u32::from_le_bytes(rest[8..12].try_into()?) as usize;
+        let Some(body) = rest.get(12..12 + len) else {
+            warn!(
+                "session recording is truncated after {} frames",
+                frames.len()
+            );
+            break;
+        };
+
+        let mut aligned = AlignedVec::<16>::with_capacity(len);
+        aligned.extend_from_slice(body);
+        let event = rkyv::from_bytes::<RecordedEvent, rkyv::rancor::Error>(&aligned)
+            .with_context(|| format!("frame {} is corrupted", frames.len()))?;
+
+        frames.push(RecordedFrame {
+            offset: Duration::from_millis(offset_ms),
+            event,
+        });
+        rest = &rest[12 + len..];
+    }
+
+    Ok(frames)
+}
+
+// Serializes and writes on a background thread so recording never blocks the bus.
+pub struct SessionWriter {
+    started: Instant,
+    tx: mpsc::Sender<(Duration, RecordedEvent)>,
+}
+
+impl SessionWriter {
+    pub fn create(path: &Path) -> anyhow::Result<Self> {
+        let mut file =
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        file.write_all(MAGIC)?;
+
+        let (tx, rx) = mpsc::channel::<(Duration, RecordedEvent)>();
+        std::thread::Builder::new()
+            .name("session-recorder".into())
+            .spawn(move || {
+                let mut out = BufWriter::new(file);
+                for (offset, event) in rx {
+                    if let Err(e) = write_frame(&mut out, offset, &event) {
+                        warn!("session recording stopped: {e}");
+                        break;
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            started: Instant::now(),
+            tx,
+        })
+    }
+
+    pub fn record(&self, event: impl Into<RecordedEvent>) {
+        let _ = self.tx.send((self.started.elapsed(), event.into()));
+    }
+}
+
+fn write_frame(
+    out: &mut impl Write,
+    offset: Duration,
+    event: &RecordedEvent,
+) -> anyhow::Result<()> {
+    let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(event)?;
+    out.write_all(&(offset.as_millis() as u64).to_le_bytes())?;
+    out.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    out.write_all(&bytes)?;
+    out.flush()?;
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use macros::feature_settings;
+use serde::{Deserialize, Serialize};
 
 #[feature_settings(prefix = "agents")]
 pub struct AgentSettings {
@@ -7,4 +8,66 @@ pub struct AgentSettings {
 
     #[setting(default = 2000u64)]
     pub ping_interval_ms: u64,
+
+    // Backoff after a connect or handshake fails.
+    #[setting(default = serde_json::json!({}))]
+    pub connect_retry: RetryPolicy,
+
+    // Backoff for reconnecting after an established connection drops.
+    #[setting(default = serde_json::json!({ "base-secs": 0.5, "max-secs": 10.0 }))]
+    pub reconnect_retry: RetryPolicy,
+
+    // Empty disables recording; otherwise every agent report is appended to this file.
+    #[setting(default = String::new())]
+    pub record_path: String,
+
+    // A recording to play back instead of connecting to live agents.
+    #[setting(default = String::new())]
+    pub replay_path: String,
+
+    #[setting(default = 1.0f64)]
+    pub replay_speed: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct RetryPolicy {
+    pub base_secs: f64,
+    pub max_secs: f64,
+    pub multiplier: f64,
+    // Each delay is spread by up to this fraction in either direction, 0 disables it.
+    pub jitter: f64,
+    // Failed attempts in a row before giving up; 0 retries forever.
+    pub max_attempts: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            base_secs: 1.0,
+            max_secs: 15.0,
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_attempts: 0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    // `attempt` counts from 0; `unit` is a random value in 0..1 that picks the jitter.
+    pub fn delay(&self, attempt: u32, unit: f64) -> std::time::Duration {
+        let exp = self.multiplier.max(1.0).powi(attempt.min(64) as i32);
+        let delay = (self.base_secs * exp).min(self.max_secs);
+        let spread = delay * self.jitter.clamp(0.0, 1.0) * (unit * 2.0 - 1.0);
+        let secs = delay + spread;
+        if secs.is_finite() && secs > 0.0 {
+            std::time::Duration::from_secs_f64(secs)
+        } else {
+            std::time::Duration::ZERO
+        }
+    }
+
+    pub fn gives_up_after(&self, attempts: u32) -> bool {
+        self.max_attempts > 0 && attempts >= self.max_attempts
+    }
 }
@@ -0,0 +1,104 @@
+use app_contracts::features::agents::{AgentHealth, ReplayControl, ReplayStatus};
+use app_contracts::features::environments::{
+    EnvironmentsBinder, UiEnvironmentsBindings, UiEnvironmentsPort,
+};
+use app_core::actor::ManagedActor;
+use app_core::actor::addr::Addr;
+use app_core::actor::event_bus::EventBus;
+use framework::app::Window;
+use framework::feature::{WindowFeature, WindowFeatureInitContext};
+use macros::{actor_manifest, handler, window_feature};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+// Drives the diagnostics page: the latest health of every agent backend and the controls of a
+// session replay.
+pub struct AgentDiagnosticsActor<P: UiEnvironmentsPort> {
+    backends: BTreeMap<&'static str, AgentHealth>,
+    ui_port: P,
+}
+
+#[actor_manifest]
+impl<P: UiEnvironmentsPort> ManagedActor for AgentDiagnosticsActor<P> {
+    type Bus = bus!(AgentHealth, ReplayStatus);
+    type Handlers = handlers!(
+        @AgentHealth,
+        @ReplayStatus,
+        PauseReplay(bool),
+        SeekReplay(f32),
+        SetReplaySpeed(f32)
+    );
+}
+
+impl<P: UiEnvironmentsPort> AgentDiagnosticsActor<P> {
+    pub fn new(ui_port: P) -> Self {
+        Self {
+            backends: BTreeMap::new(),
+            ui_port,
+        }
+    }
+}
+
+#[handler]
+fn on_agent_health<P: UiEnvironmentsPort>(this: &mut AgentDiagnosticsActor<P>, msg: AgentHealth) {
+    // Every ping tick republishes the health; the page only redraws on changes.
+    if this.backends.get(msg.backend) == Some(&msg) {
+        return;
+    }
+    this.backends.insert(msg.backend, msg);
+    this.ui_port
+        .set_agent_health(this.backends.values().cloned().collect());
+}
+
+// Only arrives while a session replay is installed, which is what shows its controls.
+#[handler]
+fn on_replay_status<P: UiEnvironmentsPort>(this: &mut AgentDiagnosticsActor<P>, msg: ReplayStatus) {
+    this.ui_port.set_replay_status(msg);
+}
+
+#[handler]
+fn pause_replay<P: UiEnvironmentsPort>(_: &mut AgentDiagnosticsActor<P>, msg: PauseReplay) {
+    EventBus::publish(if msg.0 {
+        ReplayControl::Pause
+    } else {
+        ReplayControl::Resume
+    });
+}
+
+#[handler]
+fn seek_replay<P: UiEnvironmentsPort>(_: &mut AgentDiagnosticsActor<P>, msg: SeekReplay) {
+    let to = Duration::try_from_secs_f32(msg.0).unwrap_or_default();
+    EventBus::publish(ReplayControl::Seek(to));
+}
+
+#[handler]
+fn set_replay_speed<P: UiEnvironmentsPort>(_: &mut AgentDiagnosticsActor<P>, msg: SetReplaySpeed) {
+    EventBus::publish(ReplayControl::SetSpeed(msg.0 as f64));
+}
+
+#[window_feature]
+pub struct DiagnosticsFeature;
+
+#[window_feature]
+impl<TWindow, F, P> WindowFeature<TWindow> for DiagnosticsFeature<F>
+where
+    TWindow: Window,
+    F: Fn(&TWindow) -> P + 'static + Clone,
+    P: UiEnvironmentsPort + UiEnvironmentsBindings + Clone + 'static,
+{
+    fn install(&mut self, ctx: &mut WindowFeatureInitContext<TWindow>) -> anyhow::Result<()> {
+        let ui_port = (self.make_port)(ctx.ui);
+        let token = ctx.ui.new_token();
+        let addr = Addr::new_managed(
+            AgentDiagnosticsActor::new(ui_port.clone()),
+            token,
+            &self.tracker,
+        );
+
+        EnvironmentsBinder::new(&addr, &ui_port)
+            .on_replay_pause(PauseReplay)
+            .on_replay_seek(SeekReplay)
+            .on_replay_speed(SetReplaySpeed);
+        Ok(())
+    }
+}
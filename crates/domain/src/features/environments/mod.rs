@@ -3,6 +3,7 @@ use framework::app::Window;
 use framework::feature::{WindowFeature, WindowFeatureInitContext};
 use macros::window_feature;
 
+pub mod diagnostics;
 pub mod host;
 pub mod wsl;
 
@@ -19,6 +20,7 @@ where
     fn install(&mut self, ctx: &mut WindowFeatureInitContext<TWindow>) -> anyhow::Result<()> {
         host::HostFeature::new(self.make_port.clone()).install(ctx)?;
         wsl::WslFeature::new(self.make_port.clone()).install(ctx)?;
+        diagnostics::DiagnosticsFeature::new(self.make_port.clone()).install(ctx)?;
         Ok(())
     }
 }
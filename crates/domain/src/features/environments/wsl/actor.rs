@@ -1,14 +1,19 @@
 use crate::features::environments::wsl::domain::{
     check_wsl_availability_async, fetch_distros_data, inject_agent_async,
 };
+use app_contracts::features::agents::Reconnect;
 use app_contracts::features::environments::{
     UiEnvironmentsPort, WslAgentRuntimeEvent, WslConnectionState, WslDistroDto,
 };
+use app_core::actor::event_bus::EventBus;
 use app_core::actor::{Context, ManagedActor};
 
 use macros::{actor_manifest, handler};
 use std::fmt::Debug;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+// Name of the WSL agent backend; reconnects from this page only concern it.
+const WSL_BACKEND: &str = "WSL";
 
 #[actor_manifest]
 impl<P: UiEnvironmentsPort> ManagedActor for WslEnvActor<P> {
@@ -16,6 +21,7 @@ impl<P: UiEnvironmentsPort> ManagedActor for WslEnvActor<P> {
     type Handlers = handlers!(
         Init,
         InstallAgent(String),
+        ReconnectAgent,
         CheckStatus,
         SetStatus(bool),
         RefreshDistros,
@@ -25,6 +31,10 @@ impl<P: UiEnvironmentsPort> ManagedActor for WslEnvActor<P> {
 
 pub struct WslEnvActor<P: UiEnvironmentsPort> {
     distros: Vec<WslDistroDto>,
+    // Set while the agent answers with a protocol this build does not accept.
+    agent_outdated: bool,
+    // Set once the retry policy stopped trying; cleared by the next attempt.
+    agent_gave_up: bool,
     ui_port: P,
 }
 
@@ -32,6 +42,8 @@ impl<P: UiEnvironmentsPort> WslEnvActor<P> {
     pub fn new(ui_port: P) -> Self {
         Self {
             distros: Vec::new(),
+            agent_outdated: false,
+            agent_gave_up: false,
             ui_port,
         }
     }
@@ -42,6 +54,16 @@ impl<P: UiEnvironmentsPort> WslEnvActor<P> {
 
     fn set_distros(&mut self, updated: Vec<WslDistroDto>) {
         self.distros = updated;
+        self.apply_agent_issues(self.agent_outdated, self.agent_gave_up);
+    }
+
+    fn apply_agent_issues(&mut self, outdated: bool, gave_up: bool) {
+        self.agent_outdated = outdated;
+        self.agent_gave_up = gave_up;
+        self.distros.iter_mut().for_each(|d| {
+            d.agent_outdated = outdated && d.is_installed;
+            d.agent_gave_up = gave_up && d.is_running;
+        });
         self.sync_to_ui();
     }
 
@@ -64,8 +86,12 @@ fn handle_agent_runtime_event<P: UiEnvironmentsPort>(
     this: &mut WslEnvActor<P>,
     msg: WslAgentRuntimeEvent,
 ) {
+    let had_issue = this.agent_outdated || this.agent_gave_up;
     match msg.state {
         WslConnectionState::Connected => {
+            if had_issue {
+                this.apply_agent_issues(false, false);
+            }
             if let Some(latency_ms) = msg.latency_ms {
                 this.apply_latency(latency_ms);
             }
@@ -73,7 +99,20 @@ fn handle_agent_runtime_event<P: UiEnvironmentsPort>(
         WslConnectionState::Disconnected | WslConnectionState::WaitingRetry { .. } => {
             this.apply_disconnected();
         }
-        WslConnectionState::Connecting => {}
+        WslConnectionState::Incompatible { agent_version } => {
+            warn!("WSL agent speaks protocol {agent_version} and needs an update");
+            this.apply_agent_issues(true, false);
+        }
+        WslConnectionState::GaveUp { attempts } => {
+            warn!("Stopped connecting to the WSL agent after {attempts} attempts");
+            this.apply_disconnected();
+            this.apply_agent_issues(false, true);
+        }
+        WslConnectionState::Connecting | WslConnectionState::Handshaking => {
+            if had_issue {
+                this.apply_agent_issues(false, false);
+            }
+        }
     }
 }
 
@@ -121,6 +160,8 @@ fn refresh_distros<P: UiEnvironmentsPort>(
                 is_installed: d.is_installed,
                 is_running: d.is_running,
                 latency_ms: d.latency_ms,
+                agent_outdated: false,
+                agent_gave_up: false,
             })
             .collect();
         UpdateDistros(distros)
@@ -142,9 +183,21 @@ fn install_agent<P: UiEnvironmentsPort>(
     let distro_name = msg.0;
     ctx.spawn_bg(async move {
         match inject_agent_async(&distro_name).await {
-            Ok(_) => info!("Agent installed in {distro_name}"),
+            Ok(_) => {
+                info!("Agent installed in {distro_name}");
+                EventBus::publish(Reconnect {
+                    backend: WSL_BACKEND,
+                });
+            }
             Err(err) => error!("Failed to install agent in {distro_name}: {err}"),
         }
         RefreshDistros
     });
 }
+
+#[handler]
+fn reconnect_agent<P: UiEnvironmentsPort>(_: &mut WslEnvActor<P>, _: ReconnectAgent) {
+    EventBus::publish(Reconnect {
+        backend: WSL_BACKEND,
+    });
+}
@@ -2,7 +2,7 @@ use framework::app::Window;
 mod actor;
 pub mod domain;
 
-pub use actor::{Init, InstallAgent, WslEnvActor};
+pub use actor::{Init, InstallAgent, ReconnectAgent, WslEnvActor};
 
 use app_contracts::features::environments::{
     EnvironmentsBinder, UiEnvironmentsBindings, UiEnvironmentsPort,
@@ -26,7 +26,9 @@ where
         let token = ctx.ui.new_token();
         let addr = Addr::new_managed(WslEnvActor::new(ui_port.clone()), token, &self.tracker);
 
-        EnvironmentsBinder::new(&addr, &ui_port).on_install_agent(InstallAgent);
+        EnvironmentsBinder::new(&addr, &ui_port)
+            .on_install_agent(InstallAgent)
+            .on_reconnect_agent(ReconnectAgent);
 
         addr.send(Init);
         Ok(())
@@ -18,6 +18,7 @@ pub fn apply<P: L10nPort>(port: &P) {
     port.set_services_pid(t!("services.pid").to_string());
     port.set_services_properties(t!("services.properties").to_string());
     port.set_services_restart(t!("services.restart").to_string());
+    port.set_services_search_placeholder(t!("services.search_placeholder").to_string());
     port.set_services_service_name(t!("services.service_name").to_string());
     port.set_services_start(t!("services.start").to_string());
     port.set_services_stop(t!("services.stop").to_string());
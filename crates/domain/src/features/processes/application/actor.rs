@@ -1,28 +1,47 @@
 use crate::features::processes::domain::table::ProcessTable;
+use crate::features::processes::services::alerts;
+use crate::features::processes::services::control::{self, ProcessCommand, ProcessTarget};
+use crate::features::processes::services::details;
 use crate::features::processes::services::metadata::ProcessMetadataService;
 use crate::processes_impl::application::process_snapshot_actor::{
     ActiveStatus, ProcessSnapshotReady,
 };
+use crate::processes_impl::domain::history::MetricHistory;
 use crate::processes_impl::domain::snapshot::BridgeSnapshot;
+use crate::processes_impl::domain::source;
+use crate::processes_impl::domain::table_builder::ProcessTreeMode;
+use crate::processes_impl::settings::AlertAction;
+use app_contracts::features::agents::AgentCapabilities;
 #[cfg(target_os = "windows")]
 use app_contracts::features::environments::WindowsAgentRuntimeEvent;
 use app_contracts::features::environments::{AgentConnectionState, WslAgentRuntimeEvent};
-use app_contracts::features::processes::UiProcessesPort;
+use app_contracts::features::processes::{
+    AlertRaised, ProcessActivity, ProcessDetailsDto, ProcessExited, ProcessKey, ProcessStarted,
+    UiProcessDetailsPort, UiProcessesPort, DETAILS_WINDOW_KEY,
+};
 use app_contracts::features::tabs::TabContextKey;
+use app_contracts::features::windows_manager::OpenedWindow;
 use app_core::actor::event_bus::EventBus;
 use app_core::actor::ManagedActor;
-use app_core::actor::{Context, Handler, Message, NoOp};
+use app_core::actor::{Context, Handler, Message};
 use context::page_status::{PageStatus, RouteStatusChanged, RouteStatusRegistry};
 use framework::feature::{Events, FeatureComponent, FeatureContextState};
+use framework::native_windows::slint_factory::{
+    OpenWindow, SlintWindowRegistry, WindowClosed, WindowRegistry,
+};
 use framework::navigation::RouteActivated;
-use framework::settings::SettingSubscription;
+use framework::settings::{ReactiveSetting, SettingSubscription};
 use framework::uri::AppUri;
 use macros::{actor_manifest, handler};
 use slint::SharedString;
 use std::borrow::Cow;
-use std::sync::Arc;
-use sysinfo::{Pid, ProcessesToUpdate, System};
-use tracing::{info, instrument};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, instrument, warn};
+use widgets::table::export::{ExportFormat, NdjsonRecorder};
+use widgets::table::flow::SelectMode;
 
 pub struct ProcessActor<P: UiProcessesPort> {
     pub table: ProcessTable,
@@ -30,24 +49,52 @@ pub struct ProcessActor<P: UiProcessesPort> {
     pub route_status: Arc<RouteStatusRegistry>,
     pub is_active: bool,
     pub active_context_key: Cow<'static, str>,
-    pub is_grouped: bool,
     pub ui_port: P,
     pub has_snapshot_data: bool,
+    pub history: Arc<Mutex<MetricHistory>>,
+    pub terminate_timeout_ms: ReactiveSetting<u64>,
+    pub scope_to_context: ReactiveSetting<bool>,
+    pub registry: Arc<SlintWindowRegistry>,
+    pub details_windows: HashMap<String, DetailsWindow>,
+    pub recorder: Option<NdjsonRecorder>,
+    pub alerts: VecDeque<AlertRaised>,
+    pub activity: VecDeque<ProcessActivity>,
+    pub activity_filter: SharedString,
+    pub activity_dirty: bool,
+    // As reported by the WSL agent's handshake; unknown until it connects.
+    pub wsl_capabilities: Option<AgentCapabilities>,
     pub ctx: FeatureContextState,
 }
 
+const MAX_ALERTS: usize = 200;
+const MAX_ACTIVITY: usize = 500;
+
+pub struct DetailsWindow {
+    pub target: ProcessTarget,
+    pub in_flight: bool,
+}
+
 #[actor_manifest]
 impl<P: UiProcessesPort> ManagedActor for ProcessActor<P> {
     type Bus = Events<
         bus!(
+            ColumnLayoutChanged,
+            OpenedWindow,
+            WindowClosed,
             WslAgentRuntimeEvent,
             #[cfg(target_os = "windows")]
             WindowsAgentRuntimeEvent,
+            ProcessStarted,
+            ProcessExited,
         ),
     >;
     type Handlers = handlers!(
         @WslAgentRuntimeEvent,
         @WindowsAgentRuntimeEvent,
+        @ProcessStarted,
+        @ProcessExited,
+        @OpenedWindow,
+        @WindowClosed,
         GroupClicked,
         Sort(SharedString),
         ToggleExpand(SharedString),
@@ -56,14 +103,59 @@ impl<P: UiProcessesPort> ManagedActor for ProcessActor<P> {
             count: usize
         },
         Select {
+            source: SharedString,
+            pid: u32,
+            idx: usize,
+            mode: SelectMode
+        },
+        TerminateSelected {
+            tree: bool
+        },
+        RunCommand(ProcessCommand),
+        SetAffinity(SharedString),
+        ProcessActionResult {
             pid: u32,
-            idx: usize
+            name: SharedString,
+            action: &'static str,
+            error: Option<String>
+        },
+        OpenDetails {
+            source: SharedString,
+            pid: u32
+        },
+        TogglePin {
+            source: SharedString,
+            pid: u32
+        },
+        DetailsFetched {
+            key: String,
+            details: ProcessDetailsDto
         },
-        TerminateSelected,
+        Export(SharedString),
+        ExportFinished {
+            path: String,
+            rows: usize,
+            error: Option<String>
+        },
+        ToggleRecording(SharedString),
+        AlertFired {
+            alert: AlertRaised,
+            action: AlertAction
+        },
+        ClearAlerts,
+        FilterActivity(SharedString),
+        Filter(SharedString),
+        ToggleScope,
         ResizeColumn {
             id: String,
             width: f32
-        }
+        },
+        ToggleColumn(SharedString),
+        MoveColumn {
+            id: SharedString,
+            delta: i32
+        },
+        ColumnLayoutChanged
     );
 }
 
@@ -76,6 +168,7 @@ impl<P: UiProcessesPort> FeatureComponent for ProcessActor<P> {
         self.is_active = true;
         EventBus::publish(ActiveStatus(true));
         self.active_context_key = uri.context_name.clone();
+        self.table.apply_filter(&self.active_context_key);
     }
 
     fn on_deactivated(&mut self, _: &AppUri, _: &Context<Self>) {
@@ -91,6 +184,79 @@ impl<P: UiProcessesPort> ProcessActor<P> {
             .set_process_rows_window(batch.total_rows, batch.start, batch.rows);
     }
 
+    fn push_columns(&self) {
+        self.ui_port
+            .set_column_defs(self.table.get_header_columns());
+        self.ui_port.set_column_widths(self.table.column_widths());
+        self.ui_port
+            .set_column_metadata(self.table.column_metadata());
+        self.ui_port.set_column_choices(self.table.column_choices());
+    }
+
+    fn push_history(&self) {
+        let columns = self.table.get_header_columns();
+        let history = self.history.lock().unwrap();
+
+        let machine = source::machine_schema(&self.active_context_key, |s| history.has_machine(s))
+            .map(|schema| history.machine_series(schema, &columns))
+            .unwrap_or_default();
+        self.ui_port.set_header_history(machine);
+
+        let pid = self.ui_port.get_selected_pid();
+        let source = self.ui_port.get_selected_source();
+        let selected = match u32::try_from(pid)
+            .ok()
+            .and_then(|pid| self.table.key_for(&source, pid))
+        {
+            Some(key) => history.process_series(key, &columns),
+            None => Vec::new(),
+        };
+        self.ui_port.set_selected_history(selected);
+    }
+
+    // Skips windows whose previous fetch has not come back yet.
+    fn fetch_details(&mut self, key: &str, ctx: &Context<Self>) {
+        let Some(window) = self.details_windows.get_mut(key) else {
+            return;
+        };
+        if window.in_flight {
+            return;
+        }
+        window.in_flight = true;
+
+        let key = key.to_string();
+        let target = window.target.clone();
+        ctx.spawn_bg(async move {
+            let details = details::fetch(&target)
+                .await
+                .unwrap_or_else(|e| ProcessDetailsDto {
+                    pid: target.pid,
+                    name: target.name.clone(),
+                    error: Some(e.to_string().into()),
+                    ..Default::default()
+                });
+            DetailsFetched { key, details }
+        });
+    }
+
+    fn push_activity(&mut self) {
+        let filter = self.activity_filter.as_str();
+        self.ui_port.set_activity(
+            self.activity
+                .iter()
+                .filter(|a| a.matches(filter))
+                .cloned()
+                .collect(),
+        );
+        self.activity_dirty = false;
+    }
+
+    fn record_activity(&mut self, activity: ProcessActivity) {
+        self.activity.push_front(activity);
+        self.activity.truncate(MAX_ACTIVITY);
+        self.activity_dirty = true;
+    }
+
     fn set_empty_state(&self, visible: bool, title: &str, message: &str) {
         self.ui_port.set_empty_state_visible(visible);
         self.ui_port.set_empty_state_title(title.into());
@@ -104,6 +270,26 @@ impl<P: UiProcessesPort> ProcessActor<P> {
             "The process list will appear after the agent connects and sends its first snapshot.",
         );
     }
+
+    // Answers right away for agents that reported they cannot control processes.
+    fn refuse_unsupported(
+        &self,
+        target: &ProcessTarget,
+        action: &'static str,
+        ctx: &Context<Self>,
+    ) -> bool {
+        let unsupported =
+            target.schema_id == "wsl" && self.wsl_capabilities.is_some_and(|c| !c.process_control);
+        if unsupported {
+            ctx.addr().send(ProcessActionResult {
+                pid: target.pid,
+                name: target.name.clone(),
+                action,
+                error: Some("the WSL agent does not support process control".into()),
+            });
+        }
+        unsupported
+    }
 }
 
 #[handler]
@@ -111,23 +297,33 @@ impl<P: UiProcessesPort> ProcessActor<P> {
 fn process_snapshot_ready<P: UiProcessesPort>(
     this: &mut ProcessActor<P>,
     msg: ProcessSnapshotReady,
+    ctx: &Context<ProcessActor<P>>,
 ) {
     let processes = msg.processes.lock().unwrap().clone();
     this.has_snapshot_data = msg.total_count > 0;
+    if this.table.set_distros(msg.distros) {
+        this.table.apply_filter(&this.active_context_key);
+    }
 
     let snapshot = BridgeSnapshot {
         column_defs: msg.column_defs,
         processes,
     };
 
-    let _ = this.table.handle_snapshot(snapshot, &mut this.metadata);
+    if let Some(recorder) = &this.recorder {
+        match snapshot.export().to_record_line() {
+            Ok(line) => recorder.record(line),
+            Err(e) => warn!("failed to serialize snapshot for recording: {e}"),
+        }
+    }
 
+    let _ = this.table.handle_snapshot(snapshot, &mut this.metadata);
     this.ui_port
-        .set_column_defs(this.table.get_header_columns());
-    this.ui_port.set_column_widths(this.table.column_widths());
-    this.ui_port
-        .set_column_metadata(this.table.column_metadata());
+        .set_stuck_count(this.table.stuck_count() as i32);
+
+    this.push_columns();
     this.ui_port.set_total_processes_count(msg.total_count);
+    this.push_history();
 
     if msg.total_count == 0 {
         this.set_empty_state(
@@ -147,6 +343,16 @@ fn process_snapshot_ready<P: UiProcessesPort>(
     });
 
     this.push_batch();
+    // Diff events arrive one by one ahead of the snapshot; the log is pushed once per snapshot.
+    if this.activity_dirty {
+        this.push_activity();
+    }
+
+    // Open details windows follow the snapshots; one whose last fetch is still running is skipped.
+    let open: Vec<String> = this.details_windows.keys().cloned().collect();
+    for key in open {
+        this.fetch_details(&key, ctx);
+    }
 }
 
 #[handler]
@@ -154,6 +360,7 @@ fn sync_wsl_agent_status<P: UiProcessesPort>(
     this: &mut ProcessActor<P>,
     msg: WslAgentRuntimeEvent,
 ) {
+    this.wsl_capabilities = msg.capabilities;
     if this.has_snapshot_data {
         return;
     }
@@ -164,12 +371,23 @@ fn sync_wsl_agent_status<P: UiProcessesPort>(
             "Waiting For First Snapshot",
             "The WSL agent is connected. Waiting for it to publish the first process report.",
         ),
-        AgentConnectionState::Connecting | AgentConnectionState::WaitingRetry { .. } => this
-            .set_empty_state(
-                true,
-                "Connecting To WSL Agent",
-                "Process data is unavailable until the WSL agent connection is established.",
-            ),
+        AgentConnectionState::Connecting
+        | AgentConnectionState::Handshaking
+        | AgentConnectionState::WaitingRetry { .. } => this.set_empty_state(
+            true,
+            "Connecting To WSL Agent",
+            "Process data is unavailable until the WSL agent connection is established.",
+        ),
+        AgentConnectionState::Incompatible { .. } => this.set_empty_state(
+            true,
+            "WSL Agent Needs An Update",
+            "The agent installed in WSL is too old for this version. Update it from the environments menu.",
+        ),
+        AgentConnectionState::GaveUp { .. } => this.set_empty_state(
+            true,
+            "WSL Agent Unreachable",
+            "Stopped retrying the WSL agent connection. Reconnect from the environments menu.",
+        ),
         AgentConnectionState::Disconnected => this.set_agent_waiting_state(),
     }
 }
@@ -190,12 +408,23 @@ fn sync_windows_agent_status<P: UiProcessesPort>(
             "Waiting For First Snapshot",
             "The Windows agent is connected. Waiting for it to publish the first process report.",
         ),
-        AgentConnectionState::Connecting | AgentConnectionState::WaitingRetry { .. } => this
-            .set_empty_state(
-                true,
-                "Connecting To Windows Agent",
-                "Process data is unavailable until the Windows agent connection is established.",
-            ),
+        AgentConnectionState::Connecting
+        | AgentConnectionState::Handshaking
+        | AgentConnectionState::WaitingRetry { .. } => this.set_empty_state(
+            true,
+            "Connecting To Windows Agent",
+            "Process data is unavailable until the Windows agent connection is established.",
+        ),
+        AgentConnectionState::Incompatible { .. } => this.set_empty_state(
+            true,
+            "Windows Agent Needs An Update",
+            "The Windows agent is too old for this version. Reinstall it to match.",
+        ),
+        AgentConnectionState::GaveUp { .. } => this.set_empty_state(
+            true,
+            "Windows Agent Unreachable",
+            "Stopped retrying the Windows agent connection. Check that the agent service is running.",
+        ),
         AgentConnectionState::Disconnected => this.set_agent_waiting_state(),
     }
 }
@@ -224,34 +453,391 @@ fn change_viewport<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: Viewport
 
 #[handler]
 fn select_process<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: Select) {
-    this.table.select(msg.pid, msg.idx);
-    this.ui_port.set_selected_pid(msg.pid as i32);
-    if let Some(name) = this.table.selected_name_for_pid(msg.pid) {
-        this.ui_port.set_selected_name(name);
+    this.table.select(&msg.source, msg.pid, msg.idx, msg.mode);
+
+    let selected = this.table.selected_keys();
+    let focused = selected
+        .iter()
+        .find(|k| k.schema_id == msg.source.as_str() && k.pid == msg.pid)
+        .or(selected.first())
+        .copied();
+
+    match focused {
+        Some(key) => {
+            this.ui_port.set_selected_pid(key.pid as i32);
+            this.ui_port.set_selected_source(key.schema_id.into());
+            if let Some(name) = this.table.name_for(key) {
+                this.ui_port.set_selected_name(name);
+            }
+        }
+        None => this.ui_port.set_selected_pid(-1),
     }
+
+    this.push_history();
+    this.push_batch();
 }
 
 #[handler]
 fn terminate_selected_process<P: UiProcessesPort>(
     this: &mut ProcessActor<P>,
-    _: TerminateSelected,
+    msg: TerminateSelected,
+    ctx: &Context<ProcessActor<P>>,
+) {
+    let timeout = Duration::from_millis(this.terminate_timeout_ms.get());
+    let targets = this
+        .table
+        .action_targets(&this.table.selected_keys(), msg.tree);
+
+    for target in targets {
+        if this.refuse_unsupported(&target, "Terminate", ctx) {
+            continue;
+        }
+        ctx.spawn_bg(async move {
+            let (action, error) = match control::terminate(&target, timeout).await {
+                Ok(outcome) => (outcome.action(), None),
+                Err(e) => ("Terminate", Some(e.to_string())),
+            };
+            ProcessActionResult {
+                pid: target.pid,
+                name: target.name,
+                action,
+                error,
+            }
+        });
+    }
+
+    this.table.clear_selection();
+    this.ui_port.set_selected_pid(-1);
+    this.ui_port.set_selected_source(SharedString::default());
+    this.push_batch();
+}
+
+#[handler]
+fn run_process_command<P: UiProcessesPort>(
+    this: &mut ProcessActor<P>,
+    msg: RunCommand,
+    ctx: &Context<ProcessActor<P>>,
+) {
+    let command = msg.0;
+    let targets = this
+        .table
+        .action_targets(&this.table.selected_keys(), false);
+
+    for target in targets {
+        if this.refuse_unsupported(&target, command.action(), ctx) {
+            continue;
+        }
+        ctx.spawn_bg(async move {
+            let error = control::execute(&target, command)
+                .await
+                .err()
+                .map(|e| e.to_string());
+            ProcessActionResult {
+                pid: target.pid,
+                name: target.name,
+                action: command.action(),
+                error,
+            }
+        });
+    }
+}
+
+#[handler]
+fn set_process_affinity<P: UiProcessesPort>(
+    this: &mut ProcessActor<P>,
+    msg: SetAffinity,
+    ctx: &Context<ProcessActor<P>>,
+) {
+    match control::parse_cpu_list(&msg.0) {
+        Ok(mask) => ctx
+            .addr()
+            .send(RunCommand(ProcessCommand::SetAffinity(mask))),
+        Err(e) => this
+            .ui_port
+            .set_action_status(format!("Set affinity: {e}").into()),
+    }
+}
+
+#[handler]
+fn report_process_action<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: ProcessActionResult) {
+    let status = match &msg.error {
+        None => {
+            info!(pid = msg.pid, action = msg.action, "process action done");
+            format!("{} {} ({}): done", msg.action, msg.name, msg.pid)
+        }
+        Some(e) => {
+            warn!(
+                pid = msg.pid,
+                action = msg.action,
+                "process action failed: {e}"
+            );
+            format!("{} {} ({}): {e}", msg.action, msg.name, msg.pid)
+        }
+    };
+    this.ui_port.set_action_status(status.into());
+}
+
+#[handler]
+fn open_process_details<P: UiProcessesPort>(
+    this: &mut ProcessActor<P>,
+    msg: OpenDetails,
+    ctx: &Context<ProcessActor<P>>,
+) {
+    let Some(target) = this
+        .table
+        .key_for(&msg.source, msg.pid)
+        .and_then(|key| this.table.action_targets(&[key], false).pop())
+    else {
+        return;
+    };
+
+    let key = format!("{DETAILS_WINDOW_KEY}-{}-{}", target.schema_id, target.pid);
+    if this.details_windows.contains_key(&key) {
+        if let Some(window) = this.registry.get_window(&key) {
+            window.focus();
+        }
+        this.fetch_details(&key, ctx);
+        return;
+    }
+
+    this.details_windows.insert(
+        key.clone(),
+        DetailsWindow {
+            target,
+            in_flight: false,
+        },
+    );
+    EventBus::publish(OpenWindow {
+        key,
+        template: DETAILS_WINDOW_KEY.to_string(),
+        data: Arc::new(()),
+    });
+}
+
+#[handler]
+fn toggle_process_pin<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: TogglePin) {
+    let Some(key) = this.table.key_for(&msg.source, msg.pid) else {
+        return;
+    };
+    if let Err(e) = this.table.toggle_pin(key) {
+        warn!("toggle_pin failed: {e}");
+    }
+}
+
+#[handler]
+fn on_details_window_opened<P: UiProcessesPort>(
+    this: &mut ProcessActor<P>,
+    msg: OpenedWindow,
     ctx: &Context<ProcessActor<P>>,
 ) {
-    let pid = this.ui_port.get_selected_pid();
-    let Some(pid) = (pid != -1).then_some(pid as u32) else {
+    this.fetch_details(&msg.key, ctx);
+}
+
+#[handler]
+fn on_details_window_closed<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: WindowClosed) {
+    this.details_windows.remove(&msg.key);
+}
+
+#[handler]
+fn show_process_details<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: DetailsFetched) {
+    let Some(window) = this.details_windows.get_mut(&msg.key) else {
         return;
     };
+    window.in_flight = false;
+
+    let mut details = msg.details;
+    // The host may deny reading another user's command line; the agent snapshot still has it.
+    if details.cmdline.is_empty()
+        && let Some(cmdline) = this.table.cmdline_for(ProcessKey {
+            schema_id: window.target.schema_id,
+            pid: window.target.pid,
+        })
+    {
+        details.cmdline = cmdline;
+    }
 
+    let Some(port) = this
+        .registry
+        .get_window(&msg.key)
+        .and_then(|window| window.get_port::<dyn UiProcessDetailsPort>())
+    else {
+        return;
+    };
+    port.set_process_details(details);
+}
+
+#[handler]
+fn export_processes<P: UiProcessesPort>(
+    this: &mut ProcessActor<P>,
+    msg: Export,
+    ctx: &Context<ProcessActor<P>>,
+) {
+    let path = PathBuf::from(msg.0.trim());
+    let format = match ExportFormat::from_path(&path) {
+        Ok(format) => format,
+        Err(e) => {
+            return this
+                .ui_port
+                .set_action_status(format!("Export: {e}").into())
+        }
+    };
+
+    let export = this.table.export();
     ctx.spawn_bg(async move {
-        let mut system = System::new();
-        system.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]), false);
-        if let Some(process) = system.process(Pid::from_u32(pid)) {
-            process.kill();
+        let rows = export.len();
+        let display = path.display().to_string();
+        let error = match tokio::task::spawn_blocking(move || export.write_to(&path, format)).await
+        {
+            Ok(written) => written.err().map(|e| e.to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+        ExportFinished {
+            path: display,
+            rows,
+            error,
         }
-        NoOp
     });
+}
 
-    this.table.clear_selection();
+#[handler]
+fn report_export<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: ExportFinished) {
+    let status = match msg.error {
+        None => format!("Exported {} processes to {}", msg.rows, msg.path),
+        Some(e) => {
+            warn!(path = %msg.path, "process export failed: {e}");
+            format!("Export: {e}")
+        }
+    };
+    this.ui_port.set_action_status(status.into());
+}
+
+// Appends one line per snapshot to an NDJSON file until toggled again.
+#[handler]
+fn toggle_recording<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: ToggleRecording) {
+    if this.recorder.take().is_some() {
+        this.ui_port.set_is_recording(false);
+        this.ui_port.set_action_status("Recording stopped".into());
+        return;
+    }
+
+    let path = PathBuf::from(msg.0.trim());
+    let started = match ExportFormat::from_path(&path) {
+        Ok(ExportFormat::Ndjson) => NdjsonRecorder::start(&path),
+        Ok(_) => Err(anyhow::anyhow!("recording needs an .ndjson or .jsonl file")),
+        Err(e) => Err(e),
+    };
+
+    match started {
+        Ok(recorder) => {
+            this.recorder = Some(recorder);
+            this.ui_port.set_is_recording(true);
+            this.ui_port
+                .set_action_status(format!("Recording to {}", path.display()).into());
+        }
+        Err(e) => this
+            .ui_port
+            .set_action_status(format!("Recording: {e}").into()),
+    }
+}
+
+#[handler]
+fn on_alert_fired<P: UiProcessesPort>(
+    this: &mut ProcessActor<P>,
+    msg: AlertFired,
+    ctx: &Context<ProcessActor<P>>,
+) {
+    let alert = msg.alert;
+    warn!(rule = %alert.rule, pid = alert.pid, "{}: {}", alert.process_name, alert.message);
+
+    match msg.action {
+        AlertAction::Log => {}
+        AlertAction::Run { command } => {
+            let alert = alert.clone();
+            ctx.spawn_bg(async move {
+                let error = alerts::run_command(&command, &alert)
+                    .await
+                    .err()
+                    .map(|e| e.to_string());
+                ProcessActionResult {
+                    pid: alert.pid,
+                    name: alert.process_name,
+                    action: "Alert command",
+                    error,
+                }
+            });
+        }
+        AlertAction::Terminate => {
+            let timeout = Duration::from_millis(this.terminate_timeout_ms.get());
+            let target = ProcessTarget {
+                pid: alert.pid,
+                name: alert.process_name.clone(),
+                schema_id: alert.schema_id,
+            };
+            if this.refuse_unsupported(&target, "Terminate", ctx) {
+                return;
+            }
+            ctx.spawn_bg(async move {
+                let (action, error) = match control::terminate(&target, timeout).await {
+                    Ok(outcome) => (outcome.action(), None),
+                    Err(e) => ("Terminate", Some(e.to_string())),
+                };
+                ProcessActionResult {
+                    pid: target.pid,
+                    name: target.name,
+                    action,
+                    error,
+                }
+            });
+        }
+    }
+
+    this.alerts.push_front(alert);
+    this.alerts.truncate(MAX_ALERTS);
+    this.ui_port
+        .set_alerts(this.alerts.iter().cloned().collect());
+}
+
+#[handler]
+fn clear_alerts<P: UiProcessesPort>(this: &mut ProcessActor<P>, _: ClearAlerts) {
+    this.alerts.clear();
+    this.ui_port.set_alerts(Vec::new());
+}
+
+#[handler]
+fn process_started<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: ProcessStarted) {
+    this.record_activity(ProcessActivity::Started(msg));
+}
+
+#[handler]
+fn process_exited<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: ProcessExited) {
+    this.record_activity(ProcessActivity::Exited(msg));
+}
+
+#[handler]
+fn filter_activity<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: FilterActivity) {
+    this.activity_filter = msg.0;
+    this.push_activity();
+}
+
+#[handler]
+fn filter_processes<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: Filter) {
+    this.table.set_filter(&msg.0, &this.active_context_key);
+    this.table.refresh(&mut this.metadata).ok();
+    this.push_batch();
+}
+
+// Limits the list to the active tab's context; the choice is persisted.
+#[handler]
+fn toggle_context_scope<P: UiProcessesPort>(this: &mut ProcessActor<P>, _: ToggleScope) {
+    let scoped = !this.scope_to_context.get();
+    if let Err(e) = this.scope_to_context.set(scoped) {
+        warn!("failed to persist scope: {e}");
+    }
+
+    this.table.set_scoped(scoped, &this.active_context_key);
+    this.ui_port.set_is_scoped(scoped);
+    this.table.refresh(&mut this.metadata).ok();
+    this.push_batch();
 }
 
 #[handler]
@@ -265,7 +851,39 @@ fn resize_process_column<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: Re
 
 #[handler]
 fn toggle_grouping<P: UiProcessesPort>(this: &mut ProcessActor<P>, _msg: GroupClicked) {
-    info!("clicked");
-    this.is_grouped = !this.is_grouped;
-    this.ui_port.set_is_grouped(this.is_grouped);
+    let mode = this.table.tree_mode().next();
+    info!(mode = mode.as_str(), "tree mode changed");
+
+    this.table.set_tree_mode(mode);
+    this.ui_port.set_is_grouped(mode != ProcessTreeMode::Flat);
+    this.ui_port.set_tree_mode(mode.as_str().into());
+    this.table.refresh(&mut this.metadata).ok();
+    this.push_batch();
+}
+
+#[handler]
+fn toggle_process_column<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: ToggleColumn) {
+    if let Err(e) = this.table.toggle_column(&msg.0) {
+        warn!("toggle_column failed: {e}");
+    }
+}
+
+#[handler]
+fn move_process_column<P: UiProcessesPort>(this: &mut ProcessActor<P>, msg: MoveColumn) {
+    if let Err(e) = this.table.move_column(&msg.id, msg.delta) {
+        warn!("move_column failed: {e}");
+    }
+}
+
+#[handler]
+fn apply_column_layout<P: UiProcessesPort>(this: &mut ProcessActor<P>, _: ColumnLayoutChanged) {
+    if !this.has_snapshot_data {
+        return;
+    }
+
+    this.table.build_header();
+    this.table.refresh(&mut this.metadata).ok();
+    this.push_columns();
+    this.push_history();
+    this.push_batch();
 }
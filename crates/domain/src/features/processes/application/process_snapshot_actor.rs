@@ -1,33 +1,93 @@
-use crate::processes_impl::application::actor::ProcessActor;
+use crate::processes_impl::application::actor::{AlertFired, ProcessActor};
+use crate::processes_impl::domain::activity;
+use crate::processes_impl::domain::alerts::AlertEngine;
+use crate::processes_impl::domain::history::MetricHistory;
 use crate::processes_impl::domain::snapshot::BridgeSnapshot;
+use crate::processes_impl::domain::source::source_label;
 use crate::processes_impl::scanner::base::ScanResult;
+use crate::processes_impl::scanner::consts::{ID_SOURCE, LBL_SOURCE};
 use crate::processes_impl::scanner::ctx::StatefulContext;
+#[cfg(target_os = "linux")]
+use crate::processes_impl::scanner::procfs::{self, ProcfsMachine, ProcfsProcess};
 use crate::processes_impl::scanner::visitors::linux::WslScanResult;
+#[cfg(target_os = "linux")]
+use crate::processes_impl::scanner::visitors::procfs::ProcfsScanResult;
 use crate::processes_impl::scanner::visitors::windows::WindowsScanResult;
-use app_contracts::features::agents::RemoteScanResult;
+use crate::processes_impl::settings::AlertRule;
+use app_contracts::features::agents::{RemoteScanResult, ReportConsumed};
+#[cfg(target_os = "linux")]
+use app_contracts::features::agents::{ReplayStatus, ScanTick};
 use app_contracts::features::environments::WslAgentRuntimeEvent;
 use app_contracts::features::processes::{
     FieldDefDto, ProcessFieldDto, ProcessNodeDto, UiProcessesPort,
 };
 use app_core::actor::addr::Addr;
+use app_core::actor::event_bus::EventBus;
+#[cfg(target_os = "linux")]
+use app_core::actor::Context;
 use app_core::actor::ManagedActor;
 use app_core::{messages, ratelimit};
 use framework::feature::Events;
 use framework::navigation::RouteActivated;
+use framework::settings::reactive::ReactiveSettingSubscription;
+use framework::settings::ReactiveSetting;
 use macros::{actor_manifest, handler};
 use slint::SharedString;
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
-use tracing::Span;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{debug, Span};
+use uniproc_protocol::LinuxEnvironmentKind;
+
+#[cfg(target_os = "linux")]
+pub const LOCAL_SCHEMA_ID: &str = "local";
+
+// Per-pid scanner state survives this many scans without the pid before it is dropped.
+const EVICT_AFTER_SCANS: u64 = 3;
+
+const CACHE_LOG_INTERVAL: Duration = Duration::from_secs(600);
+
+// Without an agent report for this long, processes are read from /proc directly.
+#[cfg(target_os = "linux")]
+const AGENT_STALE_AFTER: Duration = Duration::from_secs(5);
+
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+pub struct LocalScanState {
+    last_agent_report: Option<Instant>,
+    in_flight: bool,
+    // A session replay stands in for the agent until it reaches its end, even while it is paused.
+    replaying: bool,
+}
+
+#[cfg(target_os = "linux")]
+impl LocalScanState {
+    fn agent_is_live(&self) -> bool {
+        self.replaying
+            || self
+                .last_agent_report
+                .is_some_and(|at| at.elapsed() < AGENT_STALE_AFTER)
+    }
+}
 
 pub struct ProcessSnapshotActor<P: UiProcessesPort> {
     pub snapshots: HashMap<&'static str, BridgeSnapshot>,
+    // The distro each agent schema reported running in.
+    pub distros: HashMap<&'static str, SharedString>,
     pub contexts: HashMap<&'static str, Arc<StatefulContext>>,
+    // When each schema last logged its scanner cache size.
+    pub cache_logged: HashMap<&'static str, Instant>,
     pub target: Addr<ProcessActor<P>>,
 
     pub is_active: bool,
     pub scratch_processes: Arc<Mutex<Vec<ProcessNodeDto>>>,
     pub scratch_seen: HashSet<SharedString>,
+    pub history: Arc<Mutex<MetricHistory>>,
+    pub _history_sub: ReactiveSettingSubscription,
+    pub alert_rules: ReactiveSetting<Vec<AlertRule>>,
+    pub alerts: AlertEngine,
+    #[cfg(target_os = "linux")]
+    pub local_scan: LocalScanState,
 }
 
 messages! {
@@ -35,6 +95,14 @@ messages! {
         column_defs: Vec<FieldDefDto>,
         processes: Arc<Mutex<Vec<ProcessNodeDto>>>,
         total_count: usize,
+        distros: HashMap<&'static str, SharedString>,
+    }
+}
+
+#[cfg(target_os = "linux")]
+messages! {
+    LocalScanned {
+        result: Result<(Vec<ProcfsProcess>, ProcfsMachine), String>,
     }
 }
 
@@ -45,12 +113,22 @@ impl<P: UiProcessesPort> ManagedActor for ProcessSnapshotActor<P> {
         RemoteScanResult,
         #[cfg(target_os = "windows")]
         app_contracts::features::agents::WindowsReportMessage,
+        #[cfg(target_os = "linux")]
+        ScanTick,
+        #[cfg(target_os = "linux")]
+        ReplayStatus,
     );
     type Handlers = handlers!(
         ActiveStatus(bool),
         @RemoteScanResult,
         #[cfg(target_os = "windows")]
         @app_contracts::features::agents::WindowsReportMessage,
+        #[cfg(target_os = "linux")]
+        @ScanTick,
+        #[cfg(target_os = "linux")]
+        @LocalScanned,
+        #[cfg(target_os = "linux")]
+        @ReplayStatus,
     );
 }
 
@@ -62,6 +140,76 @@ impl<P: UiProcessesPort> ProcessSnapshotActor<P> {
             .clone()
     }
 
+    // Alert rules keep snapshots flowing while the page is hidden; only the UI push is skipped.
+    fn should_scan(&self) -> bool {
+        self.is_active || !self.alert_rules.get_arc().is_empty()
+    }
+
+    // A skipped report leaves the kept snapshot behind; the next one must not be diffed against
+    // it, or everything that started or exited while the page was hidden is logged as new.
+    fn skip_report(&mut self, schema_id: &'static str) {
+        self.snapshots.remove(schema_id);
+    }
+
+    fn accept_snapshot(&mut self, schema_id: &'static str, snapshot: BridgeSnapshot) {
+        self.history.lock().unwrap().record(schema_id, &snapshot);
+
+        let stats = self
+            .context_for(schema_id)
+            .sweep(snapshot.processes.iter().map(|p| p.pid), EVICT_AFTER_SCANS);
+        if stats.evicted_pids > 0 {
+            debug!(
+                schema_id,
+                evicted = stats.evicted_pids,
+                "evicted scanner state"
+            );
+        }
+
+        let now = Instant::now();
+        let log_due = self
+            .cache_logged
+            .get(schema_id)
+            .is_none_or(|at| now.duration_since(*at) >= CACHE_LOG_INTERVAL);
+        if log_due {
+            self.cache_logged.insert(schema_id, now);
+            debug!(
+                schema_id,
+                pids = stats.pids,
+                fields = stats.fields,
+                counters = stats.counters,
+                display_names = stats.display_names,
+                "scanner cache size"
+            );
+        }
+
+        let rules = self.alert_rules.get_arc();
+        if !rules.is_empty() {
+            for fired in self.alerts.evaluate(&rules, schema_id, &snapshot, now) {
+                EventBus::publish(fired.alert.clone());
+                self.target.send(AlertFired {
+                    alert: fired.alert,
+                    action: fired.action,
+                });
+            }
+        }
+
+        // The first snapshot of a source has nothing to compare against and is not reported.
+        if let Some(previous) = self.snapshots.get(schema_id) {
+            let diff = activity::diff(schema_id, previous, &snapshot, SystemTime::now());
+            for exited in diff.exited {
+                EventBus::publish(exited);
+            }
+            for started in diff.started {
+                EventBus::publish(started);
+            }
+        }
+
+        self.snapshots.insert(schema_id, snapshot);
+        if self.is_active {
+            self.rebuild_and_send();
+        }
+    }
+
     fn rebuild_and_send(&mut self) {
         if self.snapshots.is_empty() {
             return;
@@ -100,6 +248,7 @@ impl<P: UiProcessesPort> ProcessSnapshotActor<P> {
             column_defs,
             processes: self.scratch_processes.clone(),
             total_count,
+            distros: self.distros.clone(),
         });
     }
 }
@@ -114,19 +263,34 @@ fn process_remote_scan<P: UiProcessesPort>(
     this: &mut ProcessSnapshotActor<P>,
     msg: RemoteScanResult,
 ) {
-    if !this.is_active {
-        return;
+    #[cfg(target_os = "linux")]
+    {
+        this.local_scan.last_agent_report = Some(Instant::now());
     }
 
-    let ctx = this.context_for(msg.schema_id);
-    let result = WslScanResult {
-        processes: msg.processes,
-        machine: msg.machine,
-        ctx,
-    };
-    let snapshot = build_snapshot(&result);
-    this.snapshots.insert(msg.schema_id, snapshot);
-    this.rebuild_and_send();
+    let distro = msg.environments.iter().find_map(|env| match &env.kind {
+        LinuxEnvironmentKind::CurrentDistro { name } => Some(SharedString::from(name.as_str())),
+        _ => None,
+    });
+    if let Some(distro) = distro {
+        this.distros.insert(msg.schema_id, distro);
+    }
+
+    let schema_id = msg.schema_id;
+    if this.should_scan() {
+        let ctx = this.context_for(schema_id);
+        ctx.set_recorded_at(msg.recorded_at);
+        let result = WslScanResult {
+            processes: msg.processes,
+            machine: msg.machine,
+            ctx,
+        };
+        let snapshot = build_snapshot(schema_id, &result);
+        this.accept_snapshot(schema_id, snapshot);
+    } else {
+        this.skip_report(schema_id);
+    }
+    EventBus::publish(ReportConsumed { schema_id });
 }
 
 #[cfg(target_os = "windows")]
@@ -135,19 +299,103 @@ fn process_windows_report<P: UiProcessesPort>(
     this: &mut ProcessSnapshotActor<P>,
     msg: app_contracts::features::agents::WindowsReportMessage,
 ) {
-    if !this.is_active {
+    if this.should_scan() {
+        let ctx = this.context_for("windows");
+        ctx.set_recorded_at(msg.1);
+        let result = WindowsScanResult { report: msg.0, ctx };
+        let snapshot = build_snapshot("windows", &result);
+        this.accept_snapshot("windows", snapshot);
+    } else {
+        this.skip_report("windows");
+    }
+    EventBus::publish(ReportConsumed {
+        schema_id: "windows",
+    });
+}
+
+#[cfg(target_os = "linux")]
+#[handler]
+fn track_replay<P: UiProcessesPort>(this: &mut ProcessSnapshotActor<P>, msg: ReplayStatus) {
+    this.local_scan.replaying = msg.position < msg.length;
+}
+
+#[cfg(target_os = "linux")]
+#[handler]
+fn scan_local_procfs<P: UiProcessesPort>(
+    this: &mut ProcessSnapshotActor<P>,
+    _: ScanTick,
+    ctx: &Context<ProcessSnapshotActor<P>>,
+) {
+    if !this.should_scan() {
+        this.skip_report(LOCAL_SCHEMA_ID);
         return;
     }
+    if this.local_scan.in_flight {
+        return;
+    }
+
+    if this.local_scan.agent_is_live() {
+        if this.snapshots.remove(LOCAL_SCHEMA_ID).is_some() && this.is_active {
+            this.rebuild_and_send();
+        }
+        return;
+    }
+
+    this.local_scan.in_flight = true;
+    ctx.spawn_bg(async {
+        let read = tokio::task::spawn_blocking(|| -> anyhow::Result<_> {
+            let root = std::path::Path::new("/proc");
+            Ok((procfs::read_processes(root)?, procfs::read_machine(root)?))
+        })
+        .await;
 
-    let ctx = this.context_for("windows");
-    let result = WindowsScanResult { report: msg.0, ctx };
-    let snapshot = build_snapshot(&result);
-    this.snapshots.insert("windows", snapshot);
-    this.rebuild_and_send();
+        let result = match read {
+            Ok(read) => read.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        LocalScanned { result }
+    });
+}
+
+#[cfg(target_os = "linux")]
+#[handler]
+fn process_local_scan<P: UiProcessesPort>(this: &mut ProcessSnapshotActor<P>, msg: LocalScanned) {
+    this.local_scan.in_flight = false;
+
+    if !this.should_scan() {
+        this.skip_report(LOCAL_SCHEMA_ID);
+        return;
+    }
+    // An agent may have connected while /proc was being read.
+    if this.local_scan.agent_is_live() {
+        return;
+    }
+
+    let (processes, machine) = match msg.result {
+        Ok(scan) => scan,
+        Err(e) => {
+            tracing::warn!("local /proc scan failed: {e}");
+            return;
+        }
+    };
+
+    let ctx = this.context_for(LOCAL_SCHEMA_ID);
+    let result = ProcfsScanResult {
+        processes,
+        machine,
+        ctx,
+    };
+    let snapshot = build_snapshot(LOCAL_SCHEMA_ID, &result);
+    this.accept_snapshot(LOCAL_SCHEMA_ID, snapshot);
 }
 
-pub fn build_snapshot(result: &dyn ScanResult) -> BridgeSnapshot {
-    let mut column_defs: Vec<FieldDefDto> = vec![];
+pub fn build_snapshot(schema_id: &'static str, result: &dyn ScanResult) -> BridgeSnapshot {
+    let source = source_label(schema_id);
+    let mut column_defs: Vec<FieldDefDto> = vec![FieldDefDto {
+        id: ID_SOURCE.clone(),
+        label: LBL_SOURCE.clone(),
+        ..Default::default()
+    }];
 
     result.visit_stats(&mut |mut field| {
         column_defs.push(FieldDefDto {
@@ -176,12 +424,20 @@ pub fn build_snapshot(result: &dyn ScanResult) -> BridgeSnapshot {
                 threshold: field.threshold,
             });
         });
+        fields.push(ProcessFieldDto {
+            id: ID_SOURCE.clone(),
+            text: source.clone(),
+            numeric: -1.0,
+            threshold: 0.0,
+        });
 
         processes.push(ProcessNodeDto {
             pid: proc.pid(),
+            schema_id,
             name: proc.name(ctx),
             parent_pid: proc.parent_pid(),
             exe_path: proc.exe_path(ctx),
+            cmdline: proc.cmdline(ctx),
             fields: fields.clone(),
             #[cfg(windows)]
             package_name: proc.package_name(ctx),
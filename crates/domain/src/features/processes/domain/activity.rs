@@ -0,0 +1,154 @@
+use crate::processes_impl::domain::snapshot::BridgeSnapshot;
+use app_contracts::features::processes::{ProcessExited, ProcessNodeDto, ProcessStarted};
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+#[derive(Default)]
+pub struct SnapshotDiff {
+    pub started: Vec<ProcessStarted>,
+    pub exited: Vec<ProcessExited>,
+}
+
+// Compares two consecutive snapshots of the same source.
+pub fn diff(
+    schema_id: &'static str,
+    previous: &BridgeSnapshot,
+    current: &BridgeSnapshot,
+    at: SystemTime,
+) -> SnapshotDiff {
+    let before: HashMap<u32, &ProcessNodeDto> =
+        previous.processes.iter().map(|p| (p.pid, p)).collect();
+    let after: HashMap<u32, &ProcessNodeDto> =
+        current.processes.iter().map(|p| (p.pid, p)).collect();
+    let mut diff = SnapshotDiff::default();
+
+    for proc in &previous.processes {
+        if !after.contains_key(&proc.pid) {
+            diff.exited.push(ProcessExited {
+                pid: proc.pid,
+                schema_id,
+                name: proc.name.clone(),
+                exe_path: proc.exe_path.clone(),
+                at,
+            });
+        }
+    }
+
+    for proc in &current.processes {
+        let previous_exe = match before.get(&proc.pid) {
+            None => None,
+            // Unreadable exe paths come back empty and are not treated as a change.
+            Some(old)
+                if !old.exe_path.is_empty()
+                    && !proc.exe_path.is_empty()
+                    && old.exe_path != proc.exe_path =>
+            {
+                Some(old.exe_path.clone())
+            }
+            Some(_) => continue,
+        };
+        diff.started.push(ProcessStarted {
+            pid: proc.pid,
+            schema_id,
+            name: proc.name.clone(),
+            exe_path: proc.exe_path.clone(),
+            previous_exe,
+            at,
+        });
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(processes: &[(u32, &str)]) -> BridgeSnapshot {
+        BridgeSnapshot {
+            column_defs: Vec::new(),
+            processes: processes
+                .iter()
+                .map(|&(pid, exe)| ProcessNodeDto {
+                    pid,
+                    name: exe.rsplit('/').next().unwrap_or_default().into(),
+                    exe_path: exe.into(),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn run(previous: &[(u32, &str)], current: &[(u32, &str)]) -> SnapshotDiff {
+        diff(
+            "local",
+            &snapshot(previous),
+            &snapshot(current),
+            SystemTime::now(),
+        )
+    }
+
+    fn started(diff: &SnapshotDiff) -> Vec<(u32, Option<&str>)> {
+        diff.started
+            .iter()
+            .map(|e| (e.pid, e.previous_exe.as_deref()))
+            .collect()
+    }
+
+    fn exited(diff: &SnapshotDiff) -> Vec<u32> {
+        diff.exited.iter().map(|e| e.pid).collect()
+    }
+
+    #[test]
+    fn unchanged_snapshot_reports_nothing() {
+        let procs = [(1, "/sbin/init"), (20, "/bin/bash")];
+        let diff = run(&procs, &procs);
+
+        assert!(diff.started.is_empty());
+        assert!(diff.exited.is_empty());
+    }
+
+    #[test]
+    fn new_pid_is_started_without_previous_exe() {
+        let diff = run(&[(1, "/sbin/init")], &[(1, "/sbin/init"), (50, "/bin/ls")]);
+
+        assert_eq!(started(&diff), [(50, None)]);
+        assert_eq!(diff.started[0].name, "ls");
+        assert!(diff.exited.is_empty());
+    }
+
+    #[test]
+    fn missing_pid_is_exited() {
+        let diff = run(
+            &[(1, "/sbin/init"), (20, "/bin/bash")],
+            &[(1, "/sbin/init")],
+        );
+
+        assert_eq!(exited(&diff), [20]);
+        assert_eq!(diff.exited[0].exe_path, "/bin/bash");
+        assert!(diff.started.is_empty());
+    }
+
+    #[test]
+    fn replaced_image_is_started_with_previous_exe() {
+        let diff = run(&[(30, "/bin/sh")], &[(30, "/usr/bin/make")]);
+
+        assert_eq!(started(&diff), [(30, Some("/bin/sh"))]);
+        assert!(diff.exited.is_empty());
+    }
+
+    #[test]
+    fn unreadable_exe_path_is_not_a_replacement() {
+        assert!(run(&[(40, "")], &[(40, "/bin/cat")]).started.is_empty());
+        assert!(run(&[(40, "/bin/cat")], &[(40, "")]).started.is_empty());
+    }
+
+    #[test]
+    fn events_carry_schema_and_timestamp() {
+        let at = SystemTime::UNIX_EPOCH;
+        let diff = diff("wsl", &snapshot(&[(1, "/a")]), &snapshot(&[(2, "/b")]), at);
+
+        assert_eq!((diff.started[0].schema_id, diff.started[0].at), ("wsl", at));
+        assert_eq!((diff.exited[0].schema_id, diff.exited[0].at), ("wsl", at));
+    }
+}
@@ -0,0 +1,374 @@
+use crate::processes_impl::domain::snapshot::BridgeSnapshot;
+use crate::processes_impl::settings::{AlertAction, AlertCondition, AlertRule};
+use app_contracts::features::processes::AlertRaised;
+use slint::SharedString;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
+
+pub struct FiredAlert {
+    pub alert: AlertRaised,
+    pub action: AlertAction,
+}
+
+#[derive(Default)]
+struct RuleState {
+    breached_since: Option<Instant>,
+    firing: bool,
+    last_raised: Option<Instant>,
+}
+
+impl RuleState {
+    fn cooled_down(&self, cooldown: Duration, now: Instant) -> bool {
+        self.last_raised
+            .is_none_or(|at| now.duration_since(at) >= cooldown)
+    }
+
+    // Returns true when the alert should be raised on this sample.
+    fn observe(
+        &mut self,
+        breached: bool,
+        cleared: bool,
+        hold: Duration,
+        cooldown: Duration,
+        now: Instant,
+    ) -> bool {
+        if self.firing {
+            if cleared {
+                self.firing = false;
+                self.breached_since = None;
+            }
+            return false;
+        }
+
+        if !breached {
+            self.breached_since = None;
+            return false;
+        }
+
+        let since = *self.breached_since.get_or_insert(now);
+        if now.duration_since(since) < hold || !self.cooled_down(cooldown, now) {
+            return false;
+        }
+
+        self.firing = true;
+        self.last_raised = Some(now);
+        true
+    }
+}
+
+// Keyed by rule name, schema and pid; exit rules track their cooldown without a pid.
+type StateKey = (String, &'static str, Option<u32>);
+
+#[derive(Default)]
+pub struct AlertEngine {
+    states: HashMap<StateKey, RuleState>,
+    alive: HashMap<&'static str, HashMap<u32, SharedString>>,
+}
+
+impl AlertEngine {
+    pub fn evaluate(
+        &mut self,
+        rules: &[AlertRule],
+        schema_id: &'static str,
+        snapshot: &BridgeSnapshot,
+        now: Instant,
+    ) -> Vec<FiredAlert> {
+        let current: HashMap<u32, SharedString> = snapshot
+            .processes
+            .iter()
+            .map(|p| (p.pid, p.name.clone()))
+            .collect();
+        let previous = self.alive.remove(schema_id).unwrap_or_default();
+        let mut fired = Vec::new();
+
+        for rule in rules {
+            let hold = Duration::from_secs(rule.for_secs);
+            let cooldown = Duration::from_secs(rule.cooldown_secs);
+
+            match &rule.condition {
+                AlertCondition::Above {
+                    metric,
+                    value,
+                    clear_below,
+                } => {
+                    for process in snapshot.processes.iter().filter(|p| rule.matches(&p.name)) {
+                        let Some(sample) = process
+                            .fields
+                            .iter()
+                            .find(|f| f.id == metric.as_str())
+                            .map(|f| f.numeric)
+                            .filter(|v| *v >= 0.0)
+                        else {
+                            continue;
+                        };
+
+                        let state = self
+                            .states
+                            .entry((rule.name.clone(), schema_id, Some(process.pid)))
+                            .or_default();
+                        let cleared = sample < clear_below.unwrap_or(*value);
+                        if state.observe(sample > *value, cleared, hold, cooldown, now) {
+                            fired.push(FiredAlert {
+                                alert: alert(
+                                    rule,
+                                    schema_id,
+                                    process.pid,
+                                    &process.name,
+                                    format!("{metric} {sample:.1} above {value}"),
+                                ),
+                                action: rule.action.clone(),
+                            });
+                        }
+                    }
+                }
+                AlertCondition::Exited => {
+                    let state = self
+                        .states
+                        .entry((rule.name.clone(), schema_id, None))
+                        .or_default();
+
+                    for (pid, name) in &previous {
+                        if current.contains_key(pid)
+                            || !rule.matches(name)
+                            || !state.cooled_down(cooldown, now)
+                        {
+                            continue;
+                        }
+                        state.last_raised = Some(now);
+
+                        // Nothing is left to terminate once the process is gone.
+                        let action = match &rule.action {
+                            AlertAction::Terminate => AlertAction::Log,
+                            other => other.clone(),
+                        };
+                        fired.push(FiredAlert {
+                            alert: alert(rule, schema_id, *pid, name, "exited".to_string()),
+                            action,
+                        });
+                    }
+                }
+            }
+        }
+
+        self.states.retain(|(name, schema, pid), _| {
+            rules.iter().any(|r| &r.name == name)
+                && (*schema != schema_id || pid.is_none_or(|pid| current.contains_key(&pid)))
+        });
+        self.alive.insert(schema_id, current);
+
+        fired
+    }
+}
+
+fn alert(
+    rule: &AlertRule,
+    schema_id: &'static str,
+    pid: u32,
+    name: &SharedString,
+    message: String,
+) -> AlertRaised {
+    AlertRaised {
+        rule: rule.name.as_str().into(),
+        pid,
+        schema_id,
+        process_name: name.clone(),
+        message: message.into(),
+        raised_at: SystemTime::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use app_contracts::features::processes::{ProcessFieldDto, ProcessNodeDto};
+
+    fn process(pid: u32, name: &str, cpu: Option<f32>) -> ProcessNodeDto {
+        ProcessNodeDto {
+            pid,
+            name: name.into(),
+            fields: cpu
+                .map(|numeric| ProcessFieldDto {
+                    id: "cpu".into(),
+                    numeric,
+                    ..Default::default()
+                })
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn snapshot(processes: Vec<ProcessNodeDto>) -> BridgeSnapshot {
+        BridgeSnapshot {
+            column_defs: Vec::new(),
+            processes,
+        }
+    }
+
+    fn cpu(samples: &[(u32, f32)]) -> BridgeSnapshot {
+        snapshot(
+            samples
+                .iter()
+                .map(|&(pid, value)| process(pid, "vmmem", Some(value)))
+                .collect(),
+        )
+    }
+
+    fn hot(for_secs: u64, cooldown_secs: u64) -> AlertRule {
+        AlertRule {
+            name: "hot".into(),
+            process: Some("VMMEM".into()),
+            condition: AlertCondition::Above {
+                metric: "cpu".into(),
+                value: 80.0,
+                clear_below: Some(50.0),
+            },
+            for_secs,
+            cooldown_secs,
+            action: AlertAction::Log,
+        }
+    }
+
+    fn gone(cooldown_secs: u64) -> AlertRule {
+        AlertRule {
+            name: "gone".into(),
+            process: None,
+            condition: AlertCondition::Exited,
+            for_secs: 0,
+            cooldown_secs,
+            action: AlertAction::Terminate,
+        }
+    }
+
+    struct Run {
+        engine: AlertEngine,
+        rules: Vec<AlertRule>,
+        start: Instant,
+    }
+
+    impl Run {
+        fn new(rules: Vec<AlertRule>) -> Self {
+            Self {
+                engine: AlertEngine::default(),
+                rules,
+                start: Instant::now(),
+            }
+        }
+
+        fn fire(
+            &mut self,
+            secs: u64,
+            schema_id: &'static str,
+            s: BridgeSnapshot,
+        ) -> Vec<FiredAlert> {
+            let now = self.start + Duration::from_secs(secs);
+            self.engine.evaluate(&self.rules, schema_id, &s, now)
+        }
+
+        fn at(&mut self, secs: u64, s: BridgeSnapshot) -> Vec<(String, u32)> {
+            self.fire(secs, "wsl", s)
+                .into_iter()
+                .map(|f| (f.alert.rule.to_string(), f.alert.pid))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn fires_only_after_the_hold_period() {
+        let mut run = Run::new(vec![hot(10, 0)]);
+
+        assert!(run.at(0, cpu(&[(1, 90.0)])).is_empty());
+        assert!(run.at(5, cpu(&[(1, 95.0)])).is_empty());
+        assert_eq!(run.at(10, cpu(&[(1, 95.0)])), [("hot".to_string(), 1)]);
+    }
+
+    #[test]
+    fn dropping_below_the_threshold_restarts_the_hold() {
+        let mut run = Run::new(vec![hot(10, 0)]);
+
+        assert!(run.at(0, cpu(&[(1, 90.0)])).is_empty());
+        assert!(run.at(5, cpu(&[(1, 70.0)])).is_empty());
+        assert!(run.at(10, cpu(&[(1, 90.0)])).is_empty());
+        assert!(run.at(15, cpu(&[(1, 90.0)])).is_empty());
+        assert_eq!(run.at(20, cpu(&[(1, 90.0)])), [("hot".to_string(), 1)]);
+    }
+
+    #[test]
+    fn rearms_only_below_the_clear_level() {
+        let mut run = Run::new(vec![hot(0, 0)]);
+
+        assert_eq!(run.at(0, cpu(&[(1, 95.0)])).len(), 1);
+        // Dipping between the clear level and the threshold does not re-arm the rule.
+        assert!(run.at(1, cpu(&[(1, 60.0)])).is_empty());
+        assert!(run.at(2, cpu(&[(1, 95.0)])).is_empty());
+        assert!(run.at(3, cpu(&[(1, 10.0)])).is_empty());
+        assert_eq!(run.at(4, cpu(&[(1, 95.0)])).len(), 1);
+    }
+
+    #[test]
+    fn cooldown_suppresses_refiring_until_it_expires() {
+        let mut run = Run::new(vec![hot(0, 30)]);
+
+        assert_eq!(run.at(0, cpu(&[(1, 95.0)])).len(), 1);
+        assert!(run.at(1, cpu(&[(1, 10.0)])).is_empty());
+        assert!(run.at(2, cpu(&[(1, 95.0)])).is_empty());
+        assert!(run.at(29, cpu(&[(1, 95.0)])).is_empty());
+        assert_eq!(run.at(30, cpu(&[(1, 95.0)])), [("hot".to_string(), 1)]);
+    }
+
+    #[test]
+    fn missing_or_invalid_samples_neither_fire_nor_reset_the_hold() {
+        let mut run = Run::new(vec![hot(10, 0)]);
+
+        assert!(run.at(0, cpu(&[(1, 90.0)])).is_empty());
+        assert!(run.at(4, cpu(&[(1, f32::NAN)])).is_empty());
+        assert!(run.at(6, cpu(&[(1, -1.0)])).is_empty());
+        assert!(
+            run.at(8, snapshot(vec![process(1, "vmmem", None)]))
+                .is_empty()
+        );
+        assert_eq!(run.at(10, cpu(&[(1, 90.0)])), [("hot".to_string(), 1)]);
+    }
+
+    #[test]
+    fn process_filter_is_a_case_insensitive_substring() {
+        let mut run = Run::new(vec![hot(0, 0)]);
+        let s = snapshot(vec![
+            process(1, "VmMem.exe", Some(95.0)),
+            process(2, "chrome", Some(95.0)),
+        ]);
+
+        assert_eq!(run.at(0, s), [("hot".to_string(), 1)]);
+    }
+
+    #[test]
+    fn exit_downgrades_terminate_to_log() {
+        let mut run = Run::new(vec![gone(0)]);
+
+        assert!(run.fire(0, "wsl", cpu(&[(1, 10.0)])).is_empty());
+        let fired = run.fire(1, "wsl", cpu(&[]));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].alert.pid, 1);
+        assert!(matches!(fired[0].action, AlertAction::Log));
+    }
+
+    #[test]
+    fn exit_cooldown_is_shared_by_every_pid_of_the_rule() {
+        let mut run = Run::new(vec![gone(60)]);
+
+        assert!(run.at(0, cpu(&[(1, 0.0), (2, 0.0)])).is_empty());
+        assert_eq!(run.at(1, cpu(&[(2, 0.0)])), [("gone".to_string(), 1)]);
+        assert!(run.at(2, cpu(&[])).is_empty());
+        assert!(run.at(60, cpu(&[(3, 0.0)])).is_empty());
+        assert_eq!(run.at(61, cpu(&[])), [("gone".to_string(), 3)]);
+    }
+
+    #[test]
+    fn exits_are_tracked_per_source() {
+        let mut run = Run::new(vec![gone(0)]);
+
+        assert!(run.fire(0, "wsl", cpu(&[(1, 0.0)])).is_empty());
+        assert!(run.fire(1, "local", cpu(&[])).is_empty());
+        assert_eq!(run.fire(2, "wsl", cpu(&[])).len(), 1);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::processes_impl::domain::source;
+use app_contracts::features::processes::ProcessEntryVm;
+use widgets::table::query::{QueryRow, contains_ignore_case};
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+pub struct ProcessQueryRow<'a> {
+    pub vm: &'a ProcessEntryVm,
+    /// Distro the row's source reported, used to resolve full context keys.
+    pub distro: Option<&'a str>,
+}
+
+impl QueryRow for ProcessQueryRow<'_> {
+    fn contains_text(&self, needle: &str) -> bool {
+        contains_ignore_case(&self.vm.name, needle)
+    }
+
+    fn id(&self) -> Option<u64> {
+        u64::try_from(self.vm.pid).ok()
+    }
+
+    // `ctx:` takes a full context key, e.g. `ctx:wsl/distro/Ubuntu`.
+    fn context(&self) -> Option<&str> {
+        Some(self.vm.schema_id)
+    }
+
+    fn in_context(&self, key: &str) -> bool {
+        // Query keys arrive lowercased, so compare the distro the same way.
+        let distro = self.distro.map(str::to_lowercase);
+        source::in_context(self.vm.schema_id, distro.as_deref(), key)
+    }
+
+    fn metric(&self, field: &str) -> Option<f64> {
+        let id = match field {
+            "mem" => "memory",
+            other => other,
+        };
+        let numeric = self.vm.fields.iter().find(|f| f.id == id)?.numeric as f64;
+
+        // Memory columns carry GiB in `numeric`, while queries speak bytes.
+        Some(match id {
+            "memory" => numeric * GIB,
+            _ => numeric,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use widgets::table::query::TableQuery;
+
+    #[test]
+    fn context_term_matches_the_full_context_key() {
+        let wsl = ProcessEntryVm {
+            pid: 7,
+            schema_id: "wsl",
+            ..Default::default()
+        };
+        let windows = ProcessEntryVm {
+            schema_id: "windows",
+            ..wsl.clone()
+        };
+        let row = |vm, distro| ProcessQueryRow { vm, distro };
+
+        let query = TableQuery::parse("ctx:wsl/distro/Ubuntu");
+        assert!(query.matches(&row(&wsl, Some("Ubuntu"))));
+        assert!(!query.matches(&row(&wsl, Some("Debian"))));
+        assert!(!query.matches(&row(&windows, None)));
+        assert!(TableQuery::parse("ctx:host").matches(&row(&windows, None)));
+    }
+}
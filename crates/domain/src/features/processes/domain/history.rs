@@ -0,0 +1,244 @@
+use crate::processes_impl::domain::snapshot::BridgeSnapshot;
+use crate::processes_impl::scanner::consts::{ID_CPU, ID_DISK, ID_MEM, ID_NET};
+use app_contracts::features::processes::{FieldDefDto, MetricSeriesDto, ProcessKey};
+use slint::SharedString;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+fn is_tracked(id: &str) -> bool {
+    [&*ID_CPU, &*ID_MEM, &*ID_DISK, &*ID_NET]
+        .iter()
+        .any(|tracked| tracked.as_str() == id)
+}
+
+#[derive(Default)]
+struct SeriesSet(Vec<(SharedString, VecDeque<f32>)>);
+
+impl SeriesSet {
+    fn push(&mut self, id: &SharedString, value: f32, capacity: usize) {
+        let idx = match self.0.iter().position(|(key, _)| key == id) {
+            Some(idx) => idx,
+            None => {
+                self.0.push((id.clone(), VecDeque::with_capacity(capacity)));
+                self.0.len() - 1
+            }
+        };
+
+        let samples = &mut self.0[idx].1;
+        while samples.len() >= capacity {
+            samples.pop_front();
+        }
+        samples.push_back(value);
+    }
+
+    fn get(&self, id: &str) -> Option<&VecDeque<f32>> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == id)
+            .map(|(_, samples)| samples)
+    }
+
+    fn truncate(&mut self, capacity: usize) {
+        for (_, samples) in &mut self.0 {
+            while samples.len() > capacity {
+                samples.pop_front();
+            }
+        }
+    }
+}
+
+pub struct MetricHistory {
+    capacity: usize,
+    processes: HashMap<(&'static str, u32), SeriesSet>,
+    machine: HashMap<&'static str, SeriesSet>,
+    scratch_seen: HashSet<u32>,
+}
+
+impl MetricHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(2),
+            processes: HashMap::new(),
+            machine: HashMap::new(),
+            scratch_seen: HashSet::new(),
+        }
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(2);
+        for set in self.processes.values_mut().chain(self.machine.values_mut()) {
+            set.truncate(self.capacity);
+        }
+    }
+
+    pub fn record(&mut self, schema_id: &'static str, snapshot: &BridgeSnapshot) {
+        let capacity = self.capacity;
+
+        let machine = self.machine.entry(schema_id).or_default();
+        for def in snapshot.column_defs.iter().filter(|d| is_tracked(&d.id)) {
+            machine.push(&def.id, def.stat_numeric, capacity);
+        }
+
+        self.scratch_seen.clear();
+        for proc in &snapshot.processes {
+            self.scratch_seen.insert(proc.pid);
+            let set = self.processes.entry((schema_id, proc.pid)).or_default();
+            for field in proc.fields.iter().filter(|f| is_tracked(&f.id)) {
+                set.push(&field.id, field.numeric, capacity);
+            }
+        }
+
+        let seen = &self.scratch_seen;
+        self.processes
+            .retain(|(schema, pid), _| *schema != schema_id || seen.contains(pid));
+    }
+
+    pub fn process_series(&self, key: ProcessKey, columns: &[FieldDefDto]) -> Vec<MetricSeriesDto> {
+        let Some(set) = self.processes.get(&(key.schema_id, key.pid)) else {
+            return Vec::new();
+        };
+
+        columns
+            .iter()
+            .filter_map(|def| {
+                set.get(&def.id).map(|samples| MetricSeriesDto {
+                    id: def.id.clone(),
+                    label: def.label.clone(),
+                    samples: samples.iter().copied().collect(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn has_machine(&self, schema_id: &str) -> bool {
+        self.machine.contains_key(schema_id)
+    }
+
+    pub fn machine_series(&self, schema_id: &str, columns: &[FieldDefDto]) -> Vec<MetricSeriesDto> {
+        let Some(set) = self.machine.get(schema_id) else {
+            return Vec::new();
+        };
+
+        columns
+            .iter()
+            .filter_map(|def| {
+                set.get(&def.id).map(|samples| MetricSeriesDto {
+                    id: def.id.clone(),
+                    label: def.label.clone(),
+                    samples: samples.iter().copied().collect(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use app_contracts::features::processes::{ProcessFieldDto, ProcessNodeDto};
+
+    fn cpu_def(value: f32) -> FieldDefDto {
+        FieldDefDto {
+            id: ID_CPU.clone(),
+            label: "CPU".into(),
+            stat_numeric: value,
+            ..Default::default()
+        }
+    }
+
+    fn snapshot(machine_cpu: f32, processes: &[(u32, f32)]) -> BridgeSnapshot {
+        BridgeSnapshot {
+            column_defs: vec![cpu_def(machine_cpu)],
+            processes: processes
+                .iter()
+                .map(|&(pid, cpu)| ProcessNodeDto {
+                    pid,
+                    fields: vec![ProcessFieldDto {
+                        id: ID_CPU.clone(),
+                        numeric: cpu,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn samples(series: Vec<MetricSeriesDto>) -> Vec<f32> {
+        series
+            .into_iter()
+            .next()
+            .map(|s| s.samples)
+            .unwrap_or_default()
+    }
+
+    fn recorded(history: &MetricHistory, schema_id: &'static str, pid: u32) -> usize {
+        history.processes[&(schema_id, pid)]
+            .get(&ID_CPU)
+            .map_or(0, |samples| samples.len())
+    }
+
+    #[test]
+    fn keeps_only_the_latest_samples() {
+        let mut history = MetricHistory::new(3);
+        for cpu in 1..=5 {
+            history.record("wsl", &snapshot(0.0, &[(7, cpu as f32)]));
+        }
+
+        let series = history.process_series(
+            ProcessKey {
+                schema_id: "wsl",
+                pid: 7,
+            },
+            &[cpu_def(0.0)],
+        );
+        assert_eq!(samples(series), vec![3.0, 4.0, 5.0]);
+
+        history.set_capacity(2);
+        let series = history.process_series(
+            ProcessKey {
+                schema_id: "wsl",
+                pid: 7,
+            },
+            &[cpu_def(0.0)],
+        );
+        assert_eq!(samples(series), vec![4.0, 5.0]);
+    }
+
+    #[test]
+    fn equal_pids_from_different_sources_keep_their_own_series() {
+        let mut history = MetricHistory::new(4);
+        history.record("wsl", &snapshot(0.0, &[(1, 10.0)]));
+        history.record("windows", &snapshot(0.0, &[(1, 90.0)]));
+        history.record("wsl", &snapshot(0.0, &[(1, 20.0)]));
+
+        assert_eq!(recorded(&history, "wsl", 1), 2);
+        assert_eq!(recorded(&history, "windows", 1), 1);
+    }
+
+    #[test]
+    fn drops_processes_missing_from_their_own_source_only() {
+        let mut history = MetricHistory::new(4);
+        history.record("wsl", &snapshot(0.0, &[(1, 10.0), (2, 10.0)]));
+        history.record("windows", &snapshot(0.0, &[(2, 10.0)]));
+        history.record("wsl", &snapshot(0.0, &[(1, 10.0)]));
+
+        assert!(history.processes.contains_key(&("wsl", 1)));
+        assert!(!history.processes.contains_key(&("wsl", 2)));
+        assert!(history.processes.contains_key(&("windows", 2)));
+    }
+
+    #[test]
+    fn machine_series_come_from_the_requested_source() {
+        let mut history = MetricHistory::new(4);
+        history.record("wsl", &snapshot(10.0, &[]));
+        history.record("windows", &snapshot(90.0, &[]));
+
+        assert!(history.has_machine("wsl"));
+        assert!(!history.has_machine("local"));
+        assert_eq!(
+            samples(history.machine_series("windows", &[cpu_def(0.0)])),
+            vec![90.0]
+        );
+        assert!(history.machine_series("local", &[cpu_def(0.0)]).is_empty());
+    }
+}
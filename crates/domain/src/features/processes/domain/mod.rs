@@ -1,3 +1,10 @@
+pub mod activity;
+pub mod alerts;
+pub mod filter;
+pub mod history;
+pub mod pins;
 pub mod snapshot;
+pub mod source;
+pub mod stuck;
 pub mod table;
 pub mod table_builder;
@@ -0,0 +1,142 @@
+use app_contracts::features::processes::ProcessNodeDto;
+use slint::SharedString;
+
+// Patterns match the process name, or the exe path when they contain a path separator.
+// Matching ignores case, `*` stands for any run of characters and a trailing `.exe` is optional.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessPins {
+    patterns: Vec<(SharedString, String)>,
+}
+
+impl ProcessPins {
+    pub fn new(patterns: &[String]) -> Self {
+        Self {
+            patterns: patterns
+                .iter()
+                .map(|p| p.trim())
+                .filter(|p| !p.is_empty())
+                .map(|p| (p.into(), p.to_lowercase()))
+                .collect(),
+        }
+    }
+
+    pub fn pattern_for(&self, proc: &ProcessNodeDto) -> Option<SharedString> {
+        if self.patterns.is_empty() {
+            return None;
+        }
+
+        let name = proc.name.to_lowercase();
+        let stem = name.strip_suffix(".exe").unwrap_or(&name);
+        let exe_path = proc.exe_path.to_lowercase();
+
+        self.patterns
+            .iter()
+            .find(|(_, pattern)| {
+                if pattern.contains(['/', '\\']) {
+                    wildcard_match(pattern, &exe_path)
+                } else {
+                    wildcard_match(pattern, &name) || wildcard_match(pattern, stem)
+                }
+            })
+            .map(|(original, _)| original.clone())
+    }
+}
+
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc(name: &str, exe_path: &str) -> ProcessNodeDto {
+        ProcessNodeDto {
+            name: name.into(),
+            exe_path: exe_path.into(),
+            ..Default::default()
+        }
+    }
+
+    fn pinned_by(patterns: &[&str], name: &str, exe_path: &str) -> Option<String> {
+        let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+        ProcessPins::new(&patterns)
+            .pattern_for(&proc(name, exe_path))
+            .map(|p| p.to_string())
+    }
+
+    #[test]
+    fn name_match_ignores_case_and_exe_suffix() {
+        assert_eq!(
+            pinned_by(&["dockerd"], "DockerD.exe", "").as_deref(),
+            Some("dockerd")
+        );
+        assert_eq!(
+            pinned_by(&["Code.EXE"], "code.exe", "").as_deref(),
+            Some("Code.EXE")
+        );
+        assert!(pinned_by(&["dockerd"], "dockerd-helper", "").is_none());
+    }
+
+    #[test]
+    fn trailing_wildcard_matches_name_prefix() {
+        assert_eq!(
+            pinned_by(&["rust-analyzer*"], "rust-analyzer-proc-macro-srv", "").as_deref(),
+            Some("rust-analyzer*")
+        );
+        assert!(pinned_by(&["rust-analyzer*"], "cargo", "").is_none());
+    }
+
+    #[test]
+    fn patterns_with_separators_match_the_exe_path() {
+        let patterns = ["/opt/*/bin/node"];
+
+        assert!(pinned_by(&patterns, "node", "/opt/nvm/bin/node").is_some());
+        assert!(pinned_by(&patterns, "node", "/usr/bin/node").is_none());
+        assert!(pinned_by(&[r"C:\Tools\*"], "x", r"c:\tools\x.exe").is_some());
+        // A path pattern never falls back to the name.
+        assert!(pinned_by(&["/opt/*/bin/node"], "/opt/a/bin/node", "").is_none());
+    }
+
+    #[test]
+    fn blank_patterns_are_ignored() {
+        let pins = ProcessPins::new(&["  ".into(), String::new()]);
+        assert!(pins.pattern_for(&proc("anything", "")).is_none());
+
+        assert_eq!(pinned_by(&["  sshd "], "sshd", "").as_deref(), Some("sshd"));
+    }
+
+    #[test]
+    fn first_matching_pattern_wins() {
+        assert_eq!(
+            pinned_by(&["ssh*", "sshd"], "sshd", "").as_deref(),
+            Some("ssh*")
+        );
+    }
+
+    #[test]
+    fn wildcard_parts_do_not_overlap() {
+        assert!(wildcard_match("a*a", "aa"));
+        assert!(!wildcard_match("a*a", "a"));
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("a*b*c", "a-b-b-c"));
+        assert!(!wildcard_match("a*b*c", "a-c-b"));
+    }
+}
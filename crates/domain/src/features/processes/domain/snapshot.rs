@@ -1,7 +1,54 @@
+use crate::processes_impl::scanner::consts::ID_SOURCE;
 use app_contracts::features::processes::{FieldDefDto, ProcessNodeDto};
+use serde_json::Value;
+use widgets::table::export::{ExportColumn, TableExport};
 
 #[derive(Clone)]
 pub struct BridgeSnapshot {
     pub column_defs: Vec<FieldDefDto>,
     pub processes: Vec<ProcessNodeDto>,
 }
+
+impl BridgeSnapshot {
+    // The full snapshot regardless of filters or hidden columns.
+    pub fn export(&self) -> TableExport {
+        // The raw schema id is exported instead of the source column's display label.
+        let defs: Vec<&FieldDefDto> = self
+            .column_defs
+            .iter()
+            .filter(|c| c.id != *ID_SOURCE)
+            .collect();
+
+        let mut columns = vec![
+            ExportColumn::new("pid", "PID"),
+            ExportColumn::new("parent_pid", "Parent PID"),
+            ExportColumn::new("source", "Source"),
+            ExportColumn::new("name", "Name"),
+            ExportColumn::new("exe_path", "Executable"),
+        ];
+        columns.extend(
+            defs.iter()
+                .map(|c| ExportColumn::new(c.id.as_str(), c.label.as_str())),
+        );
+
+        let mut export = TableExport::new(columns);
+        for process in &self.processes {
+            let mut cells: Vec<Value> = vec![
+                process.pid.into(),
+                process.parent_pid.into(),
+                process.schema_id.into(),
+                process.name.as_str().into(),
+                process.exe_path.as_str().into(),
+            ];
+            cells.extend(defs.iter().map(|c| {
+                process
+                    .fields
+                    .iter()
+                    .find(|f| f.id == c.id)
+                    .map_or(Value::Null, |f| f.text.as_str().into())
+            }));
+            export.push_row(cells);
+        }
+        export
+    }
+}
@@ -0,0 +1,74 @@
+use slint::SharedString;
+
+pub fn source_label(schema_id: &str) -> SharedString {
+    match schema_id {
+        "windows" => "Windows".into(),
+        "wsl" => "WSL".into(),
+        "linux" => "Linux".into(),
+        "local" => "Local".into(),
+        other => other.into(),
+    }
+}
+
+// Tab context keys are `host` or `<schema>/<kind>/<name>`, e.g. `wsl/distro/Ubuntu`. Everything
+// but WSL runs on the host itself. `distro` is the one the schema's agent reported running in;
+// containers cannot be told apart, so they share their schema.
+pub fn in_context(schema_id: &str, distro: Option<&str>, context_key: &str) -> bool {
+    match context_key.split_once('/') {
+        Some((schema, rest)) => {
+            schema == schema_id
+                && match rest.strip_prefix("distro/") {
+                    Some(name) => distro.is_none_or(|d| d == name),
+                    None => true,
+                }
+        }
+        None if context_key == "host" => schema_id != "wsl",
+        None => context_key == schema_id,
+    }
+}
+
+// Host tabs take the machine stats of the first host-side source that has reported.
+const HOST_SCHEMAS: [&str; 3] = ["windows", "linux", "local"];
+
+pub fn machine_schema<'a>(
+    context_key: &'a str,
+    reported: impl Fn(&str) -> bool,
+) -> Option<&'a str> {
+    match context_key.split_once('/') {
+        Some((schema, _)) => Some(schema),
+        None if context_key == "host" => HOST_SCHEMAS.into_iter().find(|s| reported(s)),
+        None => Some(context_key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_sources_to_tab_contexts() {
+        assert!(in_context("windows", None, "host"));
+        assert!(in_context("local", None, "host"));
+        assert!(!in_context("wsl", None, "host"));
+        assert!(in_context("wsl", None, "wsl/docker/0123abcd"));
+        assert!(!in_context("windows", None, "wsl/distro/Ubuntu"));
+    }
+
+    #[test]
+    fn distro_tabs_only_show_the_agent_distro() {
+        assert!(in_context("wsl", Some("Ubuntu"), "wsl/distro/Ubuntu"));
+        assert!(!in_context("wsl", Some("Debian"), "wsl/distro/Ubuntu"));
+        assert!(in_context("wsl", Some("Debian"), "wsl/docker/0123abcd"));
+        // Agents that do not report their distro keep matching every distro tab.
+        assert!(in_context("wsl", None, "wsl/distro/Ubuntu"));
+    }
+
+    #[test]
+    fn picks_machine_stats_for_the_tab() {
+        let reported = |s: &str| s == "wsl" || s == "local";
+        assert_eq!(machine_schema("wsl/distro/Ubuntu", reported), Some("wsl"));
+        assert_eq!(machine_schema("host", reported), Some("local"));
+        assert_eq!(machine_schema("host", |_| true), Some("windows"));
+        assert_eq!(machine_schema("host", |s| s == "wsl"), None);
+    }
+}
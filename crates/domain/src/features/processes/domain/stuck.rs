@@ -0,0 +1,199 @@
+use crate::processes_impl::scanner::consts::ID_STATE;
+use app_contracts::features::processes::{ProcessKey, ProcessNodeDto};
+use slint::SharedString;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// Tracks how long processes stay in uninterruptible sleep (D) or as unreaped zombies (Z).
+#[derive(Default)]
+pub struct StuckDetector {
+    since: HashMap<ProcessKey, (char, Instant)>,
+}
+
+impl StuckDetector {
+    // Returns a badge for every process that has held its state for at least `after`.
+    pub fn observe(
+        &mut self,
+        processes: &[ProcessNodeDto],
+        after: Duration,
+        now: Instant,
+    ) -> HashMap<ProcessKey, SharedString> {
+        let by_key: HashMap<ProcessKey, &ProcessNodeDto> =
+            processes.iter().map(|p| (p.key(), p)).collect();
+        let mut since = HashMap::new();
+        let mut flagged = HashMap::new();
+
+        for proc in processes {
+            let Some(state @ ('D' | 'Z')) = state_of(proc) else {
+                continue;
+            };
+            let key = proc.key();
+            let started = match self.since.get(&key) {
+                Some(&(prev, at)) if prev == state => at,
+                _ => now,
+            };
+            since.insert(key, (state, started));
+
+            let held = now.duration_since(started);
+            if held < after {
+                continue;
+            }
+
+            let parent = by_key.get(&ProcessKey {
+                schema_id: proc.schema_id,
+                pid: proc.parent_pid,
+            });
+            let badge = match (state, parent) {
+                ('Z', Some(parent)) => format!(
+                    "Zombie {}, not reaped by {} ({})",
+                    short_duration(held),
+                    parent.name,
+                    parent.pid
+                ),
+                ('Z', None) => format!("Zombie {}", short_duration(held)),
+                _ => format!("D-state {}", short_duration(held)),
+            };
+            flagged.insert(key, badge.into());
+        }
+
+        self.since = since;
+        flagged
+    }
+}
+
+fn state_of(proc: &ProcessNodeDto) -> Option<char> {
+    proc.fields
+        .iter()
+        .find(|f| f.id == ID_STATE.as_str())
+        .and_then(|f| f.text.chars().next())
+}
+
+fn short_duration(d: Duration) -> String {
+    match d.as_secs() {
+        s if s < 60 => format!("{s}s"),
+        s if s < 3600 => format!("{}m", s / 60),
+        s => format!("{}h", s / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use app_contracts::features::processes::ProcessFieldDto;
+
+    const AFTER: Duration = Duration::from_secs(30);
+
+    fn process(pid: u32, parent_pid: u32, name: &str, state: &str) -> ProcessNodeDto {
+        ProcessNodeDto {
+            pid,
+            parent_pid,
+            schema_id: "linux",
+            name: name.into(),
+            fields: vec![ProcessFieldDto {
+                id: ID_STATE.clone(),
+                text: state.into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn key(pid: u32) -> ProcessKey {
+        ProcessKey {
+            schema_id: "linux",
+            pid,
+        }
+    }
+
+    fn secs(start: Instant, s: u64) -> Instant {
+        start + Duration::from_secs(s)
+    }
+
+    #[test]
+    fn nothing_is_flagged_before_the_hold_elapses() {
+        let mut detector = StuckDetector::default();
+        let start = Instant::now();
+        let procs = [process(12, 1, "cp", "D")];
+
+        assert!(detector.observe(&procs, AFTER, start).is_empty());
+        assert!(detector.observe(&procs, AFTER, secs(start, 29)).is_empty());
+        assert_eq!(
+            detector.observe(&procs, AFTER, secs(start, 30))[&key(12)],
+            "D-state 30s"
+        );
+    }
+
+    #[test]
+    fn zombie_badge_names_the_parent() {
+        let mut detector = StuckDetector::default();
+        let start = Instant::now();
+        let procs = [process(10, 1, "bash", "S"), process(11, 10, "defunct", "Z")];
+
+        detector.observe(&procs, AFTER, start);
+        let flagged = detector.observe(&procs, AFTER, secs(start, 90));
+
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[&key(11)], "Zombie 1m, not reaped by bash (10)");
+    }
+
+    #[test]
+    fn zombie_without_visible_parent_has_plain_badge() {
+        let mut detector = StuckDetector::default();
+        let start = Instant::now();
+        let procs = [process(11, 99, "defunct", "Z")];
+
+        detector.observe(&procs, AFTER, start);
+        assert_eq!(
+            detector.observe(&procs, AFTER, secs(start, 7200))[&key(11)],
+            "Zombie 2h"
+        );
+    }
+
+    #[test]
+    fn leaving_the_state_restarts_the_clock() {
+        let mut detector = StuckDetector::default();
+        let start = Instant::now();
+        let stuck = [process(12, 1, "cp", "D")];
+
+        detector.observe(&stuck, AFTER, start);
+        detector.observe(&[process(12, 1, "cp", "R")], AFTER, secs(start, 60));
+
+        assert!(detector.observe(&stuck, AFTER, secs(start, 61)).is_empty());
+    }
+
+    #[test]
+    fn switching_between_stuck_states_restarts_the_clock() {
+        let mut detector = StuckDetector::default();
+        let start = Instant::now();
+
+        detector.observe(&[process(12, 1, "cp", "D")], AFTER, start);
+        let flagged = detector.observe(&[process(12, 1, "cp", "Z")], AFTER, secs(start, 60));
+
+        assert!(flagged.is_empty());
+    }
+
+    #[test]
+    fn same_pid_in_another_source_is_tracked_separately() {
+        let mut detector = StuckDetector::default();
+        let start = Instant::now();
+        let mut wsl = process(12, 1, "cp", "D");
+        wsl.schema_id = "wsl";
+
+        detector.observe(&[process(12, 1, "cp", "D")], AFTER, start);
+        let flagged = detector.observe(&[wsl], AFTER, secs(start, 60));
+
+        assert!(flagged.is_empty());
+    }
+
+    #[test]
+    fn rows_without_state_are_ignored() {
+        let mut detector = StuckDetector::default();
+        let start = Instant::now();
+        let mut procs = [process(12, 1, "cp", "")];
+        detector.observe(&procs, AFTER, start);
+        assert!(detector.observe(&procs, AFTER, secs(start, 60)).is_empty());
+
+        procs[0].fields.clear();
+        assert!(detector.observe(&procs, AFTER, secs(start, 120)).is_empty());
+    }
+}
@@ -1,15 +1,32 @@
+use crate::processes_impl::application::actor::ColumnLayoutChanged;
+use crate::processes_impl::domain::filter::ProcessQueryRow;
+use crate::processes_impl::domain::pins::ProcessPins;
 use crate::processes_impl::domain::snapshot::BridgeSnapshot;
-use crate::processes_impl::domain::table_builder::ProcessTreeBuilder;
+use crate::processes_impl::domain::source;
+use crate::processes_impl::domain::stuck::StuckDetector;
+use crate::processes_impl::domain::table_builder::{ProcessTreeBuilder, ProcessTreeMode};
+use crate::processes_impl::scanner::consts::{
+    ID_CPU_TIME, ID_FDS, ID_PAGE_FAULTS, ID_SOURCE, ID_STATE, ID_THREADS, NET_TRANSPORTS,
+};
+use crate::processes_impl::services::control::ProcessTarget;
 use crate::processes_impl::services::metadata::ProcessMetadataService;
-use crate::processes_impl::settings::ProcessSettings;
+use crate::processes_impl::settings::{ColumnMetadata, ProcessSettings};
 use app_contracts::features::processes::{
-    FieldDefDto, FieldMetadata, ProcessEntryVm, ProcessNodeDto,
+    ColumnChoiceDto, FieldDefDto, FieldMetadata, ProcessEntryVm, ProcessFieldDto, ProcessKey,
+    ProcessNodeDto,
 };
+use app_core::actor::event_bus::EventBus;
 use dashmap::DashMap;
 use framework::settings::reactive::ReactiveSettingSubscription;
+use serde_json::Value;
 use slint::SharedString;
-use widgets::table::flow::{SortState, TableNode};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use widgets::table::export::{ExportColumn, TableExport};
+use widgets::table::flow::{SelectMode, SortState, TableNode};
 use widgets::table::layout::TableSettingsProvider;
+use widgets::table::query::TableQuery;
 use widgets::table::view::TableView;
 use widgets::table::window::TableBatch;
 
@@ -37,10 +54,27 @@ impl TableSettingsProvider for ProcessTableSettingsAdapter {
 }
 
 pub struct ProcessTable {
-    view: TableView<ProcessNodeDto, ProcessEntryVm, u32, SharedString, SharedString, SharedString>,
+    view: TableView<
+        ProcessNodeDto,
+        ProcessEntryVm,
+        ProcessKey,
+        SharedString,
+        SharedString,
+        SharedString,
+    >,
     settings: ProcessSettings,
     grouping_scratchpad: Vec<(SharedString, usize)>,
+    tree_mode: ProcessTreeMode,
+    query: TableQuery,
+    scoped: bool,
+    distros: HashMap<&'static str, SharedString>,
+    pins: ProcessPins,
+    pin_patterns: Arc<Vec<String>>,
+    stuck: StuckDetector,
+    stuck_badges: HashMap<ProcessKey, SharedString>,
     _sub: ReactiveSettingSubscription,
+    _layout_subs: [ReactiveSettingSubscription; 3],
+    snapshot_columns: Vec<FieldDefDto>,
     header_columns: Vec<FieldDefDto>,
 }
 
@@ -53,16 +87,46 @@ impl ProcessTable {
             },
             50,
         );
+        view.flow
+            .set_pin_key(|vm: &ProcessEntryVm| vm.pinned_by.clone());
 
         let sub = ProcessTableSettingsAdapter(settings.clone()).setup(&mut view.layout)?;
+        register_builtin_columns(&settings)?;
+        let layout_subs = [
+            settings
+                .columns()
+                .visible()
+                .subscribe(|_| EventBus::publish(ColumnLayoutChanged)),
+            settings
+                .columns()
+                .order()
+                .subscribe(|_| EventBus::publish(ColumnLayoutChanged)),
+            settings
+                .pinned()
+                .subscribe(|_| EventBus::publish(ColumnLayoutChanged)),
+        ];
+        let pin_patterns = settings.pinned().get_arc();
+        let scoped = settings.scope_to_context().get();
 
-        Ok(Self {
+        let mut table = Self {
             view,
             settings,
             grouping_scratchpad: Vec::with_capacity(1024),
+            tree_mode: ProcessTreeMode::default(),
+            query: TableQuery::default(),
+            scoped,
+            distros: HashMap::new(),
+            pins: ProcessPins::new(&pin_patterns),
+            pin_patterns,
+            stuck: StuckDetector::default(),
+            stuck_badges: HashMap::new(),
             _sub: sub,
+            _layout_subs: layout_subs,
+            snapshot_columns: Vec::new(),
             header_columns: Vec::new(),
-        })
+        };
+        table.seed_pins();
+        Ok(table)
     }
 
     pub fn handle_snapshot(
@@ -70,12 +134,17 @@ impl ProcessTable {
         snapshot: BridgeSnapshot,
         metadata: &mut ProcessMetadataService,
     ) -> anyhow::Result<()> {
+        let stuck_after = Duration::from_secs(self.settings.stuck_after_secs().get());
+        self.stuck_badges = self
+            .stuck
+            .observe(&snapshot.processes, stuck_after, Instant::now());
         self.view.flow.set_items(snapshot.processes);
-        self.build_header(snapshot.column_defs);
+        self.snapshot_columns = snapshot.column_defs;
+        self.build_header();
         self.refresh(metadata)
     }
 
-    fn build_header(&mut self, snapshot_metrics: Vec<FieldDefDto>) {
+    pub fn build_header(&mut self) {
         let mut columns = Vec::new();
 
         let mut name_def = FieldDefDto::default();
@@ -83,25 +152,50 @@ impl ProcessTable {
         name_def.label = "Name".into();
         columns.push(name_def);
 
-        for metric in snapshot_metrics {
-            if !columns.iter().any(|c| c.id == metric.id) {
-                columns.push(metric);
+        let visible = self.settings.columns().visible().get_arc();
+        let metadata = self.settings.columns().column_metadata().get_arc();
+        columns.extend(
+            self.arranged_metrics()
+                .into_iter()
+                .filter(|m| is_column_visible(&m.id, &visible, &metadata)),
+        );
+
+        self.header_columns = columns;
+    }
+
+    // Deduplicated snapshot metrics in persisted order; unknown ids keep snapshot order at the end.
+    fn arranged_metrics(&self) -> Vec<FieldDefDto> {
+        let order = self.settings.columns().order().get_arc();
+        let mut metrics: Vec<FieldDefDto> = Vec::with_capacity(self.snapshot_columns.len());
+        for metric in &self.snapshot_columns {
+            if !metrics.iter().any(|m| m.id == metric.id) {
+                metrics.push(metric.clone());
             }
         }
 
-        self.header_columns = columns;
+        metrics.sort_by_key(|m| {
+            order
+                .iter()
+                .position(|id| id == m.id.as_str())
+                .unwrap_or(usize::MAX)
+        });
+        metrics
     }
 
     pub fn refresh(&mut self, metadata: &mut ProcessMetadataService) -> anyhow::Result<()> {
+        self.sync_pins();
         let mut builder = ProcessTreeBuilder {
             metadata,
+            pins: &self.pins,
+            stuck: &self.stuck_badges,
             grouping_scratchpad: &mut self.grouping_scratchpad,
+            mode: self.tree_mode,
         };
         let sort_state = self.view.flow.sort.clone();
         self.view.refresh_full(
             &mut builder,
             |nodes, _| sort_nodes_inplace(nodes, &sort_state),
-            |vm| vm.pid as u32,
+            |vm| vm.key(),
             |vm| vm.is_dead = true,
             |vm, col_id, _width| {
                 if let Some(f) = vm.fields.iter_mut().find(|f| f.id == *col_id)
@@ -111,24 +205,248 @@ impl ProcessTable {
                 }
             },
         );
+        self.project_fields();
+        self.mark_selection();
         Ok(())
     }
 
+    // Ghosts of patterns that were unpinned must not linger, so they are dropped on any change.
+    fn sync_pins(&mut self) {
+        let patterns = self.settings.pinned().get_arc();
+        if Arc::ptr_eq(&patterns, &self.pin_patterns) {
+            return;
+        }
+        self.pins = ProcessPins::new(&patterns);
+        self.pin_patterns = patterns;
+        self.view.flow.clear_pinned();
+        self.seed_pins();
+    }
+
+    // Pins outlive restarts, so every pattern shows up as a dead row until a process matches it.
+    fn seed_pins(&mut self) {
+        let placeholders = self
+            .pin_patterns
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                let pattern = SharedString::from(p);
+                let vm = ProcessEntryVm {
+                    // No real pid, so the stand-in never shares a key with a live row.
+                    pid: -1,
+                    name: pattern.clone(),
+                    pinned_by: Some(pattern.clone()),
+                    ..Default::default()
+                };
+                (pattern, vm)
+            });
+        self.view.flow.seed_pinned(placeholders);
+    }
+
+    // Unpins the pattern a row was pinned by, or pins the process by its name.
+    pub fn toggle_pin(&self, key: ProcessKey) -> anyhow::Result<()> {
+        let pinned_by = self
+            .view
+            .rows
+            .items
+            .iter()
+            .find(|vm| vm.key() == key)
+            .and_then(|vm| vm.pinned_by.clone());
+
+        if let Some(pattern) = pinned_by {
+            return self.settings.patch_pinned(|pinned| {
+                *pinned = pinned
+                    .iter()
+                    .filter(|p| p.trim() != pattern.as_str())
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .into();
+            });
+        }
+
+        let Some(name) = self.name_for(key) else {
+            return Ok(());
+        };
+        self.settings.patch_pinned(|pinned| {
+            let mut next = pinned.as_ref().clone();
+            next.push(name.to_string());
+            *pinned = next.into();
+        })
+    }
+
+    fn mark_selection(&mut self) {
+        self.view
+            .mark_selection(|vm| vm.key(), |vm, selected| vm.is_selected = selected);
+    }
+
+    // Rows render cells by position, so fields must follow the header order.
+    fn project_fields(&mut self) {
+        let columns = self.header_columns.get(1..).unwrap_or_default();
+
+        for vm in &mut self.view.rows.items {
+            let in_order = vm.fields.len() == columns.len()
+                && vm.fields.iter().zip(columns).all(|(f, c)| f.id == c.id);
+            if in_order {
+                continue;
+            }
+
+            let mut fields = std::mem::take(&mut vm.fields);
+            vm.fields = columns
+                .iter()
+                .map(|c| match fields.iter().position(|f| f.id == c.id) {
+                    Some(idx) => fields.swap_remove(idx),
+                    None => ProcessFieldDto {
+                        id: c.id.clone(),
+                        numeric: -1.0,
+                        ..Default::default()
+                    },
+                })
+                .collect();
+        }
+    }
+
+    pub fn stuck_count(&self) -> usize {
+        self.stuck_badges.len()
+    }
+
     pub fn get_header_columns(&self) -> Vec<FieldDefDto> {
         self.header_columns.clone()
     }
 
-    pub fn selected_name_for_pid(&self, pid: u32) -> Option<SharedString> {
-        self.view
+    // Exports the rows as currently filtered and sorted, with every column the snapshot supplies,
+    // hidden ones included. Metric columns carry their numeric value in a `<id>_value` column.
+    pub fn export(&self) -> TableExport {
+        let metadata = self.settings.columns().column_metadata().get_arc();
+        let columns: Vec<(FieldDefDto, bool)> = self
+            .arranged_metrics()
+            .into_iter()
+            .filter(|c| c.id != *ID_SOURCE)
+            .map(|c| {
+                let is_metric = metadata.get(c.id.as_str()).is_some_and(|m| m.is_metric);
+                (c, is_metric)
+            })
+            .collect();
+
+        let mut header = vec![
+            ExportColumn::new("pid", "PID"),
+            ExportColumn::new("source", "Source"),
+            ExportColumn::new("name", "Name"),
+        ];
+        for (column, is_metric) in &columns {
+            header.push(ExportColumn::new(column.id.as_str(), column.label.as_str()));
+            if *is_metric {
+                header.push(ExportColumn::new(
+                    format!("{}_value", column.id),
+                    format!("{} (value)", column.label),
+                ));
+            }
+        }
+
+        // Rows only hold the visible fields, so values are read from the snapshot items. Dead
+        // ghosts are no longer there and export what they last showed.
+        let items: HashMap<ProcessKey, &ProcessNodeDto> = self
+            .view
             .flow
             .items()
             .iter()
-            .find(|p| p.pid == pid)
+            .map(|p| (p.key(), p))
+            .collect();
+
+        let mut export = TableExport::new(header);
+        for vm in &self.view.rows.items {
+            let fields = match items.get(&vm.key()) {
+                Some(item) if !vm.is_dead => &item.fields,
+                _ => &vm.fields,
+            };
+
+            let mut cells = vec![vm.pid.into(), vm.schema_id.into(), vm.name.as_str().into()];
+            for (column, is_metric) in &columns {
+                let field = fields.iter().find(|f| f.id == column.id);
+                cells.push(field.map_or(Value::Null, |f| f.text.as_str().into()));
+                if *is_metric {
+                    // Negative values mark a metric the source did not report.
+                    let value = field.filter(|f| f.numeric >= 0.0);
+                    cells.push(value.map_or(Value::Null, |f| f64::from(f.numeric).into()));
+                }
+            }
+            export.push_row(cells);
+        }
+        export
+    }
+
+    // Resolves the source name the UI sends back into the row's key; selected ghosts count too.
+    pub fn key_for(&self, source: &str, pid: u32) -> Option<ProcessKey> {
+        self.view
+            .rows
+            .items
+            .iter()
+            .map(|vm| vm.key())
+            .chain(self.view.flow.selected_ids().copied())
+            .find(|k| k.schema_id == source && k.pid == pid)
+    }
+
+    pub fn name_for(&self, key: ProcessKey) -> Option<SharedString> {
+        self.view
+            .flow
+            .find(|p| p.key() == key)
             .map(|p| p.name.clone())
     }
 
+    pub fn cmdline_for(&self, key: ProcessKey) -> Option<SharedString> {
+        self.view
+            .flow
+            .find(|p| p.key() == key)
+            .map(|p| p.cmdline.clone())
+    }
+
+    // With `tree`, descendants reachable through parent_pid links within the same schema are included.
+    pub fn action_targets(&self, keys: &[ProcessKey], tree: bool) -> Vec<ProcessTarget> {
+        let items = self.view.flow.items();
+        let by_key: HashMap<ProcessKey, &ProcessNodeDto> =
+            items.iter().map(|p| (p.key(), p)).collect();
+
+        let mut targets = Vec::new();
+        let mut seen = HashSet::new();
+        let mut queue = keys.to_vec();
+
+        while let Some(key) = queue.pop() {
+            if !seen.insert(key) {
+                continue;
+            }
+            let Some(node) = by_key.get(&key) else {
+                continue;
+            };
+
+            if tree {
+                queue.extend(
+                    items
+                        .iter()
+                        .filter(|p| {
+                            p.parent_pid == key.pid
+                                && p.pid != key.pid
+                                && p.schema_id == key.schema_id
+                        })
+                        .map(|p| p.key()),
+                );
+            }
+
+            targets.push(ProcessTarget {
+                pid: key.pid,
+                name: node.name.clone(),
+                schema_id: key.schema_id,
+            });
+        }
+
+        targets
+    }
+
     pub fn clear_selection(&mut self) {
         self.view.flow.clear_selection();
+        self.mark_selection();
+    }
+
+    pub fn selected_keys(&self) -> Vec<ProcessKey> {
+        self.view.flow.selected_ids().copied().collect()
     }
 
     pub fn sort_state(&self) -> &SortState<SharedString> {
@@ -168,14 +486,82 @@ impl ProcessTable {
             .collect()
     }
     pub fn column_widths(&self) -> Vec<(SharedString, u64)> {
-        self.view
-            .layout
-            .widths
+        let def_width = self.settings.columns().default_width_px().get();
+        let persisted = self.settings.columns().widths_px().get_arc();
+
+        self.header_columns
             .iter()
-            .map(|(key, signal)| (key.clone(), signal.get()))
+            .map(|c| {
+                let width = match self.view.layout.widths.get(&c.id) {
+                    Some(signal) => signal.get(),
+                    None => persisted
+                        .get(c.id.as_str())
+                        .map(|w| *w.value())
+                        .unwrap_or(def_width),
+                };
+                (c.id.clone(), width)
+            })
             .collect()
     }
 
+    pub fn column_choices(&self) -> Vec<ColumnChoiceDto> {
+        let visible = self.settings.columns().visible().get_arc();
+        let metadata = self.settings.columns().column_metadata().get_arc();
+
+        self.arranged_metrics()
+            .into_iter()
+            .map(|m| ColumnChoiceDto {
+                visible: is_column_visible(&m.id, &visible, &metadata),
+                id: m.id,
+                label: m.label,
+            })
+            .collect()
+    }
+
+    pub fn toggle_column(&self, id: &str) -> anyhow::Result<()> {
+        let metadata = self.settings.columns().column_metadata().get_arc();
+        let visible = self.settings.columns().visible().get_arc();
+        let next = !is_column_visible(id, &visible, &metadata);
+
+        self.settings.columns().patch_visible(|visible| {
+            visible.insert(id.to_string(), next);
+        })
+    }
+
+    pub fn move_column(&self, id: &str, delta: i32) -> anyhow::Result<()> {
+        let mut order: Vec<String> = self
+            .arranged_metrics()
+            .into_iter()
+            .map(|m| m.id.to_string())
+            .collect();
+        let Some(from) = order.iter().position(|c| c == id) else {
+            return Ok(());
+        };
+        let to = from
+            .saturating_add_signed(delta as isize)
+            .min(order.len() - 1);
+        if from == to {
+            return Ok(());
+        }
+
+        let column = order.remove(from);
+        order.insert(to, column);
+        order.insert(0, "name".to_string());
+
+        // Keep ids that are not in the current snapshot, e.g. columns of another context.
+        let persisted = self.settings.columns().order().get_arc();
+        let missing: Vec<String> = persisted
+            .iter()
+            .filter(|c| !order.contains(c))
+            .cloned()
+            .collect();
+        order.extend(missing);
+
+        self.settings.columns().patch_order(|current| {
+            *current = order.into();
+        })
+    }
+
     pub fn toggle_sort(&mut self, field_id: SharedString) {
         let current = &mut self.view.flow.sort;
         if current.field_id.as_ref() == Some(&field_id) {
@@ -186,12 +572,65 @@ impl ProcessTable {
         }
     }
 
+    pub fn tree_mode(&self) -> ProcessTreeMode {
+        self.tree_mode
+    }
+
+    pub fn set_tree_mode(&mut self, mode: ProcessTreeMode) {
+        self.tree_mode = mode;
+    }
+
+    pub fn set_filter(&mut self, query: &str, context: &str) {
+        self.query = TableQuery::parse(query);
+        self.apply_filter(context);
+    }
+
+    pub fn set_scoped(&mut self, scoped: bool, context: &str) {
+        self.scoped = scoped;
+        self.apply_filter(context);
+    }
+
+    // Returns true when the distro an agent runs in has changed and the filter needs reapplying.
+    pub fn set_distros(&mut self, distros: HashMap<&'static str, SharedString>) -> bool {
+        if distros == self.distros {
+            return false;
+        }
+        self.distros = distros;
+        true
+    }
+
+    pub fn apply_filter(&mut self, context: &str) {
+        if self.query.is_empty() && !self.scoped {
+            self.view.flow.clear_filter();
+            return;
+        }
+
+        let query = self.query.clone();
+        let scoped = self.scoped;
+        let context = context.to_string();
+        let distros = self.distros.clone();
+        self.view.flow.set_filter(move |vm| {
+            let distro = distros.get(vm.schema_id).map(|d| d.as_str());
+            (!scoped || source::in_context(vm.schema_id, distro, &context))
+                && query.matches(&ProcessQueryRow { vm, distro })
+        });
+    }
+
     pub fn toggle_expand(&mut self, group_id: SharedString) {
         self.view.flow.toggle_expand(group_id);
     }
 
-    pub fn select(&mut self, pid: u32, idx: usize) {
-        self.view.flow.select(pid, idx);
+    // The row index is tried first; rows may have moved since the click, so it is checked.
+    pub fn select(&mut self, source: &str, pid: u32, idx: usize, mode: SelectMode) {
+        let key = match self.view.rows.items.get(idx) {
+            Some(vm) if vm.schema_id == source && vm.pid as u32 == pid => Some(vm.key()),
+            _ => self.key_for(source, pid),
+        };
+        let Some(key) = key else {
+            return;
+        };
+        self.view.select(key, idx, mode, |vm| vm.key());
+        self.mark_selection();
     }
 
     pub fn set_viewport(&mut self, start: usize, count: usize) {
@@ -203,11 +642,67 @@ impl ProcessTable {
     }
 }
 
+fn is_column_visible(
+    id: &str,
+    visible: &DashMap<String, bool>,
+    metadata: &DashMap<String, ColumnMetadata>,
+) -> bool {
+    match visible.get(id) {
+        Some(v) => *v,
+        None => !metadata.get(id).is_some_and(|m| m.is_optional),
+    }
+}
+
+// Columns added after a user's metadata was persisted are merged into it.
+fn register_builtin_columns(settings: &ProcessSettings) -> anyhow::Result<()> {
+    let optional = |is_text: bool, is_metric: bool| ColumnMetadata {
+        is_text,
+        is_metric,
+        is_optional: true,
+    };
+    let mut builtin = vec![
+        (
+            ID_SOURCE.to_string(),
+            ColumnMetadata {
+                is_text: true,
+                is_metric: false,
+                is_optional: false,
+            },
+        ),
+        (ID_CPU_TIME.to_string(), optional(false, true)),
+        (ID_THREADS.to_string(), optional(false, true)),
+        (ID_FDS.to_string(), optional(false, true)),
+        (ID_PAGE_FAULTS.to_string(), optional(false, true)),
+        (ID_STATE.to_string(), optional(true, false)),
+    ];
+    builtin.extend(
+        NET_TRANSPORTS
+            .iter()
+            .map(|(id, _)| (id.to_string(), optional(false, true))),
+    );
+
+    let metadata = settings.columns().column_metadata().get();
+    if builtin.iter().all(|(id, _)| metadata.contains_key(id)) {
+        return Ok(());
+    }
+
+    settings.columns().patch_column_metadata(|metadata| {
+        for (id, meta) in builtin {
+            metadata.entry(id).or_insert(meta);
+        }
+    })
+}
+
 fn sort_nodes_inplace(
     nodes: &mut [TableNode<ProcessEntryVm, SharedString>],
     sort: &SortState<SharedString>,
 ) {
     nodes.sort_by(|a, b| {
+        let pinned = b.vm.pinned_by.is_some().cmp(&a.vm.pinned_by.is_some());
+        if pinned != std::cmp::Ordering::Equal {
+            return pinned;
+        }
+
         if let Some(ref field_id) = sort.field_id {
             let val_a =
                 a.vm.fields
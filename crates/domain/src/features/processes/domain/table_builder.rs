@@ -1,20 +1,73 @@
+use crate::processes_impl::domain::pins::ProcessPins;
 use crate::processes_impl::services::metadata::ProcessMetadataService;
-use app_contracts::features::processes::{ProcessEntryVm, ProcessFieldDto, ProcessNodeDto};
+use app_contracts::features::processes::{
+    ProcessEntryVm, ProcessFieldDto, ProcessKey, ProcessNodeDto,
+};
 use context::caches::icons::IconRequest;
 use slint::SharedString;
-use std::collections::HashSet;
-use widgets::table::flow::{TableDataBuilder, TableNode};
+use std::collections::HashMap;
+use widgets::table::flow::{Expansion, TableDataBuilder, TableNode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessTreeMode {
+    #[default]
+    GroupByName,
+    TreeByParent,
+    Flat,
+}
+
+impl ProcessTreeMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::GroupByName => Self::TreeByParent,
+            Self::TreeByParent => Self::Flat,
+            Self::Flat => Self::GroupByName,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::GroupByName => "group",
+            Self::TreeByParent => "tree",
+            Self::Flat => "flat",
+        }
+    }
+}
 
 pub struct ProcessTreeBuilder<'a> {
     pub metadata: &'a ProcessMetadataService,
+    pub pins: &'a ProcessPins,
+    pub stuck: &'a HashMap<ProcessKey, SharedString>,
     pub grouping_scratchpad: &'a mut Vec<(SharedString, usize)>,
+    pub mode: ProcessTreeMode,
 }
 
 impl<'a> TableDataBuilder<ProcessNodeDto, ProcessEntryVm> for ProcessTreeBuilder<'a> {
     fn build_tree(
         &mut self,
         items: &[ProcessNodeDto],
-        expanded: &HashSet<SharedString>,
+        expanded: Expansion<'_, SharedString>,
+        out: &mut Vec<TableNode<ProcessEntryVm>>,
+    ) {
+        match self.mode {
+            ProcessTreeMode::GroupByName => self.build_name_groups(items, expanded, out),
+            ProcessTreeMode::TreeByParent => self.build_parent_tree(items, expanded, out),
+            ProcessTreeMode::Flat => {
+                out.extend(
+                    items
+                        .iter()
+                        .map(|proc| leaf_node(self.metadata, self.pins, self.stuck, proc)),
+                );
+            }
+        }
+    }
+}
+
+impl<'a> ProcessTreeBuilder<'a> {
+    fn build_name_groups(
+        &mut self,
+        items: &[ProcessNodeDto],
+        expanded: Expansion<'_, SharedString>,
         out: &mut Vec<TableNode<ProcessEntryVm>>,
     ) {
         let buffer: &mut Vec<_> = self.grouping_scratchpad;
@@ -46,8 +99,14 @@ impl<'a> TableDataBuilder<ProcessNodeDto, ProcessEntryVm> for ProcessTreeBuilder
             let has_children = group_procs.len() > 1;
             let is_expanded = expanded.contains(&group_name);
 
-            let mut parent_vm = to_vm(self.metadata, &items[leader_idx], 0);
+            let mut parent_vm = to_vm(self.metadata, self.pins, self.stuck, &items[leader_idx], 0);
             parent_vm.name = group_name.clone();
+            parent_vm.pinned_by = group_procs
+                .iter()
+                .find_map(|(_, idx)| self.pins.pattern_for(&items[*idx]));
+            parent_vm.stuck = group_procs
+                .iter()
+                .find_map(|(_, idx)| self.stuck.get(&items[*idx].key()).cloned());
             parent_vm.has_children = has_children;
             parent_vm.is_expanded = is_expanded;
 
@@ -56,7 +115,7 @@ impl<'a> TableDataBuilder<ProcessNodeDto, ProcessEntryVm> for ProcessTreeBuilder
                 for &(_, idx) in group_procs {
                     if idx != leader_idx {
                         children.push(TableNode {
-                            vm: to_vm(self.metadata, &items[idx], 1),
+                            vm: to_vm(self.metadata, self.pins, self.stuck, &items[idx], 1),
                             group_id: None,
                             has_children: false,
                             is_expanded: false,
@@ -78,11 +137,158 @@ impl<'a> TableDataBuilder<ProcessNodeDto, ProcessEntryVm> for ProcessTreeBuilder
             i = j;
         }
     }
+
+    fn build_parent_tree(
+        &mut self,
+        items: &[ProcessNodeDto],
+        expanded: Expansion<'_, SharedString>,
+        out: &mut Vec<TableNode<ProcessEntryVm>>,
+    ) {
+        let parent_of = parent_links(items);
+
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); items.len()];
+        for (idx, parent) in parent_of.iter().enumerate() {
+            if let Some(parent) = parent {
+                children[*parent].push(idx);
+            }
+        }
+
+        for (idx, parent) in parent_of.iter().enumerate() {
+            if parent.is_none() {
+                out.push(self.parent_tree_node(items, &children, expanded, idx, 0));
+            }
+        }
+    }
+
+    fn parent_tree_node(
+        &self,
+        items: &[ProcessNodeDto],
+        children: &[Vec<usize>],
+        expanded: Expansion<'_, SharedString>,
+        idx: usize,
+        level: u8,
+    ) -> TableNode<ProcessEntryVm> {
+        let proc = &items[idx];
+        let group_id = tree_group_id(proc.key());
+        let has_children = !children[idx].is_empty();
+        let is_expanded = has_children && expanded.contains(&group_id);
+
+        let mut vm = to_vm(self.metadata, self.pins, self.stuck, proc, level as i32);
+        vm.has_children = has_children;
+        vm.is_expanded = is_expanded;
+
+        let nested = if is_expanded {
+            children[idx]
+                .iter()
+                .map(|&child| {
+                    self.parent_tree_node(items, children, expanded, child, level.saturating_add(1))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        TableNode {
+            vm,
+            group_id: Some(group_id),
+            has_children,
+            is_expanded,
+            level,
+            children: nested,
+        }
+    }
 }
 
-fn to_vm(metadata: &ProcessMetadataService, proc: &ProcessNodeDto, depth: i32) -> ProcessEntryVm {
+// Pids repeat across sources, so the group id carries the source too.
+fn tree_group_id(key: ProcessKey) -> SharedString {
+    format!("pid:{}:{}", key.schema_id, key.pid).into()
+}
+
+// Index of each process's parent within `items`.
+fn parent_links(items: &[ProcessNodeDto]) -> Vec<Option<usize>> {
+    let by_key: HashMap<ProcessKey, usize> = items
+        .iter()
+        .enumerate()
+        .map(|(idx, proc)| (proc.key(), idx))
+        .collect();
+
+    // Missing or self-referencing parents make the process a root.
+    let mut parent_of: Vec<Option<usize>> = items
+        .iter()
+        .enumerate()
+        .map(|(idx, proc)| {
+            let parent = ProcessKey {
+                schema_id: proc.schema_id,
+                pid: proc.parent_pid,
+            };
+            by_key.get(&parent).copied().filter(|&parent| parent != idx)
+        })
+        .collect();
+
+    break_parent_cycles(&mut parent_of);
+    parent_of
+}
+
+// Reparented or reused PIDs can produce parent chains that loop back on
+// themselves; cut each loop at the node that closes it so it becomes a root.
+fn break_parent_cycles(parent_of: &mut [Option<usize>]) {
+    const UNVISITED: u8 = 0;
+    const IN_PATH: u8 = 1;
+    const DONE: u8 = 2;
+
+    let mut state = vec![UNVISITED; parent_of.len()];
+    let mut path = Vec::new();
+
+    for start in 0..parent_of.len() {
+        let mut cur = start;
+        path.clear();
+
+        while state[cur] == UNVISITED {
+            state[cur] = IN_PATH;
+            path.push(cur);
+
+            let Some(parent) = parent_of[cur] else {
+                break;
+            };
+            if state[parent] == IN_PATH {
+                parent_of[cur] = None;
+                break;
+            }
+            cur = parent;
+        }
+
+        for &idx in &path {
+            state[idx] = DONE;
+        }
+    }
+}
+
+fn leaf_node(
+    metadata: &ProcessMetadataService,
+    pins: &ProcessPins,
+    stuck: &HashMap<ProcessKey, SharedString>,
+    proc: &ProcessNodeDto,
+) -> TableNode<ProcessEntryVm> {
+    TableNode {
+        vm: to_vm(metadata, pins, stuck, proc, 0),
+        group_id: None,
+        has_children: false,
+        is_expanded: false,
+        level: 0,
+        children: Vec::new(),
+    }
+}
+
+fn to_vm(
+    metadata: &ProcessMetadataService,
+    pins: &ProcessPins,
+    stuck: &HashMap<ProcessKey, SharedString>,
+    proc: &ProcessNodeDto,
+    depth: i32,
+) -> ProcessEntryVm {
     ProcessEntryVm {
         pid: proc.pid as i32,
+        schema_id: proc.schema_id,
         name: metadata.clean_name(&proc.name),
         icon: metadata.icon_by_path(
             IconRequest::builder()
@@ -94,6 +300,9 @@ fn to_vm(metadata: &ProcessMetadataService, proc: &ProcessNodeDto, depth: i32) -
         has_children: false,
         is_expanded: false,
         is_dead: false,
+        is_selected: false,
+        pinned_by: pins.pattern_for(proc),
+        stuck: stuck.get(&proc.key()).cloned(),
         fields: proc
             .fields
             .iter()
@@ -106,3 +315,64 @@ fn to_vm(metadata: &ProcessMetadataService, proc: &ProcessNodeDto, depth: i32) -
             .collect(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc(pid: u32, parent_pid: u32) -> ProcessNodeDto {
+        proc_in("local", pid, parent_pid)
+    }
+
+    fn proc_in(schema_id: &'static str, pid: u32, parent_pid: u32) -> ProcessNodeDto {
+        ProcessNodeDto {
+            pid,
+            schema_id,
+            parent_pid,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn children_link_to_their_parent_by_pid() {
+        let items = [proc(1, 0), proc(2, 1), proc(3, 2), proc(4, 1)];
+        assert_eq!(parent_links(&items), vec![None, Some(0), Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn missing_and_self_parents_become_roots() {
+        let items = [proc(5, 99), proc(6, 6)];
+        assert_eq!(parent_links(&items), vec![None, None]);
+    }
+
+    #[test]
+    fn processes_parenting_each_other_keep_one_link() {
+        let items = [proc(10, 11), proc(11, 10)];
+        assert_eq!(parent_links(&items), vec![Some(1), None]);
+    }
+
+    #[test]
+    fn equal_pids_in_other_sources_are_not_parents() {
+        let items = [
+            proc_in("windows", 1, 0),
+            proc_in("wsl", 2, 1),
+            proc_in("wsl", 1, 0),
+        ];
+        assert_eq!(parent_links(&items), [None, Some(2), None]);
+    }
+
+    #[test]
+    fn break_parent_cycles_cuts_the_node_closing_a_loop() {
+        // 0 -> 1 -> 2 -> 0 loops, 3 hangs off the loop and 4 is a root of its own.
+        let mut parent_of = vec![Some(1), Some(2), Some(0), Some(1), None];
+        break_parent_cycles(&mut parent_of);
+        assert_eq!(parent_of, vec![Some(1), Some(2), None, Some(1), None]);
+    }
+
+    #[test]
+    fn break_parent_cycles_keeps_acyclic_chains() {
+        let mut parent_of = vec![None, Some(0), Some(1), Some(1)];
+        break_parent_cycles(&mut parent_of);
+        assert_eq!(parent_of, vec![None, Some(0), Some(1), Some(1)]);
+    }
+}
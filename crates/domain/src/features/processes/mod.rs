@@ -6,17 +6,25 @@ use crate::features::processes::application::actor::*;
 use crate::features::processes::domain::table::ProcessTable;
 use crate::features::processes::services::metadata::ProcessMetadataService;
 use crate::processes_impl::application::process_snapshot_actor::ProcessSnapshotActor;
+use crate::processes_impl::domain::alerts::AlertEngine;
+use crate::processes_impl::domain::history::MetricHistory;
+use crate::processes_impl::services::control::ProcessCommand;
 use crate::processes_impl::settings::ProcessSettings;
 
 use app_contracts::features::agents::ScanTick;
-use app_contracts::features::processes::{ProcessesBinder, UiProcessesBindings, UiProcessesPort};
+use app_contracts::features::processes::{
+    ProcessesBinder, ProcessesWindowRegister, UiProcessesBindings, UiProcessesPort,
+};
 use app_core::actor::addr::Addr;
 use app_core::actor::event_bus::EventBus;
 use context::page_status::RouteStatusRegistry;
 use framework::feature::{FeatureContextState, WindowFeature, WindowFeatureInitContext};
+use framework::native_windows::slint_factory::SlintWindowRegistry;
 use macros::window_feature;
-use std::collections::HashMap;
+use slint::SharedString;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
+use widgets::table::flow::SelectMode;
 
 mod application;
 mod domain;
@@ -32,13 +40,21 @@ impl<TWindow, F, P> WindowFeature<TWindow> for ProcessFeature<F>
 where
     TWindow: Window,
     F: Fn(&TWindow) -> P + 'static + Clone,
-    P: UiProcessesPort + UiProcessesBindings + Clone + 'static,
+    P: UiProcessesPort + UiProcessesBindings + ProcessesWindowRegister + Clone + 'static,
 {
     fn install(&mut self, ctx: &mut WindowFeatureInitContext<TWindow>) -> anyhow::Result<()> {
         let settings = ProcessSettings::new(ctx.shared)?;
         let ui_port = (self.make_port)(ctx.ui);
         let token = ctx.ui.new_token();
+        let reg = ctx.shared.get::<SlintWindowRegistry>().unwrap();
         let scan_interval_ms = settings.scan_interval_ms();
+        let history = Arc::new(Mutex::new(MetricHistory::new(
+            settings.history_samples().get() as usize,
+        )));
+        let history_sub = settings.history_samples().subscribe({
+            let history = history.clone();
+            move |samples| history.lock().unwrap().set_capacity(samples as usize)
+        });
 
         let process_actor = ProcessActor {
             table: ProcessTable::new(settings.clone())?,
@@ -46,9 +62,19 @@ where
             route_status: ctx.shared.get::<RouteStatusRegistry>().unwrap(),
             is_active: true,
             active_context_key: Cow::Borrowed("host"),
-            is_grouped: false,
             ui_port: ui_port.clone(),
             has_snapshot_data: false,
+            history: history.clone(),
+            terminate_timeout_ms: settings.terminate_timeout_ms(),
+            scope_to_context: settings.scope_to_context(),
+            registry: reg.clone(),
+            details_windows: HashMap::new(),
+            recorder: None,
+            alerts: VecDeque::new(),
+            activity: VecDeque::new(),
+            activity_filter: SharedString::new(),
+            activity_dirty: false,
+            wsl_capabilities: None,
             ctx: FeatureContextState::new(ctx.window_id, "processes.list"),
         };
 
@@ -56,11 +82,19 @@ where
 
         let snapshot_actor = ProcessSnapshotActor {
             snapshots: HashMap::new(),
+            distros: HashMap::new(),
             contexts: HashMap::new(),
+            cache_logged: HashMap::new(),
             target: addr.clone(),
             is_active: true,
             scratch_processes: Arc::new(Mutex::new(Vec::new())),
             scratch_seen: Default::default(),
+            history,
+            _history_sub: history_sub,
+            alert_rules: settings.alert_rules(),
+            alerts: AlertEngine::default(),
+            #[cfg(target_os = "linux")]
+            local_scan: Default::default(),
         };
 
         let _ = Addr::new_managed(snapshot_actor, token, &self.tracker);
@@ -68,11 +102,43 @@ where
         ProcessesBinder::new(&addr, &ui_port)
             .on_sort_by(Sort)
             .on_toggle_expand_group(ToggleExpand)
-            .on_terminate(TerminateSelected)
+            .on_terminate(|tree| TerminateSelected { tree })
+            .on_suspend(RunCommand(ProcessCommand::Suspend))
+            .on_resume(RunCommand(ProcessCommand::Resume))
+            .on_set_priority(|nice| RunCommand(ProcessCommand::SetPriority(nice)))
+            .on_set_affinity(SetAffinity)
+            .on_open_details(|source, pid| OpenDetails {
+                source,
+                pid: pid as u32,
+            })
+            .on_toggle_pin(|source, pid| TogglePin {
+                source,
+                pid: pid as u32,
+            })
+            .on_export(Export)
+            .on_toggle_recording(ToggleRecording)
+            .on_clear_alerts(ClearAlerts)
+            .on_activity_filter_changed(FilterActivity)
             .on_group_clicked(GroupClicked)
-            .on_select_process(|pid, idx| Select {
+            .on_filter_changed(Filter)
+            .on_toggle_scope(ToggleScope)
+            .on_toggle_column(ToggleColumn)
+            .on_move_column(|id, delta| MoveColumn { id, delta })
+            .on_select_process(|source, pid, idx| Select {
+                source,
                 pid: pid as u32,
                 idx: idx as usize,
+                mode: SelectMode::Replace,
+            })
+            .on_extend_selection(|source, pid, idx, range| Select {
+                source,
+                pid: pid as u32,
+                idx: idx as usize,
+                mode: if range {
+                    SelectMode::Range
+                } else {
+                    SelectMode::Toggle
+                },
             })
             .on_column_resized(|id, width| ResizeColumn {
                 id: id.into(),
@@ -83,6 +149,9 @@ where
                 count: count.max(0) as usize,
             });
 
+        ui_port.register(&reg);
+        ui_port.set_is_scoped(settings.scope_to_context().get());
+
         let loop_handle = ctx
             .reactor
             .add_dynamic_loop(scan_interval_ms.as_signal(), || EventBus::publish(ScanTick));
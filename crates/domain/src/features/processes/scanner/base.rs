@@ -31,6 +31,8 @@ pub trait VisitorContext {
 
     fn tick(&self);
 
+    fn rate(&self, pid: u32, counter_id: &'static str, identity: u64, total: u64) -> f64;
+
     fn intern(&self, s: &str) -> SharedString;
 }
 
@@ -41,6 +43,7 @@ pub trait ProcessVisitor {
     fn package_name(&self, ctx: &dyn VisitorContext) -> Option<SharedString>;
     fn parent_pid(&self) -> u32;
     fn exe_path(&self, ctx: &dyn VisitorContext) -> SharedString;
+    fn cmdline(&self, ctx: &dyn VisitorContext) -> SharedString;
     // fn icon_path(&self, ctx: &dyn VisitorContext) -> SharedString;
     fn visit(&self, ctx: &dyn VisitorContext, visitor: &mut dyn FnMut(Field));
 }
@@ -8,10 +8,31 @@ lazy_static! {
     pub static ref ID_MEM: SharedString = "memory".into();
     pub static ref ID_NET: SharedString = "net".into();
     pub static ref ID_DISK: SharedString = "disk".into();
+    pub static ref ID_FDS: SharedString = "fds".into();
+    pub static ref ID_CPU_TIME: SharedString = "cpu_time".into();
+    pub static ref ID_THREADS: SharedString = "threads".into();
+    pub static ref ID_PAGE_FAULTS: SharedString = "page_faults".into();
+    pub static ref ID_STATE: SharedString = "state".into();
+    pub static ref ID_SOURCE: SharedString = "source".into();
     pub static ref LBL_CPU: SharedString = "CPU".into();
     pub static ref LBL_MEM: SharedString = "Memory".into();
     pub static ref LBL_NET: SharedString = "Net".into();
     pub static ref LBL_DISK: SharedString = "Disk".into();
+    pub static ref LBL_FDS: SharedString = "FDs".into();
+    pub static ref LBL_CPU_TIME: SharedString = "CPU time".into();
+    pub static ref LBL_THREADS: SharedString = "Threads".into();
+    pub static ref LBL_PAGE_FAULTS: SharedString = "Faults".into();
+    pub static ref LBL_STATE: SharedString = "State".into();
+    pub static ref LBL_SOURCE: SharedString = "Source".into();
+    pub static ref NET_TRANSPORTS: [(SharedString, SharedString); 7] = [
+        ("net_vsock".into(), "vsock".into()),
+        ("net_9p".into(), "9P".into()),
+        ("net_tcp_lo".into(), "TCP lo".into()),
+        ("net_tcp_remote".into(), "TCP remote".into()),
+        ("net_udp_lo".into(), "UDP lo".into()),
+        ("net_udp_remote".into(), "UDP remote".into()),
+        ("net_uds".into(), "UDS".into()),
+    ];
     pub static ref MEM_DETAIL_CACHE: Mutex<FieldValue> =
         Mutex::new(FieldValue::new(FieldValueKind::U64(0)));
     pub static ref CPU_DETAIL_CACHE: Mutex<FieldValue> =
@@ -6,32 +6,110 @@ use crate::processes_impl::scanner::field_value::{FieldValue, FieldValueKind};
 use context::caches::strings::StringsProvider;
 use dashmap::DashMap;
 use slint::SharedString;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+const MIN_RATE_INTERVAL_SECS: f64 = 0.05;
+
+pub struct CounterSample {
+    total: u64,
+    identity: u64,
+    at: Instant,
+    rate: f64,
+}
+
+impl CounterSample {
+    fn new(total: u64, identity: u64, at: Instant) -> Self {
+        Self {
+            total,
+            identity,
+            at,
+            rate: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CacheStats {
+    pub pids: usize,
+    pub fields: usize,
+    pub counters: usize,
+    pub display_names: usize,
+    pub evicted_pids: usize,
+}
 
 pub struct StatefulContext {
     pub cache: DashMap<(u32, &'static str), FieldValue>,
-    pub display_names: DashMap<String, SharedString>,
+    pub counters: DashMap<(u32, &'static str), CounterSample>,
+    // Display names are keyed by name rather than pid and remember the scan that last used them.
+    pub display_names: DashMap<String, (SharedString, u64)>,
     pub windows_cache: DashMap<u32, String>,
     pub services_cache: DashMap<u32, String>,
+    // Replayed reports carry their recording offset; rates are measured on that clock instead.
+    origin: Instant,
+    recorded_at: Mutex<Option<Duration>>,
+    generation: AtomicU64,
+    last_seen: DashMap<u32, u64>,
 }
 
 impl StatefulContext {
     pub fn new() -> Self {
         Self {
             cache: DashMap::new(),
+            counters: DashMap::new(),
             display_names: DashMap::new(),
             windows_cache: DashMap::new(),
             services_cache: DashMap::new(),
+            origin: Instant::now(),
+            recorded_at: Mutex::new(None),
+            generation: AtomicU64::new(0),
+            last_seen: DashMap::new(),
         }
     }
 
-    #[allow(dead_code)]
-    pub fn clear_dead_processes(&self, active_pids: &[u32]) {
-        self.cache
-            .retain(|(pid, _), _| *pid == 0 || active_pids.contains(pid));
-        self.windows_cache
-            .retain(|pid, _| active_pids.contains(pid));
-        self.services_cache
-            .retain(|pid, _| active_pids.contains(pid));
+    // Set before building a snapshot from a report; `None` for live reports.
+    pub fn set_recorded_at(&self, offset: Option<Duration>) {
+        *self.recorded_at.lock().unwrap() = offset;
+    }
+
+    fn now(&self) -> Instant {
+        match *self.recorded_at.lock().unwrap() {
+            Some(offset) => self.origin + offset,
+            None => Instant::now(),
+        }
+    }
+
+    // Called once per scan with the pids it reported. State of pids missing from `max_missed`
+    // consecutive scans is dropped; pid 0 holds machine-wide counters and is always kept.
+    pub fn sweep(&self, active: impl IntoIterator<Item = u32>, max_missed: u64) -> CacheStats {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed);
+        for pid in active {
+            self.last_seen.insert(pid, generation);
+        }
+
+        let before = self.last_seen.len();
+        self.last_seen
+            .retain(|_, seen| generation - *seen < max_missed);
+        let evicted_pids = before - self.last_seen.len();
+
+        if evicted_pids > 0 {
+            let alive = |pid: &u32| *pid == 0 || self.last_seen.contains_key(pid);
+            self.cache.retain(|(pid, _), _| alive(pid));
+            self.counters.retain(|(pid, _), _| alive(pid));
+            self.windows_cache.retain(|pid, _| alive(pid));
+            self.services_cache.retain(|pid, _| alive(pid));
+        }
+        self.display_names
+            .retain(|_, (_, seen)| generation - *seen < max_missed);
+
+        CacheStats {
+            pids: self.last_seen.len(),
+            fields: self.cache.len(),
+            counters: self.counters.len(),
+            display_names: self.display_names.len(),
+            evicted_pids,
+        }
     }
 
     pub fn refresh_system_metadata(&self) {
@@ -78,8 +156,10 @@ impl VisitorContext for StatefulContext {
         #[cfg(not(windows))]
         let key = req.process_name;
 
-        if let Some(cached) = self.display_names.get(key) {
-            return cached.clone();
+        let generation = self.generation.load(Ordering::Relaxed);
+        if let Some(mut cached) = self.display_names.get_mut(key) {
+            cached.1 = generation;
+            return cached.0.clone();
         }
 
         let display_name = {
@@ -121,7 +201,8 @@ impl VisitorContext for StatefulContext {
         };
 
         let shared = self.intern(&display_name);
-        self.display_names.insert(key.to_string(), shared.clone());
+        self.display_names
+            .insert(key.to_string(), (shared.clone(), generation));
         shared
     }
 
@@ -129,7 +210,86 @@ impl VisitorContext for StatefulContext {
         self.refresh_system_metadata()
     }
 
+    fn rate(&self, pid: u32, counter_id: &'static str, identity: u64, total: u64) -> f64 {
+        let now = self.now();
+        let mut entry = self
+            .counters
+            .entry((pid, counter_id))
+            .or_insert_with(|| CounterSample::new(total, identity, now));
+        let sample = entry.value_mut();
+
+        // Another identity on the same PID means it was reused; a smaller
+        // total means the counter was reset, and time going back that a replay
+        // was rewound. Either way, start over.
+        let Some(elapsed) = now
+            .checked_duration_since(sample.at)
+            .filter(|_| sample.identity == identity && total >= sample.total)
+        else {
+            *sample = CounterSample::new(total, identity, now);
+            return 0.0;
+        };
+
+        let elapsed = elapsed.as_secs_f64();
+        if elapsed < MIN_RATE_INTERVAL_SECS {
+            return sample.rate;
+        }
+
+        sample.rate = (total - sample.total) as f64 / elapsed;
+        sample.total = total;
+        sample.at = now;
+        sample.rate
+    }
+
     fn intern(&self, s: &str) -> SharedString {
         StringsProvider::global().intern(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replayed_rates_follow_recording_offsets() {
+        let ctx = StatefulContext::new();
+        let at = |secs| ctx.set_recorded_at(Some(Duration::from_secs(secs)));
+
+        at(10);
+        assert_eq!(ctx.rate(1, "disk", 1, 1000), 0.0);
+        at(12);
+        assert_eq!(ctx.rate(1, "disk", 1, 3000), 1000.0);
+
+        // A rewind starts the counter over instead of reading a negative interval.
+        at(4);
+        assert_eq!(ctx.rate(1, "disk", 1, 3000), 0.0);
+        at(5);
+        assert_eq!(ctx.rate(1, "disk", 1, 3500), 500.0);
+    }
+
+    #[test]
+    fn sweep_evicts_pids_missing_for_consecutive_scans() {
+        let ctx = StatefulContext::new();
+        let touch = |pid| {
+            ctx.get_field_value(pid, "cpu", FieldValueKind::Percent(0.0));
+            ctx.rate(pid, "disk", 1, 100);
+        };
+
+        touch(0);
+        touch(7);
+        touch(8);
+        assert_eq!(ctx.sweep([7, 8], 2).evicted_pids, 0);
+
+        touch(8);
+        assert_eq!(ctx.sweep([8], 2).evicted_pids, 0);
+        assert!(ctx.cache.contains_key(&(7, "cpu")));
+
+        touch(8);
+        let stats = ctx.sweep([8], 2);
+        assert_eq!(stats.evicted_pids, 1);
+        assert_eq!(stats.pids, 1);
+        assert!(!ctx.cache.contains_key(&(7, "cpu")));
+        assert!(!ctx.counters.contains_key(&(7, "disk")));
+        assert!(ctx.cache.contains_key(&(0, "cpu")));
+        assert!(ctx.cache.contains_key(&(8, "cpu")));
+    }
+}
@@ -16,6 +16,7 @@ pub enum FieldValueKind {
     F32(f32),
     Str(SharedString),
     Bytes(u64),
+    BytesPerSec(u64),
     Percent(f32),
     Duration(std::time::Duration),
 }
@@ -136,10 +137,21 @@ impl FieldValue {
             FieldValueKind::Bytes(b) => {
                 self.format_units_with_params(b, 1024, &["B", "KB", "MB", "GB", "TB"], &[])
             }
+            FieldValueKind::BytesPerSec(b) => self.format_units_with_params(
+                b,
+                1024,
+                &["B/s", "KB/s", "MB/s", "GB/s", "TB/s"],
+                &[],
+            ),
             FieldValueKind::Percent(p) => self.write_raw(|w| write!(w, "{:.1}%", p)),
             FieldValueKind::U64(v) => self.write_raw(|w| write!(w, "{}", v)),
             FieldValueKind::F32(v) => self.write_raw(|w| write!(w, "{:.1}", v)),
-            FieldValueKind::Duration(d) => self.write_raw(|w| write!(w, "{}ms", d.as_millis())),
+            FieldValueKind::Duration(d) => {
+                let secs = d.as_secs();
+                self.write_raw(|w| {
+                    write!(w, "{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+                })
+            }
             FieldValueKind::Str(_) => unreachable!(),
         }
     }
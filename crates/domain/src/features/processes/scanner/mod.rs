@@ -2,4 +2,6 @@ pub mod base;
 pub mod consts;
 pub mod ctx;
 pub mod field_value;
+#[cfg(target_os = "linux")]
+pub mod procfs;
 pub mod visitors;
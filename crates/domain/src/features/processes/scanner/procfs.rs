@@ -0,0 +1,206 @@
+use lazy_static::lazy_static;
+use std::fs;
+use std::path::Path;
+
+lazy_static! {
+    // USER_HZ as the kernel reports it; 100 if sysconf cannot tell.
+    pub static ref CLOCK_TICKS_PER_SEC: f64 = match unsafe { libc::sysconf(libc::_SC_CLK_TCK) } {
+        ticks if ticks > 0 => ticks as f64,
+        _ => 100.0,
+    };
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcfsProcess {
+    pub pid: u32,
+    pub parent_pid: u32,
+    pub name: String,
+    pub exe: String,
+    pub cmdline: String,
+    // Jiffies since boot; together with the pid this tells reused pids apart.
+    pub start_time: u64,
+    pub cpu_ticks: u64,
+    pub rss_kb: u64,
+    pub io_bytes: u64,
+    pub fd_count: u64,
+    // One of the proc(5) state letters, e.g. R, S, D or Z.
+    pub state: char,
+    pub threads: u64,
+    // Minor and major faults together.
+    pub page_faults: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcfsMachine {
+    pub cpu_count: u32,
+    pub cpu_busy_ticks: u64,
+    pub cpu_total_ticks: u64,
+    pub mem_total_kb: u64,
+    pub mem_available_kb: u64,
+    pub disk_io_kb: u64,
+}
+
+pub fn read_processes(root: &Path) -> anyhow::Result<Vec<ProcfsProcess>> {
+    let mut processes = Vec::new();
+
+    for entry in fs::read_dir(root)?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse().ok()) else {
+            continue;
+        };
+        // The process may exit while it is being read.
+        if let Some(process) = read_process(&entry.path(), pid) {
+            processes.push(process);
+        }
+    }
+
+    Ok(processes)
+}
+
+pub fn read_machine(root: &Path) -> anyhow::Result<ProcfsMachine> {
+    let mut machine = ProcfsMachine::default();
+
+    for line in fs::read_to_string(root.join("stat"))?.lines() {
+        let Some(rest) = line.strip_prefix("cpu") else {
+            continue;
+        };
+        if rest.starts_with(' ') {
+            let ticks: Vec<u64> = rest
+                .split_whitespace()
+                .filter_map(|v| v.parse().ok())
+                .collect();
+            // idle and iowait are the 4th and 5th columns; guest time is already part of user.
+            let idle = ticks.iter().skip(3).take(2).sum::<u64>();
+            machine.cpu_total_ticks = ticks.iter().take(8).sum();
+            machine.cpu_busy_ticks = machine.cpu_total_ticks.saturating_sub(idle);
+        } else {
+            machine.cpu_count += 1;
+        }
+    }
+
+    let meminfo = fs::read_to_string(root.join("meminfo"))?;
+    machine.mem_total_kb = kv_value(&meminfo, "MemTotal:").unwrap_or(0);
+    machine.mem_available_kb = kv_value(&meminfo, "MemAvailable:").unwrap_or(0);
+
+    if let Ok(vmstat) = fs::read_to_string(root.join("vmstat")) {
+        let paged_in = kv_value(&vmstat, "pgpgin").unwrap_or(0);
+        let paged_out = kv_value(&vmstat, "pgpgout").unwrap_or(0);
+        machine.disk_io_kb = paged_in.saturating_add(paged_out);
+    }
+
+    Ok(machine)
+}
+
+fn read_process(dir: &Path, pid: u32) -> Option<ProcfsProcess> {
+    let stat = parse_stat(&fs::read_to_string(dir.join("stat")).ok()?)?;
+    let status = fs::read_to_string(dir.join("status")).unwrap_or_default();
+
+    // io and fd are only readable for processes of the same user.
+    let io_bytes = fs::read_to_string(dir.join("io"))
+        .map(|io| {
+            kv_value(&io, "read_bytes:")
+                .unwrap_or(0)
+                .saturating_add(kv_value(&io, "write_bytes:").unwrap_or(0))
+        })
+        .unwrap_or(0);
+    let fd_count = fs::read_dir(dir.join("fd"))
+        .map(|fds| fds.count() as u64)
+        .unwrap_or(0);
+
+    let cmdline = fs::read(dir.join("cmdline"))
+        .map(|raw| {
+            raw.split(|&b| b == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(String::from_utf8_lossy)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+    let exe = fs::read_link(dir.join("exe"))
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    Some(ProcfsProcess {
+        pid,
+        parent_pid: stat.parent_pid,
+        name: stat.name,
+        exe,
+        cmdline,
+        start_time: stat.start_time,
+        cpu_ticks: stat.cpu_ticks,
+        rss_kb: kv_value(&status, "VmRSS:").unwrap_or(0),
+        io_bytes,
+        fd_count,
+        state: stat.state,
+        threads: stat.threads,
+        page_faults: stat.page_faults,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+struct StatLine {
+    name: String,
+    state: char,
+    parent_pid: u32,
+    page_faults: u64,
+    cpu_ticks: u64,
+    threads: u64,
+    start_time: u64,
+}
+
+// The command name is wrapped in parentheses and may itself contain spaces or ')'.
+fn parse_stat(raw: &str) -> Option<StatLine> {
+    let open = raw.find('(')?;
+    let close = raw.rfind(')')?;
+    let name = raw.get(open + 1..close)?.to_string();
+    let fields: Vec<&str> = raw.get(close + 1..)?.split_whitespace().collect();
+
+    // Offsets are relative to the state field, i.e. field 3 in proc(5).
+    let field = |idx: usize| fields.get(idx).and_then(|v| v.parse::<u64>().ok());
+
+    Some(StatLine {
+        name,
+        state: fields.first()?.chars().next()?,
+        parent_pid: field(1)? as u32,
+        page_faults: field(7)?.saturating_add(field(9)?),
+        cpu_ticks: field(11)?.saturating_add(field(12)?),
+        threads: field(17)?,
+        start_time: field(19)?,
+    })
+}
+
+fn kv_value(raw: &str, key: &str) -> Option<u64> {
+    raw.lines()
+        .find_map(|line| line.strip_prefix(key))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|value| value.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stat_with_tricky_names() {
+        let raw = "1234 (tmux: server) S 1 1234 1234 0 -1 4194560 \
+                   512 0 0 0 70 30 0 0 20 0 1 0 98765 10000000 800 \
+                   18446744073709551615 0 0 0 0 0 0 0 0 0 0 0 0 17 3 0 0 0 0 0";
+
+        assert_eq!(
+            parse_stat(raw),
+            Some(StatLine {
+                name: "tmux: server".into(),
+                state: 'S',
+                parent_pid: 1,
+                page_faults: 512,
+                cpu_ticks: 100,
+                threads: 1,
+                start_time: 98765,
+            })
+        );
+        assert_eq!(parse_stat("42 (truncated) R 1"), None);
+        assert_eq!(
+            kv_value("Name:\tbash\nVmRSS:\t  4096 kB\n", "VmRSS:"),
+            Some(4096)
+        );
+    }
+}
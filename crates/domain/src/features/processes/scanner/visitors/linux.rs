@@ -4,9 +4,11 @@ use crate::features::processes::scanner::base::{
 use crate::processes_impl::scanner::base::DisplayNameRequest;
 use crate::processes_impl::scanner::consts::*;
 use crate::processes_impl::scanner::ctx::StatefulContext;
-use crate::processes_impl::scanner::field_value::FieldValueKind;
+use crate::processes_impl::scanner::field_value::{FieldValue, FieldValueKind};
 use slint::SharedString;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::Arc;
+use std::time::Duration;
 use uniproc_protocol::{LinuxMachineStats, LinuxProcessStats as WslProcessStat};
 
 pub struct WslScanResult {
@@ -15,6 +17,60 @@ pub struct WslScanResult {
     pub ctx: Arc<StatefulContext>,
 }
 
+// Process and machine stats are separate protocol types with the same counter names.
+macro_rules! transport_totals {
+    ($stats:expr) => {
+        [
+            $stats.vsock_rx_bytes.saturating_add($stats.vsock_tx_bytes),
+            $stats.p9_rx_bytes.saturating_add($stats.p9_tx_bytes),
+            $stats
+                .tcp_rx_lo_bytes
+                .saturating_add($stats.tcp_tx_lo_bytes),
+            $stats
+                .tcp_rx_remote_bytes
+                .saturating_add($stats.tcp_tx_remote_bytes),
+            $stats
+                .udp_rx_lo_bytes
+                .saturating_add($stats.udp_tx_lo_bytes),
+            $stats
+                .udp_rx_remote_bytes
+                .saturating_add($stats.udp_tx_remote_bytes),
+            $stats.uds_rx_bytes.saturating_add($stats.uds_tx_bytes),
+        ]
+    };
+}
+
+fn visit_transports(
+    ctx: &dyn VisitorContext,
+    pid: u32,
+    identity: u64,
+    totals: [u64; 7],
+    visitor: &mut dyn FnMut(Field),
+) {
+    for ((id, label), total) in NET_TRANSPORTS.iter().zip(totals) {
+        let rate = ctx.rate(pid, id.as_str(), identity, total);
+        let mut value = ctx.get_field_value(pid, id.as_str(), FieldValueKind::BytesPerSec(0));
+        value.kind = FieldValueKind::BytesPerSec(rate as u64);
+        value.to_text();
+        visitor(Field {
+            id: id.clone(),
+            label: label.clone(),
+            value,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: rate as f32,
+            threshold: 0.1,
+        });
+    }
+}
+
+fn process_identity(stat: &WslProcessStat) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    stat.local_pid.hash(&mut hasher);
+    stat.name.hash(&mut hasher);
+    hasher.finish()
+}
+
 impl ProcessVisitor for WslProcessStat {
     fn pid(&self) -> u32 {
         self.global_pid
@@ -41,7 +97,7 @@ impl ProcessVisitor for WslProcessStat {
     }
 
     fn parent_pid(&self) -> u32 {
-        0
+        self.parent_pid
     }
 
     fn exe_path(&self, ctx: &dyn VisitorContext) -> SharedString {
@@ -55,6 +111,11 @@ impl ProcessVisitor for WslProcessStat {
         ctx.intern(s)
     }
 
+    // The report only carries the process name; details are fetched on demand.
+    fn cmdline(&self, _ctx: &dyn VisitorContext) -> SharedString {
+        SharedString::default()
+    }
+
     fn visit(&self, ctx: &dyn VisitorContext, visitor: &mut dyn FnMut(Field)) {
         let pid = self.pid();
 
@@ -84,8 +145,12 @@ impl ProcessVisitor for WslProcessStat {
             threshold: 1.0,
         });
 
-        let mut dr = ctx.get_field_value(pid, "disk", FieldValueKind::Bytes(self.disk_read_bytes));
-        dr.kind = FieldValueKind::Bytes(self.disk_read_bytes + self.disk_write_bytes);
+        let identity = process_identity(self);
+
+        let disk_total = self.disk_read_bytes.saturating_add(self.disk_write_bytes);
+        let disk_rate = ctx.rate(pid, "disk", identity, disk_total);
+        let mut dr = ctx.get_field_value(pid, "disk", FieldValueKind::BytesPerSec(0));
+        dr.kind = FieldValueKind::BytesPerSec(disk_rate as u64);
         dr.to_text();
         visitor(Field {
             id: ID_DISK.clone(),
@@ -93,26 +158,17 @@ impl ProcessVisitor for WslProcessStat {
             value: dr,
             stat_detail: None,
             show_indicator: false,
-            numeric: self.disk_read_bytes as f32,
+            numeric: disk_rate as f32,
             threshold: 0.1,
         });
 
-        let net_total = self.vsock_rx_bytes
-            + self.vsock_tx_bytes
-            + self.p9_rx_bytes
-            + self.p9_tx_bytes
-            + self.tcp_tx_lo_bytes
-            + self.tcp_rx_lo_bytes
-            + self.tcp_tx_remote_bytes
-            + self.tcp_rx_remote_bytes
-            + self.udp_tx_lo_bytes
-            + self.udp_rx_lo_bytes
-            + self.udp_tx_remote_bytes
-            + self.udp_rx_remote_bytes
-            + self.uds_tx_bytes
-            + self.uds_rx_bytes;
-        let mut net = ctx.get_field_value(pid, "net", FieldValueKind::Bytes(net_total));
-        net.kind = FieldValueKind::Bytes(net_total);
+        let transports = transport_totals!(self);
+        let net_total = transports
+            .iter()
+            .fold(0u64, |acc, t| acc.saturating_add(*t));
+        let net_rate = ctx.rate(pid, "net", identity, net_total);
+        let mut net = ctx.get_field_value(pid, "net", FieldValueKind::BytesPerSec(0));
+        net.kind = FieldValueKind::BytesPerSec(net_rate as u64);
         net.to_text();
         visitor(Field {
             id: ID_NET.clone(),
@@ -120,9 +176,78 @@ impl ProcessVisitor for WslProcessStat {
             value: net,
             stat_detail: None,
             show_indicator: false,
-            numeric: net_total as f32,
+            numeric: net_rate as f32,
             threshold: 0.1,
         });
+
+        visit_transports(ctx, pid, identity, transports, visitor);
+
+        let mut fds = ctx.get_field_value(pid, "fds", FieldValueKind::U64(self.fd_count));
+        fds.kind = FieldValueKind::U64(self.fd_count);
+        fds.to_text();
+        visitor(Field {
+            id: ID_FDS.clone(),
+            label: LBL_FDS.clone(),
+            value: fds,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: self.fd_count as f32,
+            threshold: 1000.0,
+        });
+
+        // The agent converts clock ticks itself, the guest's tick rate is not known here.
+        let cpu_time = Duration::from_millis(self.cpu_time_ms);
+        let mut time = ctx.get_field_value(pid, "cpu_time", FieldValueKind::Duration(cpu_time));
+        time.kind = FieldValueKind::Duration(cpu_time);
+        time.to_text();
+        visitor(Field {
+            id: ID_CPU_TIME.clone(),
+            label: LBL_CPU_TIME.clone(),
+            value: time,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: cpu_time.as_secs_f32(),
+            threshold: 3600.0,
+        });
+
+        let mut threads = ctx.get_field_value(pid, "threads", FieldValueKind::U64(self.threads));
+        threads.kind = FieldValueKind::U64(self.threads);
+        threads.to_text();
+        visitor(Field {
+            id: ID_THREADS.clone(),
+            label: LBL_THREADS.clone(),
+            value: threads,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: self.threads as f32,
+            threshold: 100.0,
+        });
+
+        let mut faults =
+            ctx.get_field_value(pid, "page_faults", FieldValueKind::U64(self.page_faults));
+        faults.kind = FieldValueKind::U64(self.page_faults);
+        faults.to_text();
+        visitor(Field {
+            id: ID_PAGE_FAULTS.clone(),
+            label: LBL_PAGE_FAULTS.clone(),
+            value: faults,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: self.page_faults as f32,
+            threshold: 0.0,
+        });
+
+        // The agent sends the state letter as a byte; sorts like the local scanner's column.
+        let state = ctx.intern((self.state as char).encode_utf8(&mut [0; 4]));
+        visitor(Field {
+            id: ID_STATE.clone(),
+            label: LBL_STATE.clone(),
+            value: FieldValue::new(FieldValueKind::Str(state)),
+            stat_detail: None,
+            show_indicator: false,
+            numeric: self.state as f32,
+            threshold: 0.0,
+        });
     }
 }
 
@@ -155,51 +280,145 @@ impl ScanResult for WslScanResult {
             threshold: 1.0,
         });
 
-        let net_total = self.machine.vsock_rx_bytes
-            + self.machine.vsock_tx_bytes
-            + self.machine.p9_rx_bytes
-            + self.machine.p9_tx_bytes
-            + self.machine.tcp_tx_lo_bytes
-            + self.machine.tcp_rx_lo_bytes
-            + self.machine.tcp_tx_remote_bytes
-            + self.machine.tcp_rx_remote_bytes
-            + self.machine.udp_tx_lo_bytes
-            + self.machine.udp_rx_lo_bytes
-            + self.machine.udp_tx_remote_bytes
-            + self.machine.udp_rx_remote_bytes
-            + self.machine.uds_tx_bytes
-            + self.machine.uds_rx_bytes;
+        let transports = transport_totals!(self.machine);
+        let net_total = transports
+            .iter()
+            .fold(0u64, |acc, t| acc.saturating_add(*t));
+        let net_rate = self.ctx.rate(0, "m_net", 0, net_total);
+
+        let mut transport_fields = Vec::with_capacity(NET_TRANSPORTS.len());
+        visit_transports(self.ctx.as_ref(), 0, 0, transports, &mut |field| {
+            transport_fields.push(field)
+        });
+
         let mut net = self
             .ctx
-            .get_field_value(0, "m_net", FieldValueKind::Bytes(net_total));
-        net.kind = FieldValueKind::Bytes(net_total);
+            .get_field_value(0, "m_net", FieldValueKind::BytesPerSec(0));
+        net.kind = FieldValueKind::BytesPerSec(net_rate as u64);
         net.to_text();
         visitor(Field {
             id: ID_NET.clone(),
             label: LBL_NET.clone(),
             value: net,
-            stat_detail: None,
+            stat_detail: transport_breakdown(&mut transport_fields),
             show_indicator: false,
-            numeric: net_total as f32,
+            numeric: net_rate as f32,
             threshold: 0.1,
         });
 
-        let mut dr = self.ctx.get_field_value(
-            0,
-            "m_disk",
-            FieldValueKind::Bytes(self.machine.disk_read_bytes),
-        );
-        dr.kind =
-            FieldValueKind::Bytes(self.machine.disk_read_bytes + self.machine.disk_write_bytes);
+        let disk_total = self
+            .machine
+            .disk_read_bytes
+            .saturating_add(self.machine.disk_write_bytes);
+        let disk_rate = self.ctx.rate(0, "m_disk", 0, disk_total);
+        let mut dr = self
+            .ctx
+            .get_field_value(0, "m_disk", FieldValueKind::BytesPerSec(0));
+        dr.kind = FieldValueKind::BytesPerSec(disk_rate as u64);
         dr.to_text();
         visitor(Field {
             id: ID_DISK.clone(),
-            label: ID_DISK.clone(),
+            label: LBL_DISK.clone(),
             value: dr,
             stat_detail: None,
             show_indicator: false,
-            numeric: self.machine.disk_read_bytes as f32,
+            numeric: disk_rate as f32,
             threshold: 0.1,
         });
+
+        for field in transport_fields {
+            visitor(field);
+        }
+
+        let open_fds: u64 = self.processes.iter().map(|p| p.fd_count).sum();
+        let mut fds = self
+            .ctx
+            .get_field_value(0, "m_fds", FieldValueKind::U64(open_fds));
+        fds.kind = FieldValueKind::U64(open_fds);
+        fds.to_text();
+        visitor(Field {
+            id: ID_FDS.clone(),
+            label: LBL_FDS.clone(),
+            value: fds,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: open_fds as f32,
+            threshold: 0.0,
+        });
+
+        let cpu_time = Duration::from_millis(self.processes.iter().map(|p| p.cpu_time_ms).sum());
+        let mut time =
+            self.ctx
+                .get_field_value(0, "m_cpu_time", FieldValueKind::Duration(cpu_time));
+        time.kind = FieldValueKind::Duration(cpu_time);
+        time.to_text();
+        visitor(Field {
+            id: ID_CPU_TIME.clone(),
+            label: LBL_CPU_TIME.clone(),
+            value: time,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: cpu_time.as_secs_f32(),
+            threshold: 0.0,
+        });
+
+        let total_threads: u64 = self.processes.iter().map(|p| p.threads).sum();
+        let mut threads =
+            self.ctx
+                .get_field_value(0, "m_threads", FieldValueKind::U64(total_threads));
+        threads.kind = FieldValueKind::U64(total_threads);
+        threads.to_text();
+        visitor(Field {
+            id: ID_THREADS.clone(),
+            label: LBL_THREADS.clone(),
+            value: threads,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: total_threads as f32,
+            threshold: 0.0,
+        });
+
+        let total_faults: u64 = self.processes.iter().map(|p| p.page_faults).sum();
+        let mut faults =
+            self.ctx
+                .get_field_value(0, "m_page_faults", FieldValueKind::U64(total_faults));
+        faults.kind = FieldValueKind::U64(total_faults);
+        faults.to_text();
+        visitor(Field {
+            id: ID_PAGE_FAULTS.clone(),
+            label: LBL_PAGE_FAULTS.clone(),
+            value: faults,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: total_faults as f32,
+            threshold: 0.0,
+        });
+
+        visitor(Field {
+            id: ID_STATE.clone(),
+            label: LBL_STATE.clone(),
+            value: FieldValue::new(FieldValueKind::Str(SharedString::default())),
+            stat_detail: None,
+            show_indicator: false,
+            numeric: 0.0,
+            threshold: 0.0,
+        });
     }
 }
+
+fn transport_breakdown(fields: &mut [Field]) -> Option<SharedString> {
+    let mut order: Vec<usize> = (0..fields.len())
+        .filter(|&i| fields[i].numeric >= 1.0)
+        .collect();
+    order.sort_by(|&a, &b| fields[b].numeric.total_cmp(&fields[a].numeric));
+
+    let parts: Vec<String> = order
+        .into_iter()
+        .map(|i| {
+            let field = &mut fields[i];
+            format!("{} {}", field.label, field.value.to_text())
+        })
+        .collect();
+
+    (!parts.is_empty()).then(|| parts.join(", ").into())
+}
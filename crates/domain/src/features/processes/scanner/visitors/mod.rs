@@ -1,4 +1,6 @@
 // #[cfg(not(target_os = "windows"))]
 pub mod linux;
+#[cfg(target_os = "linux")]
+pub mod procfs;
 #[cfg(target_os = "windows")]
 pub mod windows;
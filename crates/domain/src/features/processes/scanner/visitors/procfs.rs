@@ -0,0 +1,326 @@
+use crate::features::processes::scanner::base::{
+    Field, ProcessVisitor, ScanResult, VisitorContext,
+};
+use crate::processes_impl::scanner::base::DisplayNameRequest;
+use crate::processes_impl::scanner::consts::*;
+use crate::processes_impl::scanner::ctx::StatefulContext;
+use crate::processes_impl::scanner::field_value::{FieldValue, FieldValueKind};
+use crate::processes_impl::scanner::procfs::{CLOCK_TICKS_PER_SEC, ProcfsMachine, ProcfsProcess};
+use slint::SharedString;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct ProcfsScanResult {
+    pub processes: Vec<ProcfsProcess>,
+    pub machine: ProcfsMachine,
+    pub ctx: Arc<StatefulContext>,
+}
+
+// Binds a process to the machine it was read on, so the per-process CPU share can be computed.
+struct LocalProcess<'a> {
+    stat: &'a ProcfsProcess,
+    cpu_count: u32,
+}
+
+impl ProcessVisitor for LocalProcess<'_> {
+    fn pid(&self) -> u32 {
+        self.stat.pid
+    }
+
+    fn name(&self, ctx: &dyn VisitorContext) -> SharedString {
+        ctx.resolve_display_name(
+            DisplayNameRequest::builder()
+                .pid(self.stat.pid)
+                .process_name(&self.stat.name)
+                .exe_path(&self.stat.exe)
+                .build(),
+        )
+    }
+
+    fn parent_pid(&self) -> u32 {
+        self.stat.parent_pid
+    }
+
+    fn exe_path(&self, ctx: &dyn VisitorContext) -> SharedString {
+        ctx.intern(&self.stat.exe)
+    }
+
+    fn cmdline(&self, ctx: &dyn VisitorContext) -> SharedString {
+        ctx.intern(&self.stat.cmdline)
+    }
+
+    fn visit(&self, ctx: &dyn VisitorContext, visitor: &mut dyn FnMut(Field)) {
+        let pid = self.pid();
+        let identity = self.stat.start_time;
+
+        let ticks_per_sec = ctx.rate(pid, "cpu_ticks", identity, self.stat.cpu_ticks);
+        let cpu_percent =
+            (ticks_per_sec / *CLOCK_TICKS_PER_SEC * 100.0 / self.cpu_count.max(1) as f64) as f32;
+        let mut cpu = ctx.get_field_value(pid, "cpu", FieldValueKind::Percent(cpu_percent));
+        cpu.kind = FieldValueKind::Percent(cpu_percent);
+        cpu.to_text();
+        visitor(Field {
+            id: ID_CPU.clone(),
+            label: LBL_CPU.clone(),
+            value: cpu,
+            stat_detail: None,
+            show_indicator: true,
+            numeric: cpu_percent,
+            threshold: 50.0,
+        });
+
+        let rss = self.stat.rss_kb * 1024;
+        let mut mem = ctx.get_field_value(pid, "mem", FieldValueKind::Bytes(rss));
+        mem.kind = FieldValueKind::Bytes(rss);
+        mem.to_text();
+        visitor(Field {
+            id: ID_MEM.clone(),
+            label: LBL_MEM.clone(),
+            value: mem,
+            stat_detail: None,
+            show_indicator: true,
+            numeric: self.stat.rss_kb as f32 / (1024.0 * 1024.0),
+            threshold: 1.0,
+        });
+
+        let disk_rate = ctx.rate(pid, "disk", identity, self.stat.io_bytes);
+        let mut dr = ctx.get_field_value(pid, "disk", FieldValueKind::BytesPerSec(0));
+        dr.kind = FieldValueKind::BytesPerSec(disk_rate as u64);
+        dr.to_text();
+        visitor(Field {
+            id: ID_DISK.clone(),
+            label: LBL_DISK.clone(),
+            value: dr,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: disk_rate as f32,
+            threshold: 0.1,
+        });
+
+        let mut fds = ctx.get_field_value(pid, "fds", FieldValueKind::U64(self.stat.fd_count));
+        fds.kind = FieldValueKind::U64(self.stat.fd_count);
+        fds.to_text();
+        visitor(Field {
+            id: ID_FDS.clone(),
+            label: LBL_FDS.clone(),
+            value: fds,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: self.stat.fd_count as f32,
+            threshold: 1000.0,
+        });
+
+        let cpu_secs = self.stat.cpu_ticks as f64 / *CLOCK_TICKS_PER_SEC;
+        let cpu_time = Duration::from_secs_f64(cpu_secs);
+        let mut time = ctx.get_field_value(pid, "cpu_time", FieldValueKind::Duration(cpu_time));
+        time.kind = FieldValueKind::Duration(cpu_time);
+        time.to_text();
+        visitor(Field {
+            id: ID_CPU_TIME.clone(),
+            label: LBL_CPU_TIME.clone(),
+            value: time,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: cpu_secs as f32,
+            threshold: 3600.0,
+        });
+
+        let mut threads =
+            ctx.get_field_value(pid, "threads", FieldValueKind::U64(self.stat.threads));
+        threads.kind = FieldValueKind::U64(self.stat.threads);
+        threads.to_text();
+        visitor(Field {
+            id: ID_THREADS.clone(),
+            label: LBL_THREADS.clone(),
+            value: threads,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: self.stat.threads as f32,
+            threshold: 100.0,
+        });
+
+        let mut faults = ctx.get_field_value(
+            pid,
+            "page_faults",
+            FieldValueKind::U64(self.stat.page_faults),
+        );
+        faults.kind = FieldValueKind::U64(self.stat.page_faults);
+        faults.to_text();
+        visitor(Field {
+            id: ID_PAGE_FAULTS.clone(),
+            label: LBL_PAGE_FAULTS.clone(),
+            value: faults,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: self.stat.page_faults as f32,
+            threshold: 0.0,
+        });
+
+        // Sorts by the state letter, which keeps D, R, S and Z rows together.
+        let state = ctx.intern(self.stat.state.encode_utf8(&mut [0; 4]));
+        visitor(Field {
+            id: ID_STATE.clone(),
+            label: LBL_STATE.clone(),
+            value: FieldValue::new(FieldValueKind::Str(state)),
+            stat_detail: None,
+            show_indicator: false,
+            numeric: self.stat.state as u32 as f32,
+            threshold: 0.0,
+        });
+    }
+}
+
+impl ScanResult for ProcfsScanResult {
+    fn context(&self) -> &dyn VisitorContext {
+        self.ctx.as_ref()
+    }
+
+    fn visit_processes(&self, visitor: &mut dyn FnMut(&dyn ProcessVisitor)) {
+        for stat in &self.processes {
+            visitor(&LocalProcess {
+                stat,
+                cpu_count: self.machine.cpu_count,
+            });
+        }
+    }
+
+    fn visit_stats(&self, visitor: &mut dyn FnMut(Field)) {
+        let busy = self
+            .ctx
+            .rate(0, "m_cpu_busy", 0, self.machine.cpu_busy_ticks);
+        let total = self
+            .ctx
+            .rate(0, "m_cpu_total", 0, self.machine.cpu_total_ticks);
+        let cpu_pct = if total > 0.0 {
+            (busy / total * 100.0) as f32
+        } else {
+            0.0
+        };
+        let mut cpu = self
+            .ctx
+            .get_field_value(0, "m_cpu", FieldValueKind::Percent(cpu_pct));
+        cpu.kind = FieldValueKind::Percent(cpu_pct);
+        cpu.to_text();
+        visitor(Field {
+            id: ID_CPU.clone(),
+            label: LBL_CPU.clone(),
+            value: cpu,
+            stat_detail: None,
+            show_indicator: true,
+            numeric: cpu_pct,
+            threshold: 50.0,
+        });
+
+        let mem_total = self.machine.mem_total_kb.max(1);
+        let used = mem_total.saturating_sub(self.machine.mem_available_kb);
+        let ram_pct = (used as f32 / mem_total as f32) * 100.0;
+        let mut mem = self
+            .ctx
+            .get_field_value(0, "m_mem", FieldValueKind::Percent(ram_pct));
+        mem.kind = FieldValueKind::Percent(ram_pct);
+        mem.to_text();
+        visitor(Field {
+            id: ID_MEM.clone(),
+            label: LBL_MEM.clone(),
+            value: mem,
+            stat_detail: None,
+            show_indicator: true,
+            numeric: ram_pct,
+            threshold: 1.0,
+        });
+
+        let disk_rate = self
+            .ctx
+            .rate(0, "m_disk", 0, self.machine.disk_io_kb.saturating_mul(1024));
+        let mut dr = self
+            .ctx
+            .get_field_value(0, "m_disk", FieldValueKind::BytesPerSec(0));
+        dr.kind = FieldValueKind::BytesPerSec(disk_rate as u64);
+        dr.to_text();
+        visitor(Field {
+            id: ID_DISK.clone(),
+            label: LBL_DISK.clone(),
+            value: dr,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: disk_rate as f32,
+            threshold: 0.1,
+        });
+
+        let open_fds: u64 = self.processes.iter().map(|p| p.fd_count).sum();
+        let mut fds = self
+            .ctx
+            .get_field_value(0, "m_fds", FieldValueKind::U64(open_fds));
+        fds.kind = FieldValueKind::U64(open_fds);
+        fds.to_text();
+        visitor(Field {
+            id: ID_FDS.clone(),
+            label: LBL_FDS.clone(),
+            value: fds,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: open_fds as f32,
+            threshold: 0.0,
+        });
+
+        let cpu_secs =
+            self.processes.iter().map(|p| p.cpu_ticks).sum::<u64>() as f64 / *CLOCK_TICKS_PER_SEC;
+        let cpu_time = Duration::from_secs_f64(cpu_secs);
+        let mut time =
+            self.ctx
+                .get_field_value(0, "m_cpu_time", FieldValueKind::Duration(cpu_time));
+        time.kind = FieldValueKind::Duration(cpu_time);
+        time.to_text();
+        visitor(Field {
+            id: ID_CPU_TIME.clone(),
+            label: LBL_CPU_TIME.clone(),
+            value: time,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: cpu_secs as f32,
+            threshold: 0.0,
+        });
+
+        let total_threads: u64 = self.processes.iter().map(|p| p.threads).sum();
+        let mut threads =
+            self.ctx
+                .get_field_value(0, "m_threads", FieldValueKind::U64(total_threads));
+        threads.kind = FieldValueKind::U64(total_threads);
+        threads.to_text();
+        visitor(Field {
+            id: ID_THREADS.clone(),
+            label: LBL_THREADS.clone(),
+            value: threads,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: total_threads as f32,
+            threshold: 0.0,
+        });
+
+        let total_faults: u64 = self.processes.iter().map(|p| p.page_faults).sum();
+        let mut faults =
+            self.ctx
+                .get_field_value(0, "m_page_faults", FieldValueKind::U64(total_faults));
+        faults.kind = FieldValueKind::U64(total_faults);
+        faults.to_text();
+        visitor(Field {
+            id: ID_PAGE_FAULTS.clone(),
+            label: LBL_PAGE_FAULTS.clone(),
+            value: faults,
+            stat_detail: None,
+            show_indicator: false,
+            numeric: total_faults as f32,
+            threshold: 0.0,
+        });
+
+        visitor(Field {
+            id: ID_STATE.clone(),
+            label: LBL_STATE.clone(),
+            value: FieldValue::new(FieldValueKind::Str(SharedString::default())),
+            stat_detail: None,
+            show_indicator: false,
+            numeric: 0.0,
+            threshold: 0.0,
+        });
+    }
+}
@@ -46,6 +46,10 @@ impl ProcessVisitor for WindowsProcessStats {
         ctx.intern(&self.cmdline[0])
     }
 
+    fn cmdline(&self, ctx: &dyn VisitorContext) -> SharedString {
+        ctx.intern(&self.cmdline.join(" "))
+    }
+
     fn visit(&self, ctx: &dyn VisitorContext, visitor: &mut dyn FnMut(Field)) {
         let pid = self.pid();
 
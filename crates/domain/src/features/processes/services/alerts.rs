@@ -0,0 +1,27 @@
+use anyhow::bail;
+use app_contracts::features::processes::AlertRaised;
+use std::process::Command;
+
+// Runs a rule's command through the platform shell; the alert is passed in environment variables.
+pub async fn run_command(command: &str, alert: &AlertRaised) -> anyhow::Result<()> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    cmd.arg(command)
+        .env("UNIPROC_ALERT_RULE", alert.rule.as_str())
+        .env("UNIPROC_ALERT_PID", alert.pid.to_string())
+        .env("UNIPROC_ALERT_PROCESS", alert.process_name.as_str())
+        .env("UNIPROC_ALERT_MESSAGE", alert.message.as_str());
+
+    let status = tokio::task::spawn_blocking(move || cmd.status()).await??;
+    if !status.success() {
+        bail!("alert command exited with {status}");
+    }
+    Ok(())
+}
@@ -0,0 +1,411 @@
+use anyhow::bail;
+use app_contracts::features::agents::LinuxActionRequest;
+use app_core::actor::event_bus::{AsyncBus, EventBus, RpcRequest};
+use slint::SharedString;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessStatus, ProcessesToUpdate, System};
+use uniproc_protocol::{LinuxRequest, LinuxResponse};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const RPC_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(target_os = "linux")]
+use libc::{SIGCONT, SIGKILL, SIGSTOP, SIGTERM};
+
+// Signals go to a Linux agent, so hosts without Linux signal numbers spell them out.
+#[cfg(not(target_os = "linux"))]
+const SIGKILL: i32 = 9;
+#[cfg(not(target_os = "linux"))]
+const SIGTERM: i32 = 15;
+#[cfg(not(target_os = "linux"))]
+const SIGCONT: i32 = 18;
+#[cfg(not(target_os = "linux"))]
+const SIGSTOP: i32 = 19;
+
+#[derive(Debug, Clone)]
+pub struct ProcessTarget {
+    pub pid: u32,
+    pub name: SharedString,
+    pub schema_id: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminateOutcome {
+    Exited,
+    Killed,
+}
+
+impl TerminateOutcome {
+    pub fn action(self) -> &'static str {
+        match self {
+            Self::Exited => "Terminate",
+            Self::Killed => "Force kill",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessCommand {
+    Suspend,
+    Resume,
+    // Unix nice value, mapped onto priority classes on Windows.
+    SetPriority(i32),
+    SetAffinity(u64),
+}
+
+impl ProcessCommand {
+    pub fn action(self) -> &'static str {
+        match self {
+            Self::Suspend => "Suspend",
+            Self::Resume => "Resume",
+            Self::SetPriority(_) => "Set priority",
+            Self::SetAffinity(_) => "Set affinity",
+        }
+    }
+}
+
+pub(crate) fn is_remote(schema_id: &str) -> bool {
+    matches!(schema_id, "wsl" | "linux")
+}
+
+// Asks the process to exit, waits up to `timeout` and force-kills it if it is still alive.
+pub async fn terminate(
+    target: &ProcessTarget,
+    timeout: Duration,
+) -> anyhow::Result<TerminateOutcome> {
+    if !is_alive(target).await? {
+        bail!("process not found");
+    }
+
+    if request_exit(target).await? {
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if !is_alive(target).await? {
+                return Ok(TerminateOutcome::Exited);
+            }
+        }
+    }
+
+    force_kill(target).await?;
+    Ok(TerminateOutcome::Killed)
+}
+
+pub async fn execute(target: &ProcessTarget, command: ProcessCommand) -> anyhow::Result<()> {
+    if is_remote(target.schema_id) {
+        let request = match command {
+            ProcessCommand::Suspend => signal_request(target, SIGSTOP),
+            ProcessCommand::Resume => signal_request(target, SIGCONT),
+            ProcessCommand::SetPriority(nice) => LinuxRequest::SetPriority {
+                pid: target.pid,
+                nice,
+            },
+            ProcessCommand::SetAffinity(cpu_mask) => LinuxRequest::SetAffinity {
+                pid: target.pid,
+                cpu_mask,
+            },
+        };
+        return expect_ok(send_request(target, request).await?);
+    }
+
+    match command {
+        ProcessCommand::Suspend => host::set_suspended(target.pid, true),
+        ProcessCommand::Resume => host::set_suspended(target.pid, false),
+        ProcessCommand::SetPriority(nice) => host::set_priority(target.pid, nice),
+        ProcessCommand::SetAffinity(mask) => host::set_affinity(target.pid, mask),
+    }
+}
+
+// Parses CPU lists such as "0-3,6" into an affinity mask.
+pub fn parse_cpu_list(input: &str) -> anyhow::Result<u64> {
+    let mut mask = 0u64;
+
+    for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (first.trim().parse::<u32>()?, last.trim().parse::<u32>()?),
+            None => {
+                let cpu = part.parse::<u32>()?;
+                (cpu, cpu)
+            }
+        };
+
+        if first > last || last >= u64::BITS {
+            bail!("invalid CPU range: {part}");
+        }
+        for cpu in first..=last {
+            mask |= 1 << cpu;
+        }
+    }
+
+    if mask == 0 {
+        bail!("empty CPU list");
+    }
+    Ok(mask)
+}
+
+async fn is_alive(target: &ProcessTarget) -> anyhow::Result<bool> {
+    // A process that exited but was not reaped yet lingers as a zombie, which a signal probe
+    // would still reach.
+    if is_remote(target.schema_id) {
+        let request = LinuxRequest::GetProcessDetails { pid: target.pid };
+        return match send_request(target, request).await? {
+            LinuxResponse::ProcessDetails(details) => Ok(details.threads.is_empty()
+                || details.threads.iter().any(|t| !t.state.starts_with('Z'))),
+            LinuxResponse::Error(_) => Ok(false),
+            _ => bail!("unexpected response to a details request"),
+        };
+    }
+
+    Ok(with_host_process(target.pid, |process| {
+        process.status() != ProcessStatus::Zombie
+    })
+    .unwrap_or(false))
+}
+
+// Returns false when there was nothing to deliver a polite request to.
+async fn request_exit(target: &ProcessTarget) -> anyhow::Result<bool> {
+    if is_remote(target.schema_id) {
+        expect_ok(send_request(target, signal_request(target, SIGTERM)).await?)?;
+        return Ok(true);
+    }
+
+    Ok(host::request_exit(target.pid))
+}
+
+async fn force_kill(target: &ProcessTarget) -> anyhow::Result<()> {
+    if is_remote(target.schema_id) {
+        return expect_ok(send_request(target, signal_request(target, SIGKILL)).await?);
+    }
+
+    match with_host_process(target.pid, |process| process.kill()) {
+        Some(true) => Ok(()),
+        Some(false) => bail!("kill failed"),
+        // Exited between the last poll and the kill.
+        None => Ok(()),
+    }
+}
+
+fn signal_request(target: &ProcessTarget, signal: i32) -> LinuxRequest {
+    LinuxRequest::Signal {
+        pid: target.pid,
+        signal,
+    }
+}
+
+fn expect_ok(response: LinuxResponse) -> anyhow::Result<()> {
+    match response {
+        LinuxResponse::Error(e) => bail!(e),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) async fn send_request(
+    target: &ProcessTarget,
+    request: LinuxRequest,
+) -> anyhow::Result<LinuxResponse> {
+    if !EventBus::has_subscribers::<RpcRequest<LinuxActionRequest>>() {
+        bail!("no agent available for {}", target.schema_id);
+    }
+
+    let request = LinuxActionRequest::new(target.schema_id, request);
+    let response = AsyncBus::request(request, RPC_TIMEOUT).await?;
+    response.decode_response().map_err(anyhow::Error::msg)
+}
+
+fn with_host_process<R>(pid: u32, f: impl FnOnce(&sysinfo::Process) -> R) -> Option<R> {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    system.process(pid).map(f)
+}
+
+#[cfg(windows)]
+mod host {
+    use anyhow::Context;
+    use windows::Win32::Foundation::{CloseHandle, HANDLE, HWND, LPARAM, WPARAM};
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, TH32CS_SNAPTHREAD, THREADENTRY32, Thread32First, Thread32Next,
+    };
+    use windows::Win32::System::Threading::{
+        ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS,
+        IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, OpenProcess, OpenThread,
+        PROCESS_SET_INFORMATION, ResumeThread, SetPriorityClass, SetProcessAffinityMask,
+        SuspendThread, THREAD_SUSPEND_RESUME,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindowThreadProcessId, PostMessageW, WM_CLOSE,
+    };
+    use windows::core::BOOL;
+
+    struct CloseCtx {
+        pid: u32,
+        posted: bool,
+    }
+
+    unsafe extern "system" fn close_windows_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        unsafe {
+            let ctx = &mut *(lparam.0 as *mut CloseCtx);
+            let mut pid = 0;
+            GetWindowThreadProcessId(hwnd, Some(&mut pid));
+
+            if pid == ctx.pid && PostMessageW(Some(hwnd), WM_CLOSE, WPARAM(0), LPARAM(0)).is_ok() {
+                ctx.posted = true;
+            }
+            BOOL::from(true)
+        }
+    }
+
+    pub fn request_exit(pid: u32) -> bool {
+        let mut ctx = CloseCtx { pid, posted: false };
+        unsafe {
+            let _ = EnumWindows(
+                Some(close_windows_proc),
+                LPARAM(&mut ctx as *mut _ as isize),
+            );
+        }
+        ctx.posted
+    }
+
+    // Windows has no process-wide stop signal, so every thread is suspended individually.
+    pub fn set_suspended(pid: u32, suspended: bool) -> anyhow::Result<()> {
+        unsafe {
+            let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0)?;
+            let mut entry = THREADENTRY32 {
+                dwSize: size_of::<THREADENTRY32>() as u32,
+                ..Default::default()
+            };
+
+            let mut touched = 0;
+            let mut next = Thread32First(snapshot, &mut entry);
+            while next.is_ok() {
+                if entry.th32OwnerProcessID == pid
+                    && let Ok(thread) = OpenThread(THREAD_SUSPEND_RESUME, false, entry.th32ThreadID)
+                {
+                    let previous = if suspended {
+                        SuspendThread(thread)
+                    } else {
+                        ResumeThread(thread)
+                    };
+                    if previous != u32::MAX {
+                        touched += 1;
+                    }
+                    let _ = CloseHandle(thread);
+                }
+                next = Thread32Next(snapshot, &mut entry);
+            }
+            let _ = CloseHandle(snapshot);
+
+            if touched == 0 {
+                anyhow::bail!("no threads could be accessed");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_priority(pid: u32, nice: i32) -> anyhow::Result<()> {
+        let class = match nice {
+            ..=-10 => HIGH_PRIORITY_CLASS,
+            -9..=-1 => ABOVE_NORMAL_PRIORITY_CLASS,
+            0 => NORMAL_PRIORITY_CLASS,
+            1..=9 => BELOW_NORMAL_PRIORITY_CLASS,
+            _ => IDLE_PRIORITY_CLASS,
+        };
+        with_process(pid, |handle| unsafe { SetPriorityClass(handle, class) })
+    }
+
+    pub fn set_affinity(pid: u32, mask: u64) -> anyhow::Result<()> {
+        with_process(pid, |handle| unsafe {
+            SetProcessAffinityMask(handle, mask as usize)
+        })
+    }
+
+    fn with_process(
+        pid: u32,
+        f: impl FnOnce(HANDLE) -> windows::core::Result<()>,
+    ) -> anyhow::Result<()> {
+        unsafe {
+            let handle = OpenProcess(PROCESS_SET_INFORMATION, false, pid)
+                .context("failed to open process")?;
+            let result = f(handle);
+            let _ = CloseHandle(handle);
+            Ok(result?)
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod host {
+    use super::with_host_process;
+    use anyhow::{Context, bail};
+    use std::io;
+    use sysinfo::Signal;
+
+    pub fn request_exit(pid: u32) -> bool {
+        with_host_process(pid, |process| {
+            process.kill_with(Signal::Term).unwrap_or(false)
+        })
+        .unwrap_or(false)
+    }
+
+    pub fn set_suspended(pid: u32, suspended: bool) -> anyhow::Result<()> {
+        let signal = if suspended {
+            Signal::Stop
+        } else {
+            Signal::Continue
+        };
+        match with_host_process(pid, |process| process.kill_with(signal)) {
+            Some(Some(true)) => Ok(()),
+            Some(_) => bail!("signal failed"),
+            None => bail!("process not found"),
+        }
+    }
+
+    pub fn set_priority(pid: u32, nice: i32) -> anyhow::Result<()> {
+        let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) };
+        if result != 0 {
+            return Err(io::Error::last_os_error()).context("setpriority failed");
+        }
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set_affinity(pid: u32, mask: u64) -> anyhow::Result<()> {
+        if mask == 0 {
+            bail!("no CPUs selected");
+        }
+
+        let result = unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            for cpu in (0..u64::BITS as usize).filter(|cpu| mask & (1 << cpu) != 0) {
+                libc::CPU_SET(cpu, &mut set);
+            }
+            libc::sched_setaffinity(pid as libc::pid_t, size_of::<libc::cpu_set_t>(), &set)
+        };
+        if result != 0 {
+            return Err(io::Error::last_os_error()).context("sched_setaffinity failed");
+        }
+        Ok(())
+    }
+
+    // Only Linux lets one process pin another to CPUs.
+    #[cfg(not(target_os = "linux"))]
+    pub fn set_affinity(_pid: u32, _mask: u64) -> anyhow::Result<()> {
+        bail!("changing affinity is not supported for host processes on this platform")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cpu_lists() {
+        assert_eq!(parse_cpu_list("0").unwrap(), 0b1);
+        assert_eq!(parse_cpu_list("0-3, 6").unwrap(), 0b100_1111);
+        assert_eq!(parse_cpu_list("2,2,3").unwrap(), 0b1100);
+        assert!(parse_cpu_list("").is_err());
+        assert!(parse_cpu_list("3-1").is_err());
+        assert!(parse_cpu_list("64").is_err());
+        assert!(parse_cpu_list("a").is_err());
+    }
+}
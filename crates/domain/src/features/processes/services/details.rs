@@ -0,0 +1,239 @@
+use crate::processes_impl::services::control::{self, ProcessTarget};
+use anyhow::bail;
+use app_contracts::features::processes::{ProcessDetailsDto, ProcessThreadDto};
+use slint::SharedString;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, Users};
+use uniproc_protocol::{LinuxRequest, LinuxResponse};
+
+// Collects the slow-to-gather properties of a single process for the details window.
+pub async fn fetch(target: &ProcessTarget) -> anyhow::Result<ProcessDetailsDto> {
+    if control::is_remote(target.schema_id) {
+        return fetch_remote(target).await;
+    }
+
+    let target = target.clone();
+    tokio::task::spawn_blocking(move || fetch_host(&target)).await?
+}
+
+async fn fetch_remote(target: &ProcessTarget) -> anyhow::Result<ProcessDetailsDto> {
+    let request = LinuxRequest::GetProcessDetails { pid: target.pid };
+
+    let details = match control::send_request(target, request).await? {
+        LinuxResponse::ProcessDetails(details) => details,
+        LinuxResponse::Error(e) => bail!(e),
+        _ => bail!("unexpected response to a details request"),
+    };
+
+    Ok(ProcessDetailsDto {
+        pid: target.pid,
+        name: target.name.clone(),
+        exe_path: details.exe.into(),
+        cmdline: details.cmdline.join(" ").into(),
+        cwd: details.cwd.into(),
+        user: details.user.into(),
+        started: format_started(SystemTime::now() - Duration::from_secs(details.run_time_secs))
+            .into(),
+        environment: details.environ.into_iter().map(Into::into).collect(),
+        threads: details
+            .threads
+            .into_iter()
+            .map(|t| ProcessThreadDto {
+                tid: t.tid,
+                state: t.state.into(),
+            })
+            .collect(),
+        open_files: details.open_files.into_iter().map(Into::into).collect(),
+        error: None,
+    })
+}
+
+fn fetch_host(target: &ProcessTarget) -> anyhow::Result<ProcessDetailsDto> {
+    let pid = Pid::from_u32(target.pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::everything(),
+    );
+
+    let Some(process) = system.process(pid) else {
+        bail!("process not found");
+    };
+
+    let user = process
+        .user_id()
+        .and_then(|uid| {
+            Users::new_with_refreshed_list()
+                .get_user_by_id(uid)
+                .map(|user| user.name().to_string())
+        })
+        .unwrap_or_default();
+
+    let lossy = |s: &std::ffi::OsStr| SharedString::from(s.to_string_lossy().as_ref());
+    let path = |p: Option<&std::path::Path>| {
+        p.map(|p| SharedString::from(p.to_string_lossy().as_ref()))
+            .unwrap_or_default()
+    };
+
+    Ok(ProcessDetailsDto {
+        pid: target.pid,
+        name: target.name.clone(),
+        exe_path: path(process.exe()),
+        cmdline: process
+            .cmd()
+            .iter()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .into(),
+        cwd: path(process.cwd()),
+        user: user.into(),
+        started: format_started(UNIX_EPOCH + Duration::from_secs(process.start_time())).into(),
+        environment: process.environ().iter().map(|v| lossy(v)).collect(),
+        threads: host::threads(target.pid),
+        open_files: host::open_files(target.pid),
+        error: None,
+    })
+}
+
+// Formats as `2024-03-01 14:05:09 UTC`, matching the clock times used elsewhere in the UI.
+fn format_started(at: SystemTime) -> String {
+    let secs = at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let time = secs % 86_400;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+// Days since 1970-01-01 to a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(windows)]
+mod host {
+    use app_contracts::features::processes::ProcessThreadDto;
+    use slint::SharedString;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, TH32CS_SNAPTHREAD, THREADENTRY32, Thread32First, Thread32Next,
+    };
+
+    pub fn threads(pid: u32) -> Vec<ProcessThreadDto> {
+        let mut threads = Vec::new();
+        unsafe {
+            let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0) else {
+                return threads;
+            };
+            let mut entry = THREADENTRY32 {
+                dwSize: size_of::<THREADENTRY32>() as u32,
+                ..Default::default()
+            };
+
+            let mut next = Thread32First(snapshot, &mut entry);
+            while next.is_ok() {
+                if entry.th32OwnerProcessID == pid {
+                    threads.push(ProcessThreadDto {
+                        tid: entry.th32ThreadID,
+                        state: format!("priority {}", entry.tpBasePri).into(),
+                    });
+                }
+                next = Thread32Next(snapshot, &mut entry);
+            }
+            let _ = CloseHandle(snapshot);
+        }
+        threads
+    }
+
+    // Enumerating handles of another process needs undocumented APIs; not shown for now.
+    pub fn open_files(_pid: u32) -> Vec<SharedString> {
+        Vec::new()
+    }
+}
+
+#[cfg(not(windows))]
+mod host {
+    use app_contracts::features::processes::ProcessThreadDto;
+    use slint::SharedString;
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+    pub fn threads(pid: u32) -> Vec<ProcessThreadDto> {
+        let pid = Pid::from_u32(pid);
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+
+        let mut tids: Vec<Pid> = system
+            .process(pid)
+            .and_then(|process| process.tasks())
+            .map(|tasks| tasks.iter().copied().collect())
+            .unwrap_or_default();
+        tids.sort_unstable();
+
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&tids),
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+
+        tids.into_iter()
+            .map(|tid| ProcessThreadDto {
+                tid: tid.as_u32(),
+                state: system
+                    .process(tid)
+                    .map(|task| task.status().to_string().into())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    pub fn open_files(pid: u32) -> Vec<SharedString> {
+        let Ok(entries) = std::fs::read_dir(format!("/proc/{pid}/fd")) else {
+            return Vec::new();
+        };
+
+        let mut files: Vec<SharedString> = entries
+            .flatten()
+            .filter_map(|entry| std::fs::read_link(entry.path()).ok())
+            .map(|target| target.to_string_lossy().as_ref().into())
+            .collect();
+        files.sort();
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_start_as_utc_timestamp() {
+        assert_eq!(format_started(UNIX_EPOCH), "1970-01-01 00:00:00 UTC");
+        assert_eq!(
+            format_started(UNIX_EPOCH + Duration::from_secs(1_709_301_909)),
+            "2024-03-01 14:05:09 UTC"
+        );
+        assert_eq!(
+            format_started(UNIX_EPOCH + Duration::from_secs(951_825_600)),
+            "2000-02-29 12:00:00 UTC"
+        );
+    }
+}
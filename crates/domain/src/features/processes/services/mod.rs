@@ -1 +1,4 @@
+pub mod alerts;
+pub mod control;
+pub mod details;
 pub mod metadata;
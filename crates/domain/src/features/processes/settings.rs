@@ -10,6 +10,24 @@ pub struct ProcessSettings {
     #[setting(default = 5000u64)]
     terminate_timeout_ms: u64,
 
+    #[setting(default = 60u64)]
+    history_samples: u64,
+
+    // Shows only the processes of the active tab's context instead of every source.
+    #[setting(default = false)]
+    scope_to_context: bool,
+
+    // How long a process has to stay zombie or in uninterruptible sleep before it is flagged.
+    #[setting(default = 30u64)]
+    stuck_after_secs: u64,
+
+    #[setting(default = serde_json::json!([]))]
+    alert_rules: Vec<AlertRule>,
+
+    // Name or exe path patterns kept on top of the list, see `ProcessPins`.
+    #[setting(default = serde_json::json!([]))]
+    pinned: Vec<String>,
+
     #[setting(nested)]
     columns: ColumnsSettings,
 }
@@ -21,6 +39,7 @@ pub struct ColumnsSettings {
 
     #[setting(default = serde_json::json!({
         "name": 200u64,
+        "source": 80u64,
         "cpu": 90u64,
         "memory": 120u64,
     }))]
@@ -28,6 +47,7 @@ pub struct ColumnsSettings {
 
     #[setting(default = serde_json::json!({
         "name": { "is-text": true },
+        "source": { "is-text": true },
         "cpu": { "is-metric": true },
         "memory": { "is-metric": true },
     }))]
@@ -39,6 +59,12 @@ pub struct ColumnsSettings {
         "memory": 120u64,
     }))]
     min_widths_px: DashMap<String, u64>,
+
+    #[setting(default = serde_json::json!({}))]
+    visible: DashMap<String, bool>,
+
+    #[setting(default = serde_json::json!(["name", "source", "cpu", "memory", "disk", "net"]))]
+    order: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,4 +75,69 @@ pub struct ColumnMetadata {
 
     #[serde(default)]
     pub is_metric: bool,
+
+    #[serde(default)]
+    pub is_optional: bool,
+}
+
+// A user-defined alert, e.g. `{ "name": "hot", "condition": { "kind": "above", "metric": "cpu",
+// "value": 80.0 }, "for-secs": 30 }`. Metric values are the column numerics: percent for cpu,
+// GiB for memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AlertRule {
+    pub name: String,
+
+    // Case-insensitive substring of the process name; every process when absent.
+    #[serde(default)]
+    pub process: Option<String>,
+
+    pub condition: AlertCondition,
+
+    #[serde(default)]
+    pub for_secs: u64,
+
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+
+    #[serde(default)]
+    pub action: AlertAction,
+}
+
+fn default_cooldown_secs() -> u64 {
+    300
+}
+
+impl AlertRule {
+    pub fn matches(&self, process_name: &str) -> bool {
+        self.process.as_deref().is_none_or(|pattern| {
+            process_name
+                .to_lowercase()
+                .contains(&pattern.to_lowercase())
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "kind")]
+pub enum AlertCondition {
+    // Fires above `value` and re-arms only once the metric falls below `clear-below`.
+    Above {
+        metric: String,
+        value: f32,
+        #[serde(default)]
+        clear_below: Option<f32>,
+    },
+    Exited,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "kind")]
+pub enum AlertAction {
+    #[default]
+    Log,
+    Run {
+        command: String,
+    },
+    Terminate,
 }
@@ -17,10 +17,15 @@ use framework::uri::AppUri;
 use macros::{actor_manifest, handler};
 use slint::SharedString;
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
-use uniproc_protocol::{ServiceCommand, WindowsRequest};
+use std::time::Duration;
+use tracing::{info, warn};
+use uniproc_protocol::{ServiceCommand, WindowsRequest, WindowsResponse};
 use uuid::Uuid;
+use widgets::table::export::ExportFormat;
+use widgets::table::flow::SelectMode;
 
 #[actor_manifest]
 impl<P: UiServicesPort> ManagedActor for ServiceActor<P> {
@@ -33,7 +38,9 @@ impl<P: UiServicesPort> ManagedActor for ServiceActor<P> {
             name: String,
             kind: ServiceActionKind
         },
+        SelectionAction(ServiceActionKind),
         Sort(SharedString),
+        Filter(SharedString),
         ViewportChanged {
             start: usize,
             count: usize
@@ -42,11 +49,21 @@ impl<P: UiServicesPort> ManagedActor for ServiceActor<P> {
             id: SharedString,
             width: f32
         },
-        SelectedService(SharedString, usize),
-        OpenPropertiesWindow(ServiceEntryVm)
+        SelectedService(SharedString, usize, SelectMode),
+        OpenPropertiesWindow(ServiceEntryVm),
+        Export(SharedString),
+        ExportFinished {
+            path: String,
+            rows: usize,
+            error: Option<String>
+        },
+        ActionTimedOut(Uuid)
     );
 }
 
+// An agent that has not answered by then is reported as timed out; a late answer is dropped.
+const ACTION_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub struct ServiceActor<P: UiServicesPort> {
     pub table: ServiceTable,
     pub registry: Arc<SlintWindowRegistry>,
@@ -54,7 +71,9 @@ pub struct ServiceActor<P: UiServicesPort> {
     pub route_status: Arc<RouteStatusRegistry>,
     pub is_active: bool,
     pub active_context_key: Cow<'static, str>,
-    pub pending: HashSet<Uuid>,
+    pub pending: HashMap<Uuid, (SharedString, ServiceActionKind)>,
+    // Outcomes of the current batch, shown together so one service does not hide another.
+    pub action_results: Vec<String>,
     pub ctx_state: FeatureContextState,
 }
 
@@ -81,6 +100,39 @@ impl<P: UiServicesPort> ServiceActor<P> {
         self.ui_port
             .set_service_rows_window(b.total_rows, b.start, b.rows);
     }
+
+    fn send_command(
+        &mut self,
+        id: Uuid,
+        name: SharedString,
+        kind: ServiceActionKind,
+        ctx: &Context<Self>,
+    ) {
+        let service = name.to_string();
+        let cmd = match kind {
+            ServiceActionKind::Start => ServiceCommand::Start { name: service },
+            ServiceActionKind::Stop => ServiceCommand::Stop { name: service },
+            ServiceActionKind::Restart => ServiceCommand::Restart { name: service },
+            ServiceActionKind::Pause => ServiceCommand::Pause { name: service },
+            ServiceActionKind::Resume => ServiceCommand::Resume { name: service },
+        };
+
+        self.pending.insert(id, (name, kind));
+        EventBus::publish(WindowsActionRequest::new(
+            id,
+            WindowsRequest::ServiceCommand(cmd),
+        ));
+        ctx.spawn_bg(async move {
+            tokio::time::sleep(ACTION_TIMEOUT).await;
+            ActionTimedOut(id)
+        });
+    }
+
+    fn report_action(&mut self, result: String) {
+        self.action_results.push(result);
+        self.ui_port
+            .set_action_status(self.action_results.join("; ").into());
+    }
 }
 
 #[handler]
@@ -104,26 +156,60 @@ fn service_snapshot<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: ServiceS
 }
 
 #[handler]
-fn service_action<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: ServiceAction) {
+fn service_action<P: UiServicesPort>(
+    this: &mut ServiceActor<P>,
+    msg: ServiceAction,
+    ctx: &Context<ServiceActor<P>>,
+) {
     let id = current_or_new_correlation_uuid();
-    let cmd = match msg.kind {
-        ServiceActionKind::Start => ServiceCommand::Start { name: msg.name },
-        ServiceActionKind::Stop => ServiceCommand::Stop { name: msg.name },
-        ServiceActionKind::Restart => ServiceCommand::Restart { name: msg.name },
-        ServiceActionKind::Pause => ServiceCommand::Pause { name: msg.name },
-        ServiceActionKind::Resume => ServiceCommand::Resume { name: msg.name },
-    };
+    this.action_results.clear();
+    this.send_command(id, msg.name.into(), msg.kind, ctx);
+}
 
-    this.pending.insert(id);
-    EventBus::publish(WindowsActionRequest::new(
-        id,
-        WindowsRequest::ServiceCommand(cmd),
-    ));
+#[handler]
+fn selection_action<P: UiServicesPort>(
+    this: &mut ServiceActor<P>,
+    msg: SelectionAction,
+    ctx: &Context<ServiceActor<P>>,
+) {
+    this.action_results.clear();
+    // Each service gets its own correlation id so responses can be reported one by one.
+    for name in this.table.selected_names() {
+        this.send_command(Uuid::new_v4(), name, msg.0, ctx);
+    }
 }
 
 #[handler]
 fn on_action_response<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: WindowsActionResponse) {
-    this.pending.remove(&msg.correlation_id);
+    let Some((name, kind)) = this.pending.remove(&msg.correlation_id) else {
+        return;
+    };
+
+    let status = match msg.decode_response() {
+        Ok(WindowsResponse::Error(e)) => {
+            warn!(service = %name, ?kind, "service action failed: {e}");
+            format!("{kind:?} {name}: {e}")
+        }
+        Ok(_) => {
+            info!(service = %name, ?kind, "service action done");
+            format!("{kind:?} {name}: done")
+        }
+        Err(e) => {
+            warn!(service = %name, ?kind, "service action did not reach the agent: {e}");
+            format!("{kind:?} {name}: {e}")
+        }
+    };
+    this.report_action(status);
+}
+
+#[handler]
+fn action_timed_out<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: ActionTimedOut) {
+    let Some((name, kind)) = this.pending.remove(&msg.0) else {
+        return;
+    };
+
+    warn!(service = %name, ?kind, "service action timed out");
+    this.report_action(format!("{kind:?} {name}: timed out"));
 }
 
 #[handler]
@@ -151,6 +237,13 @@ fn sort_services<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: Sort) {
     this.push_batch();
 }
 
+#[handler]
+fn filter_services<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: Filter) {
+    this.table.set_filter(&msg.0);
+    this.table.refresh();
+    this.push_batch();
+}
+
 #[handler]
 fn change_viewport<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: ViewportChanged) {
     this.table.view.rows.set_viewport(msg.start, msg.count);
@@ -159,6 +252,8 @@ fn change_viewport<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: ViewportC
 
 #[handler]
 fn select_service<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: SelectedService) {
+    this.table.select(msg.0.clone(), msg.1, msg.2);
+
     if let Some(dto) = this.table.get_by_name(msg.0.as_str()) {
         match dto.status.as_str() {
             "Running" => this.ui_port.set_active_buttons(false, true, true),
@@ -170,7 +265,56 @@ fn select_service<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: SelectedSe
             .set_selected_service_details(dto.clone().into());
     }
 
-    this.table.select(msg.0.clone(), msg.1);
+    if this.table.selected_names().len() > 1 {
+        this.ui_port.set_active_buttons(true, true, true);
+    }
+
+    this.push_batch();
+}
+
+#[handler]
+fn export_services<P: UiServicesPort>(
+    this: &mut ServiceActor<P>,
+    msg: Export,
+    ctx: &Context<ServiceActor<P>>,
+) {
+    let path = PathBuf::from(msg.0.trim());
+    let format = match ExportFormat::from_path(&path) {
+        Ok(format) => format,
+        Err(e) => {
+            return this
+                .ui_port
+                .set_action_status(format!("Export: {e}").into())
+        }
+    };
+
+    let export = this.table.export();
+    ctx.spawn_bg(async move {
+        let rows = export.len();
+        let display = path.display().to_string();
+        let error = match tokio::task::spawn_blocking(move || export.write_to(&path, format)).await
+        {
+            Ok(written) => written.err().map(|e| e.to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+        ExportFinished {
+            path: display,
+            rows,
+            error,
+        }
+    });
+}
+
+#[handler]
+fn report_export<P: UiServicesPort>(this: &mut ServiceActor<P>, msg: ExportFinished) {
+    let status = match msg.error {
+        None => format!("Exported {} services to {}", msg.rows, msg.path),
+        Some(e) => {
+            warn!(path = %msg.path, "service export failed: {e}");
+            format!("Export: {e}")
+        }
+    };
+    this.ui_port.set_action_status(status.into());
 }
 
 #[handler]
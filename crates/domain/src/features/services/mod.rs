@@ -1,6 +1,6 @@
 use crate::features::services::application::actor::{
-    OpenPropertiesWindow, ResizeCol, SelectedService, ServiceAction, ServiceActor, Sort,
-    ViewportChanged,
+    Export, Filter, OpenPropertiesWindow, ResizeCol, SelectedService, SelectionAction,
+    ServiceAction, ServiceActor, Sort, ViewportChanged,
 };
 use crate::features::services::application::snapshot_actor::ServiceSnapshotActor;
 use crate::features::services::settings::ServiceSettings;
@@ -17,7 +17,8 @@ use framework::feature::{FeatureContextState, WindowFeature, WindowFeatureInitCo
 use framework::native_windows::slint_factory::SlintWindowRegistry;
 use macros::window_feature;
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::HashMap;
+use widgets::table::flow::SelectMode;
 
 pub mod application;
 
@@ -49,7 +50,8 @@ where
             route_status: ctx.shared.get::<RouteStatusRegistry>().unwrap(),
             is_active: true,
             active_context_key: Cow::Borrowed("host"),
-            pending: HashSet::new(),
+            pending: HashMap::new(),
+            action_results: Vec::new(),
             ctx_state: FeatureContextState::new(ctx.window_id, capabilities::SERVICES),
         };
 
@@ -82,14 +84,27 @@ where
                 name: name.to_string(),
                 kind: action.into(),
             })
-            .on_select_service(|s_name, idx| SelectedService(s_name, idx as usize))
+            .on_selection_action(SelectionAction)
+            .on_select_service(|s_name, idx| {
+                SelectedService(s_name, idx as usize, SelectMode::Replace)
+            })
+            .on_extend_selection(|s_name, idx, range| {
+                let mode = if range {
+                    SelectMode::Range
+                } else {
+                    SelectMode::Toggle
+                };
+                SelectedService(s_name, idx as usize, mode)
+            })
             .on_sort_by(Sort)
+            .on_filter_changed(Filter)
             .on_column_resized(|id, width| ResizeCol { id, width })
             .on_rows_viewport_changed(|start, count| ViewportChanged {
                 start: start as usize,
                 count: count as usize,
             })
-            .on_open_properties_window(OpenPropertiesWindow);
+            .on_open_properties_window(OpenPropertiesWindow)
+            .on_export(Export);
 
         ui_port.register(&reg);
 
@@ -4,9 +4,10 @@ use context::caches::strings::StringsProvider;
 use dashmap::DashMap;
 use framework::settings::reactive::ReactiveSettingSubscription;
 use slint::SharedString;
-use std::collections::HashSet;
-use widgets::table::flow::{SortState, TableDataBuilder, TableNode};
+use widgets::table::export::{ExportColumn, TableExport};
+use widgets::table::flow::{Expansion, SelectMode, SortState, TableDataBuilder, TableNode};
 use widgets::table::layout::TableSettingsProvider;
+use widgets::table::query::{QueryRow, TableQuery, contains_ignore_case};
 use widgets::table::view::TableView;
 use widgets::table::window::TableBatch;
 
@@ -88,6 +89,25 @@ impl ServiceTable {
             |_| {},
             |_, _, _| {},
         );
+        self.mark_selection();
+    }
+
+    fn mark_selection(&mut self) {
+        self.view.mark_selection(
+            |vm| vm.name.clone(),
+            |vm, selected| vm.is_selected = selected,
+        );
+    }
+
+    pub fn set_filter(&mut self, query: &str) {
+        let query = TableQuery::parse(query);
+        if query.is_empty() {
+            self.view.flow.clear_filter();
+        } else {
+            self.view
+                .flow
+                .set_filter(move |vm| query.matches(&ServiceQueryRow(vm)));
+        }
     }
 
     pub fn resize_column(&mut self, id: String, new_width: u64) -> anyhow::Result<()> {
@@ -117,8 +137,35 @@ impl ServiceTable {
             .collect()
     }
 
-    pub fn select(&mut self, name: SharedString, idx: usize) {
-        self.view.flow.select(name, idx);
+    pub fn select(&mut self, name: SharedString, idx: usize, mode: SelectMode) {
+        self.view.select(name, idx, mode, |vm| vm.name.clone());
+        self.mark_selection();
+    }
+
+    pub fn selected_names(&self) -> Vec<SharedString> {
+        self.view.flow.selected_ids().cloned().collect()
+    }
+
+    pub fn export(&self) -> TableExport {
+        let mut export = TableExport::new(vec![
+            ExportColumn::new("name", "Name"),
+            ExportColumn::new("display_name", "Display name"),
+            ExportColumn::new("pid", "PID"),
+            ExportColumn::new("status", "Status"),
+            ExportColumn::new("group", "Group"),
+            ExportColumn::new("description", "Description"),
+        ]);
+        for vm in &self.view.rows.items {
+            export.push_row(vec![
+                vm.name.as_str().into(),
+                vm.display_name.as_str().into(),
+                vm.pid.into(),
+                vm.status.as_str().into(),
+                vm.group.as_str().into(),
+                vm.description.as_str().into(),
+            ]);
+        }
+        export
     }
 
     pub fn batch(&self) -> TableBatch<'_, ServiceEntryVm> {
@@ -126,12 +173,26 @@ impl ServiceTable {
     }
 }
 
+struct ServiceQueryRow<'a>(&'a ServiceEntryVm);
+
+impl QueryRow for ServiceQueryRow<'_> {
+    fn contains_text(&self, needle: &str) -> bool {
+        contains_ignore_case(&self.0.name, needle)
+            || contains_ignore_case(&self.0.display_name, needle)
+            || contains_ignore_case(&self.0.description, needle)
+    }
+
+    fn id(&self) -> Option<u64> {
+        u64::try_from(self.0.pid).ok()
+    }
+}
+
 struct ServiceTableBuilder;
 impl TableDataBuilder<ServiceEntryDto, ServiceEntryVm> for ServiceTableBuilder {
     fn build_tree(
         &mut self,
         items: &[ServiceEntryDto],
-        _expanded: &HashSet<SharedString>,
+        _expanded: Expansion<'_, SharedString>,
         out: &mut Vec<TableNode<ServiceEntryVm>>,
     ) {
         let provider = StringsProvider::global();
@@ -146,6 +207,7 @@ impl TableDataBuilder<ServiceEntryDto, ServiceEntryVm> for ServiceTableBuilder {
                     status: provider.intern(&item.status),
                     group: provider.intern(&item.group),
                     description: provider.intern(&item.description),
+                    is_selected: false,
                 },
                 group_id: None,
                 has_children: false,
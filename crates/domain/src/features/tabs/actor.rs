@@ -109,9 +109,12 @@ impl<P: UiTabsPort + Clone> TabsActor<P> {
 fn runtime_state_to_page_status(state: AgentConnectionState) -> PageStatus {
     match state {
         AgentConnectionState::Connected => PageStatus::Ready,
-        AgentConnectionState::Connecting => PageStatus::Loading,
+        AgentConnectionState::Connecting | AgentConnectionState::Handshaking => PageStatus::Loading,
         AgentConnectionState::Disconnected => PageStatus::Inactive,
         AgentConnectionState::WaitingRetry { .. } => PageStatus::Loading,
+        AgentConnectionState::Incompatible { .. } | AgentConnectionState::GaveUp { .. } => {
+            PageStatus::Error
+        }
     }
 }
 
@@ -19,6 +19,7 @@ pub fn bootstrap_contexts() -> Vec<TabContextSnapshot> {
         capabilities: vec![
             capability("processes.list", "Processes"),
             capability("services.list", "Services"),
+            capability("agents.diagnostics", "Diagnostics"),
         ],
         status: PageStatus::Ready,
         ..Default::default()
@@ -162,6 +163,18 @@ fn project_pages(context: &TabContextSnapshot, routes: &RouteRegistry) -> Vec<Ta
         }
     }
 
+    if has_capability(context, "agents.diagnostics") {
+        if let Some(page) = page_descriptor(
+            routes,
+            &context.key,
+            "diagnostics",
+            "Diagnostics",
+            "pulse-regular",
+        ) {
+            pages.push(page);
+        }
+    }
+
     if has_capability(context, "disk.overview") {
         if let Some(page) = page_descriptor(routes, &context.key, "disk", "Disk", "disk") {
             pages.push(page);
@@ -69,6 +69,29 @@ fn generate_method(method: &syn::TraitItemFn, _trait_ident: &syn::Ident) -> Toke
                 }
             }
         }
+        3 => {
+            let ty1 = &types[0];
+            let ty2 = &types[1];
+            let ty3 = &types[2];
+            quote! {
+                pub fn #method_ident<M>(self, ctor: impl Fn(#ty1, #ty2, #ty3) -> M + 'static) -> Self
+                where M: app_core::actor::Message, A: app_core::actor::Handler<M> {
+                    Self { inner: self.inner.on3(|p, f| p.#method_ident(f), ctor) }
+                }
+            }
+        }
+        4 => {
+            let ty1 = &types[0];
+            let ty2 = &types[1];
+            let ty3 = &types[2];
+            let ty4 = &types[3];
+            quote! {
+                pub fn #method_ident<M>(self, ctor: impl Fn(#ty1, #ty2, #ty3, #ty4) -> M + 'static) -> Self
+                where M: app_core::actor::Message, A: app_core::actor::Handler<M> {
+                    Self { inner: self.inner.on4(|p, f| p.#method_ident(f), ctor) }
+                }
+            }
+        }
         _ => quote! {},
     }
 }
@@ -324,7 +324,44 @@ fn build_binding_tracing_wrapper(
                 }
             };
         },
-        _ => panic!("binding tracing currently supports handlers with up to 2 arguments"),
+        3 => quote! {
+            let handler = {
+                let handler = #handler_ident;
+                move |__ui_arg0, __ui_arg1, __ui_arg2| {
+                    let __ui_target = app_core::trace::format_ui_target_3(
+                        &__ui_arg0,
+                        &__ui_arg1,
+                        &__ui_arg2,
+                    );
+                    app_core::trace::in_ui_action_scope(
+                        #scope,
+                        #target_fields,
+                        __ui_target,
+                        || handler(__ui_arg0, __ui_arg1, __ui_arg2),
+                    )
+                }
+            };
+        },
+        4 => quote! {
+            let handler = {
+                let handler = #handler_ident;
+                move |__ui_arg0, __ui_arg1, __ui_arg2, __ui_arg3| {
+                    let __ui_target = app_core::trace::format_ui_target_4(
+                        &__ui_arg0,
+                        &__ui_arg1,
+                        &__ui_arg2,
+                        &__ui_arg3,
+                    );
+                    app_core::trace::in_ui_action_scope(
+                        #scope,
+                        #target_fields,
+                        __ui_target,
+                        || handler(__ui_arg0, __ui_arg1, __ui_arg2, __ui_arg3),
+                    )
+                }
+            };
+        },
+        _ => panic!("binding tracing currently supports handlers with up to 4 arguments"),
     }
 }
 
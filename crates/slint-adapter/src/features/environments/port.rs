@@ -1,9 +1,13 @@
 use crate::features::environments::UiEnvironmentsAdapter;
-use crate::{EnvironmentsFeatureGlobal, WslDistro};
-use app_contracts::features::environments::{UiEnvironmentsPort, WslDistroDto};
+use crate::{AgentHealthRow, EnvironmentsFeatureGlobal, ReplayState, WslDistro};
+use app_contracts::features::agents::{AgentHealth, ReplayStatus};
+use app_contracts::features::environments::{
+    AgentConnectionState, UiEnvironmentsPort, WslDistroDto,
+};
 use context::icons::Icons;
 use macros::slint_port_adapter;
 use slint::{ComponentHandle, ModelRc, VecModel};
+use std::time::Duration;
 
 #[slint_port_adapter(window = AppWindow)]
 impl UiEnvironmentsPort for UiEnvironmentsAdapter {
@@ -25,10 +29,91 @@ impl UiEnvironmentsPort for UiEnvironmentsAdapter {
                     _ => "linux",
                 }),
                 latency_ms: distro.latency_ms,
+                agent_outdated: distro.agent_outdated,
+                agent_gave_up: distro.agent_gave_up,
             })
             .collect::<Vec<_>>();
 
         ui.global::<EnvironmentsFeatureGlobal>()
             .set_wsl_distros(ModelRc::new(VecModel::from(model)));
     }
+
+    fn set_replay_status(&self, ui: &AppWindow, status: ReplayStatus) {
+        ui.global::<EnvironmentsFeatureGlobal>()
+            .set_replay(ReplayState {
+                active: true,
+                paused: status.paused,
+                position_secs: status.position.as_secs_f32(),
+                length_secs: status.length.as_secs_f32(),
+                speed: status.speed as f32,
+                label: format!(
+                    "{} / {} at {}x",
+                    clock(status.position),
+                    clock(status.length),
+                    status.speed
+                )
+                .into(),
+            });
+    }
+
+    fn set_agent_health(&self, ui: &AppWindow, backends: Vec<AgentHealth>) {
+        let model = backends
+            .into_iter()
+            .map(|health| AgentHealthRow {
+                backend: health.backend.into(),
+                state: state_label(health.state).into(),
+                connected: health.state == AgentConnectionState::Connected,
+                ping: millis(&[health.ping_p50_ms, health.ping_p95_ms, health.ping_p99_ms]).into(),
+                scan: millis(&[health.scan_p50_ms, health.scan_p95_ms]).into(),
+                payload: health.scan_bytes.map_or("-".into(), size).into(),
+                scans: health.scans as i32,
+                scan_failures: health.scan_failures as i32,
+                malformed_reports: health.malformed_reports as i32,
+                reconnects: health.reconnects as i32,
+            })
+            .collect::<Vec<_>>();
+
+        ui.global::<EnvironmentsFeatureGlobal>()
+            .set_agent_health(ModelRc::new(VecModel::from(model)));
+    }
+}
+
+fn clock(at: Duration) -> String {
+    let secs = at.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+fn state_label(state: AgentConnectionState) -> String {
+    match state {
+        AgentConnectionState::Disconnected => "Disconnected".into(),
+        AgentConnectionState::Connecting => "Connecting".into(),
+        AgentConnectionState::Handshaking => "Handshaking".into(),
+        AgentConnectionState::Connected => "Connected".into(),
+        AgentConnectionState::WaitingRetry { delay_ms } => {
+            format!("Retry in {:.1}s", delay_ms as f64 / 1000.0)
+        }
+        AgentConnectionState::Incompatible { agent_version } => {
+            format!("Protocol {agent_version}")
+        }
+        AgentConnectionState::GaveUp { attempts } => format!("Gave up ({attempts})"),
+    }
+}
+
+fn millis(percentiles: &[Option<u32>]) -> String {
+    if percentiles.iter().all(Option::is_none) {
+        return "-".into();
+    }
+    let values: Vec<String> = percentiles
+        .iter()
+        .map(|p| p.map_or("-".into(), |ms| ms.to_string()))
+        .collect();
+    format!("{} ms", values.join(" / "))
+}
+
+fn size(bytes: u64) -> String {
+    match bytes {
+        b if b < 1024 => format!("{b} B"),
+        b if b < 1024 * 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{:.1} MB", b as f64 / (1024.0 * 1024.0)),
+    }
 }
@@ -1,4 +1,82 @@
+use crate::{ProcessDetailsWindow, Theme};
+use app_contracts::features::processes::{
+    DETAILS_WINDOW_KEY, ProcessesWindowRegister, UiProcessDetailsPort,
+};
+use framework::native_windows::slint_factory::{SlintWindowRegistry, WindowRegistry};
+use framework::native_windows::{NativeWindowConfig, NativeWindowManager, UiAdapter};
+use i_slint_backend_winit::WinitWindowAccessor;
+use slint::ComponentHandle;
+use slint::platform::WindowEvent;
+use std::any::{Any, TypeId};
+
 mod bindings;
 mod port;
 
 pub use port::UiProcessesAdapter;
+
+impl ProcessesWindowRegister for UiProcessesAdapter {
+    fn register(&self, registry: &SlintWindowRegistry) {
+        registry.register(DETAILS_WINDOW_KEY, || {
+            let window =
+                ProcessDetailsWindow::new().expect("process details window should initialize");
+
+            window.on_drag_requested({
+                let w = window.clone_strong();
+                move || {
+                    w.window().with_winit_window(|w| {
+                        let _ = w.drag_window();
+                    });
+                }
+            });
+
+            window.on_close_requested({
+                let w = window.clone_strong();
+                move || {
+                    w.window().dispatch_event(WindowEvent::CloseRequested);
+                }
+            });
+
+            let theme = window.global::<Theme>();
+
+            if let Ok(accent_palette) =
+                framework::native_windows::platform::get_system_accent_palette()
+            {
+                theme.set_accent(accent_palette.accent.into());
+                theme.set_accent_light_1(accent_palette.accent_light_1.into());
+                theme.set_accent_light_2(accent_palette.accent_light_2.into());
+                theme.set_accent_light_3(accent_palette.accent_light_3.into());
+                theme.set_accent_dark_1(accent_palette.accent_dark_1.into());
+                theme.set_accent_dark_2(accent_palette.accent_dark_2.into());
+                theme.set_accent_dark_3(accent_palette.accent_dark_3.into());
+            }
+
+            NativeWindowManager::with_config(
+                window.clone_strong(),
+                NativeWindowConfig::win11_dialog(),
+            )
+            .with_adapter(ProcessDetailsWindowUiAdapter {
+                ui: window.as_weak(),
+            })
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct ProcessDetailsWindowUiAdapter {
+    ui: slint::Weak<ProcessDetailsWindow>,
+}
+
+impl UiAdapter for ProcessDetailsWindowUiAdapter {
+    fn query_port(&self, type_id: TypeId) -> Option<Box<dyn Any>> {
+        if type_id == TypeId::of::<dyn UiProcessDetailsPort>() {
+            let port: Box<dyn UiProcessDetailsPort> = Box::new(self.clone());
+            Some(Box::new(port))
+        } else {
+            None
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn UiAdapter> {
+        Box::new(self.clone())
+    }
+}
@@ -1,12 +1,16 @@
-use crate::AppWindow;
+use crate::features::processes::ProcessDetailsWindowUiAdapter;
+use crate::{AppWindow, ProcessDetailsWindow};
 use app_contracts::features::processes::{
-    FieldDefDto, FieldMetadata, ProcessEntryVm, UiProcessesPort,
+    AlertRaised, ColumnChoiceDto, FieldDefDto, FieldMetadata, MetricSeriesDto, ProcessActivity,
+    ProcessDetailsDto, ProcessEntryVm, UiProcessDetailsPort, UiProcessesPort,
 };
 use macros::slint_port_adapter;
-use slint::{ComponentHandle, Model, SharedString, VecModel};
+use slint::{ComponentHandle, Model, ModelRc, SharedString, VecModel};
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt::Write;
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use widgets::table::ui_cache::{SlintTableRowAdapter, UiTableCache};
 
 struct AdapterModels {
@@ -14,6 +18,9 @@ struct AdapterModels {
     columns: Rc<VecModel<crate::TableColDef>>,
     widths_model: Rc<VecModel<crate::TableColWidth>>,
     metadata_model: Rc<VecModel<crate::TableColMetadata>>,
+    header_sparklines: Rc<VecModel<crate::TableSparkline>>,
+    selected_sparklines: Rc<VecModel<crate::TableSparkline>>,
+    column_choices: Rc<VecModel<crate::TableColumnChoice>>,
     last_widths: RefCell<Vec<crate::TableColWidth>>,
     last_metadata: RefCell<Vec<crate::TableColMetadata>>,
 }
@@ -32,6 +39,9 @@ impl UiProcessesAdapter {
             columns: Rc::new(VecModel::default()),
             widths_model: Rc::new(VecModel::default()),
             metadata_model: Rc::new(VecModel::default()),
+            header_sparklines: Rc::new(VecModel::default()),
+            selected_sparklines: Rc::new(VecModel::default()),
+            column_choices: Rc::new(VecModel::default()),
             last_widths: Default::default(),
             last_metadata: Default::default(),
         });
@@ -42,6 +52,9 @@ impl UiProcessesAdapter {
             bridge.set_column_defs(models.columns.clone().into());
             bridge.set_column_widths(models.widths_model.clone().into());
             bridge.set_column_metadatas(models.metadata_model.clone().into());
+            bridge.set_header_sparklines(models.header_sparklines.clone().into());
+            bridge.set_selected_sparklines(models.selected_sparklines.clone().into());
+            bridge.set_column_choices(models.column_choices.clone().into());
         }
 
         Self {
@@ -138,11 +151,32 @@ impl UiProcessesPort for UiProcessesAdapter {
         self.models.columns.set_vec(defs);
     }
 
+    fn set_column_choices(&self, choices: Vec<ColumnChoiceDto>) {
+        let next: Vec<crate::TableColumnChoice> = choices
+            .into_iter()
+            .map(|c| crate::TableColumnChoice {
+                id: c.id,
+                label: c.label,
+                visible: c.visible,
+            })
+            .collect();
+
+        if self.models.column_choices.iter().eq(next.iter().cloned()) {
+            return;
+        }
+        patch_model(&self.models.column_choices, next);
+    }
+
     fn get_selected_pid(&self, ui: &AppWindow) -> i32 {
         ui.global::<crate::ProcessesFeatureGlobal>()
             .get_selected_pid()
     }
 
+    fn get_selected_source(&self, ui: &AppWindow) -> SharedString {
+        ui.global::<crate::ProcessesFeatureGlobal>()
+            .get_selected_source()
+    }
+
     fn set_sort_state(&self, ui: &AppWindow, field: SharedString, descending: bool) {
         let bridge = ui.global::<crate::ProcessesFeatureGlobal>();
         bridge.set_current_sort(field);
@@ -153,6 +187,153 @@ impl UiProcessesPort for UiProcessesAdapter {
         ui.global::<crate::ProcessesFeatureGlobal>()
             .set_total_processes_count(count as i32);
     }
+
+    fn set_header_history(&self, ui: &AppWindow, series: Vec<MetricSeriesDto>) {
+        let defs = ui
+            .global::<crate::ProcessesFeatureGlobal>()
+            .get_column_defs();
+        let by_id: HashMap<SharedString, MetricSeriesDto> =
+            series.into_iter().map(|s| (s.id.clone(), s)).collect();
+
+        let next: Vec<crate::TableSparkline> = defs
+            .iter()
+            .map(|def| match by_id.get(&def.id) {
+                Some(series) => to_sparkline(series),
+                None => crate::TableSparkline {
+                    id: def.id.clone(),
+                    ..Default::default()
+                },
+            })
+            .collect();
+
+        patch_model(&self.models.header_sparklines, next);
+    }
+
+    fn set_selected_history(&self, series: Vec<MetricSeriesDto>) {
+        let next = series.iter().map(to_sparkline).collect();
+        patch_model(&self.models.selected_sparklines, next);
+    }
+
+    fn set_alerts(&self, ui: &AppWindow, alerts: Vec<AlertRaised>) {
+        let entries: Vec<crate::AlertEntry> = alerts.iter().map(to_alert_entry).collect();
+        ui.global::<crate::ProcessesFeatureGlobal>()
+            .set_alerts(ModelRc::new(VecModel::from(entries)));
+    }
+
+    fn set_activity(&self, ui: &AppWindow, activity: Vec<ProcessActivity>) {
+        let entries: Vec<crate::ActivityEntry> = activity.iter().map(to_activity_entry).collect();
+        ui.global::<crate::ProcessesFeatureGlobal>()
+            .set_activity(ModelRc::new(VecModel::from(entries)));
+    }
+}
+
+#[slint_port_adapter(window = ProcessDetailsWindow)]
+impl UiProcessDetailsPort for ProcessDetailsWindowUiAdapter {
+    fn set_process_details(&self, ui: &ProcessDetailsWindow, details: ProcessDetailsDto) {
+        ui.global::<crate::ProcessesFeatureGlobal>()
+            .set_details(details.into());
+    }
+}
+
+impl From<ProcessDetailsDto> for crate::ProcessDetails {
+    fn from(value: ProcessDetailsDto) -> Self {
+        let threads: Vec<crate::ProcessThread> = value
+            .threads
+            .into_iter()
+            .map(|t| crate::ProcessThread {
+                tid: t.tid as i32,
+                state: t.state,
+            })
+            .collect();
+
+        Self {
+            pid: value.pid as i32,
+            name: value.name,
+            exe_path: value.exe_path,
+            cmdline: value.cmdline,
+            cwd: value.cwd,
+            user: value.user,
+            started: value.started,
+            environment: ModelRc::new(VecModel::from(value.environment)),
+            threads: ModelRc::new(VecModel::from(threads)),
+            open_files: ModelRc::new(VecModel::from(value.open_files)),
+            error: value.error.unwrap_or_default(),
+        }
+    }
+}
+
+fn clock_time(at: SystemTime) -> SharedString {
+    let secs = at
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() % 86_400)
+        .unwrap_or(0);
+
+    format!(
+        "{:02}:{:02}:{:02} UTC",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+    .into()
+}
+
+fn to_alert_entry(alert: &AlertRaised) -> crate::AlertEntry {
+    crate::AlertEntry {
+        time: clock_time(alert.raised_at),
+        rule: alert.rule.clone(),
+        process: format!("{} ({})", alert.process_name, alert.pid).into(),
+        message: alert.message.clone(),
+    }
+}
+
+fn to_activity_entry(activity: &ProcessActivity) -> crate::ActivityEntry {
+    let (at, pid, name, kind, detail) = match activity {
+        ProcessActivity::Started(e) => match &e.previous_exe {
+            Some(previous) => (
+                e.at,
+                e.pid,
+                &e.name,
+                "exec",
+                format!("{previous} -> {}", e.exe_path).into(),
+            ),
+            None => (e.at, e.pid, &e.name, "started", e.exe_path.clone()),
+        },
+        ProcessActivity::Exited(e) => (e.at, e.pid, &e.name, "exited", e.exe_path.clone()),
+    };
+
+    crate::ActivityEntry {
+        time: clock_time(at),
+        kind: kind.into(),
+        process: format!("{name} ({pid})").into(),
+        detail,
+    }
+}
+
+fn to_sparkline(series: &MetricSeriesDto) -> crate::TableSparkline {
+    crate::TableSparkline {
+        id: series.id.clone(),
+        label: series.label.clone(),
+        commands: sparkline_commands(&series.samples),
+    }
+}
+
+// Path commands in a 100x100 viewbox, scaled to the series maximum.
+fn sparkline_commands(samples: &[f32]) -> SharedString {
+    if samples.len() < 2 {
+        return SharedString::new();
+    }
+
+    let max = samples.iter().copied().fold(f32::EPSILON, f32::max);
+    let step = 100.0 / (samples.len() - 1) as f32;
+    let mut out = String::with_capacity(samples.len() * 14);
+
+    for (i, value) in samples.iter().enumerate() {
+        let cmd = if i == 0 { 'M' } else { 'L' };
+        let y = 100.0 - (value.max(0.0) / max) * 100.0;
+        let _ = write!(out, "{cmd} {:.1} {:.1} ", i as f32 * step, y);
+    }
+
+    out.trim_end().into()
 }
 
 impl SlintTableRowAdapter<crate::ProcessEntry, crate::TableCellData> for ProcessEntryVm {
@@ -163,12 +344,16 @@ impl SlintTableRowAdapter<crate::ProcessEntry, crate::TableCellData> for Process
     fn to_slint_row(&self, cells: slint::ModelRc<crate::TableCellData>) -> crate::ProcessEntry {
         crate::ProcessEntry {
             pid: self.pid,
+            source: self.schema_id.into(),
             name: self.name.clone(),
             icon: self.icon.clone(),
             depth: self.depth,
             has_children: self.has_children,
             is_expanded: self.is_expanded,
             is_dead: self.is_dead,
+            is_selected: self.is_selected,
+            is_pinned: self.pinned_by.is_some(),
+            stuck: self.stuck.clone().unwrap_or_default(),
             cells,
         }
     }
@@ -218,6 +403,7 @@ impl std::fmt::Debug for UiProcessesAdapter {
                     &g.get_column_metadatas().iter().collect::<Vec<_>>(),
                 )
                 .field("selected_pid", &g.get_selected_pid())
+                .field("selected_source", &g.get_selected_source().as_str())
                 .field("selected_name", &g.get_selected_name().as_str())
                 .field(
                     "sort",
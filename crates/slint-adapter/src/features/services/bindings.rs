@@ -12,15 +12,32 @@ impl UiServicesBindings for UiServicesAdapter {
     {
         ui.global::<ServicesFeatureGlobal>()
             .on_service_action(move |name, action| {
-                let kind = match action.as_str() {
-                    "Start" => ServiceActionKind::Start,
-                    "Stop" => ServiceActionKind::Stop,
-                    "Restart" => ServiceActionKind::Restart,
-                    "Pause" => ServiceActionKind::Pause,
-                    "Resume" => ServiceActionKind::Resume,
-                    _ => return,
-                };
-                handler(name, kind);
+                if let Some(kind) = parse_action_kind(&action) {
+                    handler(name, kind);
+                }
             });
     }
+
+    fn on_selection_action<F>(&self, ui: &AppWindow, handler: F)
+    where
+        F: Fn(ServiceActionKind) + 'static,
+    {
+        ui.global::<ServicesFeatureGlobal>()
+            .on_selection_action(move |action| {
+                if let Some(kind) = parse_action_kind(&action) {
+                    handler(kind);
+                }
+            });
+    }
+}
+
+fn parse_action_kind(action: &str) -> Option<ServiceActionKind> {
+    match action {
+        "Start" => Some(ServiceActionKind::Start),
+        "Stop" => Some(ServiceActionKind::Stop),
+        "Restart" => Some(ServiceActionKind::Restart),
+        "Pause" => Some(ServiceActionKind::Pause),
+        "Resume" => Some(ServiceActionKind::Resume),
+        _ => None,
+    }
 }
@@ -114,6 +114,7 @@ impl From<ServiceEntry> for ServiceEntryVm {
             description: entry.description.clone(),
             group: entry.group.clone(),
             display_name: entry.display_name.clone(),
+            is_selected: entry.is_selected,
         }
     }
 }
@@ -127,6 +128,7 @@ impl From<ServiceEntryVm> for ServiceEntry {
             description: entry.description.clone(),
             group: entry.group.clone(),
             display_name: entry.display_name.clone(),
+            is_selected: entry.is_selected,
             cells: Default::default(),
         }
     }
@@ -145,6 +147,7 @@ impl SlintTableRowAdapter<ServiceEntry, TableCellData> for ServiceEntryVm {
             status: self.status.clone(),
             group: self.group.clone(),
             description: self.description.clone(),
+            is_selected: self.is_selected,
             cells,
         }
     }
@@ -0,0 +1,275 @@
+use anyhow::{Context, bail};
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match ext.as_deref() {
+            Some("csv") => Ok(Self::Csv),
+            Some("json") => Ok(Self::Json),
+            Some("ndjson" | "jsonl") => Ok(Self::Ndjson),
+            _ => bail!("unsupported export format, use .csv, .json or .ndjson"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportColumn {
+    pub id: String,
+    pub label: String,
+}
+
+impl ExportColumn {
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+        }
+    }
+}
+
+// Rows are stored in column order; JSON output keys them by column id.
+#[derive(Debug, Clone, Default)]
+pub struct TableExport {
+    columns: Vec<ExportColumn>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl TableExport {
+    pub fn new(columns: Vec<ExportColumn>) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push_row(&mut self, cells: Vec<Value>) {
+        self.rows.push(cells);
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn write_to(&self, path: &Path, format: ExportFormat) -> anyhow::Result<()> {
+        let file =
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        let mut out = BufWriter::new(file);
+
+        match format {
+            ExportFormat::Csv => self.write_csv(&mut out)?,
+            ExportFormat::Json => {
+                let rows: Vec<Value> = self.objects().map(Value::Object).collect();
+                serde_json::to_writer_pretty(&mut out, &rows)?;
+                writeln!(out)?;
+            }
+            ExportFormat::Ndjson => {
+                for row in self.objects() {
+                    serde_json::to_writer(&mut out, &row)?;
+                    writeln!(out)?;
+                }
+            }
+        }
+
+        out.flush()?;
+        Ok(())
+    }
+
+    // One self-contained NDJSON line holding every row, for continuous recording.
+    pub fn to_record_line(&self) -> anyhow::Result<String> {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let mut record = Map::new();
+        record.insert("timestamp_ms".into(), timestamp_ms.into());
+        record.insert(
+            "rows".into(),
+            Value::Array(self.objects().map(Value::Object).collect()),
+        );
+        Ok(serde_json::to_string(&record)?)
+    }
+
+    fn objects(&self) -> impl Iterator<Item = Map<String, Value>> + '_ {
+        self.rows.iter().map(|row| {
+            self.columns
+                .iter()
+                .zip(row)
+                .map(|(col, value)| (col.id.clone(), value.clone()))
+                .collect()
+        })
+    }
+
+    fn write_csv(&self, out: &mut impl Write) -> anyhow::Result<()> {
+        let header: Vec<Cow<'_, str>> = self
+            .columns
+            .iter()
+            .map(|c| csv_escape(Cow::Borrowed(c.label.as_str())))
+            .collect();
+        writeln!(out, "{}", header.join(","))?;
+
+        for row in &self.rows {
+            let cells: Vec<Cow<'_, str>> = row
+                .iter()
+                .map(|value| match value {
+                    Value::Null => Cow::Borrowed(""),
+                    Value::String(s) => csv_escape(Cow::Borrowed(s.as_str())),
+                    other => csv_escape(Cow::Owned(other.to_string())),
+                })
+                .collect();
+            writeln!(out, "{}", cells.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+fn csv_escape(field: Cow<'_, str>) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        field
+    }
+}
+
+// Appends lines on a background thread so recording never blocks the UI.
+pub struct NdjsonRecorder {
+    tx: mpsc::Sender<String>,
+}
+
+impl NdjsonRecorder {
+    pub fn start(path: &Path) -> anyhow::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+
+        let (tx, rx) = mpsc::channel::<String>();
+        std::thread::Builder::new()
+            .name("ndjson-recorder".into())
+            .spawn(move || {
+                let mut out = BufWriter::new(file);
+                for line in rx {
+                    let written = writeln!(out, "{line}").and_then(|_| out.flush());
+                    if let Err(e) = written {
+                        tracing::warn!("recording stopped: {e}");
+                        break;
+                    }
+                }
+            })?;
+
+        Ok(Self { tx })
+    }
+
+    pub fn record(&self, line: String) {
+        let _ = self.tx.send(line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> TableExport {
+        let mut export = TableExport::new(vec![
+            ExportColumn::new("pid", "PID"),
+            ExportColumn::new("name", "Name"),
+        ]);
+        export.push_row(vec![42.into(), "plain".into()]);
+        export.push_row(vec![Value::Null, "say \"hi\", twice".into()]);
+        export
+    }
+
+    fn csv(export: &TableExport) -> String {
+        let mut out = Vec::new();
+        export.write_csv(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn csv_quotes_only_when_needed() {
+        assert_eq!(
+            csv(&sample()),
+            "PID,Name\n42,plain\n,\"say \"\"hi\"\", twice\"\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_line_breaks_and_labels() {
+        let mut export = TableExport::new(vec![ExportColumn::new("cmd", "Command, args")]);
+        export.push_row(vec!["a\nb".into()]);
+        export.push_row(vec![true.into()]);
+
+        assert_eq!(csv(&export), "\"Command, args\"\n\"a\nb\"\ntrue\n");
+    }
+
+    #[test]
+    fn format_follows_extension_case_insensitively() {
+        let format = |p: &str| ExportFormat::from_path(Path::new(p)).unwrap();
+
+        assert_eq!(format("a.csv"), ExportFormat::Csv);
+        assert_eq!(format("a.Json"), ExportFormat::Json);
+        assert_eq!(format("a.ndjson"), ExportFormat::Ndjson);
+        assert_eq!(format("a.JSONL"), ExportFormat::Ndjson);
+    }
+
+    #[test]
+    fn unknown_or_missing_extension_is_rejected() {
+        assert!(ExportFormat::from_path(Path::new("a.txt")).is_err());
+        assert!(ExportFormat::from_path(Path::new("processes")).is_err());
+    }
+
+    #[test]
+    fn objects_are_keyed_by_column_id() {
+        let rows: Vec<Value> = sample().objects().map(Value::Object).collect();
+
+        assert_eq!(
+            Value::Array(rows),
+            serde_json::json!([
+                { "pid": 42, "name": "plain" },
+                { "pid": null, "name": "say \"hi\", twice" },
+            ])
+        );
+    }
+
+    #[test]
+    fn record_line_holds_every_row_on_one_line() {
+        let line = sample().to_record_line().unwrap();
+        assert!(!line.contains('\n'));
+
+        let record: Value = serde_json::from_str(&line).unwrap();
+        assert!(record["timestamp_ms"].as_u64().unwrap() > 0);
+        assert_eq!(record["rows"].as_array().unwrap().len(), 2);
+        assert_eq!(record["rows"][0]["name"], "plain");
+    }
+
+    #[test]
+    fn empty_export_writes_header_only() {
+        let export = TableExport::new(vec![ExportColumn::new("pid", "PID")]);
+
+        assert!(export.is_empty());
+        assert_eq!(csv(&export), "PID\n");
+    }
+}
@@ -17,21 +17,64 @@ pub struct SortState<SID> {
     pub descending: bool,
 }
 
+pub enum Expansion<'a, GID> {
+    Groups(&'a HashSet<GID>),
+    // Every group, regardless of what the user collapsed.
+    All,
+}
+
+impl<GID> Clone for Expansion<'_, GID> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<GID> Copy for Expansion<'_, GID> {}
+
+impl<GID: Eq + Hash> Expansion<'_, GID> {
+    pub fn contains(&self, gid: &GID) -> bool {
+        match self {
+            Self::Groups(expanded) => expanded.contains(gid),
+            Self::All => true,
+        }
+    }
+}
+
 pub trait TableDataBuilder<T, VM, GID = SharedString> {
     fn build_tree(
         &mut self,
         items: &[T],
-        expanded: &HashSet<GID>,
+        expanded: Expansion<'_, GID>,
         out: &mut Vec<TableNode<VM, GID>>,
     );
 }
 
+pub type TableFilter<VM> = Box<dyn Fn(&VM) -> bool>;
+
+pub type TablePinKey<VM, GID> = Box<dyn Fn(&VM) -> Option<GID>>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SelectMode {
+    #[default]
+    Replace,
+    Toggle,
+    Range,
+}
+
+struct SelectedRow<ID, VM> {
+    id: ID,
+    frozen_index: usize,
+    last_known_vm: Option<VM>,
+}
+
 pub struct TableFlowState<T, VM, ID, GID, SID> {
     last_items: Vec<T>,
     expanded_groups: HashSet<GID>,
-    selected_id: Option<ID>,
-    frozen_index: Option<usize>,
-    last_known_vm: Option<VM>,
+    selection: Vec<SelectedRow<ID, VM>>,
+    anchor: Option<usize>,
+    filter: Option<TableFilter<VM>>,
+    pin_key: Option<TablePinKey<VM, GID>>,
+    pinned: Vec<(GID, VM)>,
     pub sort: SortState<SID>,
 
     tree_buffer: Vec<TableNode<VM, GID>>,
@@ -48,9 +91,11 @@ where
         Self {
             last_items: Vec::new(),
             expanded_groups: HashSet::new(),
-            selected_id: None,
-            frozen_index: None,
-            last_known_vm: None,
+            selection: Vec::new(),
+            anchor: None,
+            filter: None,
+            pin_key: None,
+            pinned: Vec::new(),
             sort: initial_sort,
             tree_buffer: Vec::with_capacity(256),
         }
@@ -65,9 +110,46 @@ where
     }
 
     pub fn clear_selection(&mut self) {
-        self.selected_id = None;
-        self.frozen_index = None;
-        self.last_known_vm = None;
+        self.selection.clear();
+        self.anchor = None;
+    }
+
+    pub fn selected_ids(&self) -> impl Iterator<Item = &ID> {
+        self.selection.iter().map(|row| &row.id)
+    }
+
+    pub fn is_selected(&self, id: &ID) -> bool {
+        self.selection.iter().any(|row| row.id == *id)
+    }
+
+    pub fn set_filter(&mut self, filter: impl Fn(&VM) -> bool + 'static) {
+        self.filter = Some(Box::new(filter));
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+    }
+
+    pub fn has_filter(&self) -> bool {
+        self.filter.is_some()
+    }
+
+    // Rows with a pin key are remembered so they can be shown as ghosts once they disappear.
+    pub fn set_pin_key(&mut self, pin_key: impl Fn(&VM) -> Option<GID> + 'static) {
+        self.pin_key = Some(Box::new(pin_key));
+    }
+
+    pub fn clear_pinned(&mut self) {
+        self.pinned.clear();
+    }
+
+    // Stand-ins for keys no live row has shown yet, e.g. pins restored from settings at startup.
+    pub fn seed_pinned(&mut self, placeholders: impl IntoIterator<Item = (GID, VM)>) {
+        for (key, vm) in placeholders {
+            if !self.pinned.iter().any(|(k, _)| *k == key) {
+                self.pinned.push((key, vm));
+            }
+        }
     }
 
     pub fn toggle_expand(&mut self, gid: GID) {
@@ -81,8 +163,49 @@ where
     }
 
     pub fn select(&mut self, id: ID, idx: usize) {
-        self.selected_id = Some(id);
-        self.frozen_index = Some(idx);
+        self.selection.clear();
+        self.selection.push(SelectedRow {
+            id,
+            frozen_index: idx,
+            last_known_vm: None,
+        });
+        self.anchor = Some(idx);
+    }
+
+    pub fn toggle_selected(&mut self, id: ID, idx: usize) {
+        match self.selection.iter().position(|row| row.id == id) {
+            Some(pos) => {
+                self.selection.remove(pos);
+            }
+            None => self.selection.push(SelectedRow {
+                id,
+                frozen_index: idx,
+                last_known_vm: None,
+            }),
+        }
+        self.anchor = Some(idx);
+    }
+
+    // Selects the rows between the anchor and `idx`, keeping the anchor for further shift-clicks.
+    pub fn select_range(&mut self, idx: usize, rows: &[VM], get_id: impl Fn(&VM) -> ID) {
+        let Some(anchor) = self.anchor else {
+            if let Some(vm) = rows.get(idx) {
+                self.select(get_id(vm), idx);
+            }
+            return;
+        };
+
+        let end = idx.max(anchor).min(rows.len().saturating_sub(1));
+        let start = idx.min(anchor).min(end);
+
+        self.selection.clear();
+        for (offset, vm) in rows.get(start..=end).unwrap_or_default().iter().enumerate() {
+            self.selection.push(SelectedRow {
+                id: get_id(vm),
+                frozen_index: start + offset,
+                last_known_vm: Some(vm.clone()),
+            });
+        }
     }
 
     pub fn update_view_model(
@@ -94,48 +217,52 @@ where
         mark_dead: impl Fn(&mut VM),
     ) {
         self.tree_buffer.clear();
-        builder.build_tree(
-            &self.last_items,
-            &self.expanded_groups,
-            &mut self.tree_buffer,
-        );
+        match &self.filter {
+            Some(filter) => build_filtered(
+                &self.last_items,
+                &self.expanded_groups,
+                &mut self.tree_buffer,
+                builder,
+                filter,
+            ),
+            None => builder.build_tree(
+                &self.last_items,
+                Expansion::Groups(&self.expanded_groups),
+                &mut self.tree_buffer,
+            ),
+        }
         sorter(&mut self.tree_buffer, &self.sort);
+        self.apply_pinned_ghosts(&mark_dead);
         self.apply_selection_stability(get_id, mark_dead);
 
         target.clear();
         flatten_to_target(&mut self.tree_buffer, target);
     }
 
-    fn apply_selection_stability(
-        &mut self,
-        get_id: impl Fn(&VM) -> ID,
-        mark_dead: impl Fn(&mut VM),
-    ) {
-        let Some(sel_id) = &self.selected_id else {
+    // Pinned keys that have no live row are inserted as dead ghosts right after the leading pinned
+    // rows, which the sorter is expected to keep on top.
+    fn apply_pinned_ghosts(&mut self, mark_dead: &impl Fn(&mut VM)) {
+        let Some(pin_key) = &self.pin_key else {
             return;
         };
 
-        let current_pos = self
+        let mut live = HashSet::new();
+        remember_pinned(&self.tree_buffer, pin_key, &mut live, &mut self.pinned);
+
+        let mut at = self
             .tree_buffer
             .iter()
-            .position(|n| get_id(&n.vm) == *sel_id);
-
-        if let Some(pos) = current_pos {
-            self.last_known_vm = Some(self.tree_buffer[pos].vm.clone());
-
-            if let Some(target_idx) = self.frozen_index {
-                let target_idx = target_idx.min(self.tree_buffer.len().saturating_sub(1));
-                if pos != target_idx {
-                    let node = self.tree_buffer.remove(pos);
-                    self.tree_buffer.insert(target_idx, node);
-                }
+            .take_while(|n| pin_key(&n.vm).is_some())
+            .count();
+        for (key, vm) in &self.pinned {
+            if live.contains(key) || self.filter.as_ref().is_some_and(|filter| !filter(vm)) {
+                continue;
             }
-        } else if let Some(ghost_vm) = &self.last_known_vm {
-            let mut dead_vm = ghost_vm.clone();
+
+            let mut dead_vm = vm.clone();
             mark_dead(&mut dead_vm);
-            let idx = self.frozen_index.unwrap_or(0).min(self.tree_buffer.len());
             self.tree_buffer.insert(
-                idx,
+                at,
                 TableNode {
                     vm: dead_vm,
                     group_id: None,
@@ -145,8 +272,138 @@ where
                     children: Vec::new(),
                 },
             );
+            at += 1;
+        }
+    }
+
+    // Every selected top-level row is pulled out of the tree and reinserted at the index it had
+    // when it was selected, in ascending order so earlier inserts don't shift later ones. Rows of
+    // expanded groups stay under their parent. Rows that vanished come back as dead ghosts.
+    fn apply_selection_stability(
+        &mut self,
+        get_id: impl Fn(&VM) -> ID,
+        mark_dead: impl Fn(&mut VM),
+    ) {
+        if self.selection.is_empty() {
+            return;
+        }
+
+        let mut pinned = Vec::with_capacity(self.selection.len());
+        for row in &mut self.selection {
+            let current_pos = self
+                .tree_buffer
+                .iter()
+                .position(|n| get_id(&n.vm) == row.id);
+
+            if let Some(pos) = current_pos {
+                let node = self.tree_buffer.remove(pos);
+                row.last_known_vm = Some(node.vm.clone());
+                pinned.push((row.frozen_index, node));
+            } else if let Some(vm) = find_nested(&self.tree_buffer, &|vm| get_id(vm) == row.id) {
+                row.last_known_vm = Some(vm.clone());
+            } else if let Some(ghost_vm) = &row.last_known_vm
+                && self.filter.as_ref().is_none_or(|filter| filter(ghost_vm))
+            {
+                let mut dead_vm = ghost_vm.clone();
+                mark_dead(&mut dead_vm);
+                pinned.push((
+                    row.frozen_index,
+                    TableNode {
+                        vm: dead_vm,
+                        group_id: None,
+                        has_children: false,
+                        is_expanded: false,
+                        level: 0,
+                        children: Vec::new(),
+                    },
+                ));
+            }
+        }
+
+        pinned.sort_by_key(|(idx, _)| *idx);
+        for (idx, node) in pinned {
+            let idx = idx.min(self.tree_buffer.len());
+            self.tree_buffer.insert(idx, node);
+        }
+    }
+}
+
+fn find_nested<'a, VM, GID>(
+    nodes: &'a [TableNode<VM, GID>],
+    matches: &impl Fn(&VM) -> bool,
+) -> Option<&'a VM> {
+    for node in nodes {
+        if let Some(child) = node.children.iter().find(|child| matches(&child.vm)) {
+            return Some(&child.vm);
+        }
+        if let Some(vm) = find_nested(&node.children, matches) {
+            return Some(vm);
         }
     }
+    None
+}
+
+// Parents are visited before their children, so the outermost row of a key is the one kept.
+fn remember_pinned<VM: Clone, GID: Eq + Hash + Clone>(
+    nodes: &[TableNode<VM, GID>],
+    pin_key: &TablePinKey<VM, GID>,
+    live: &mut HashSet<GID>,
+    pinned: &mut Vec<(GID, VM)>,
+) {
+    for node in nodes {
+        if let Some(key) = pin_key(&node.vm)
+            && live.insert(key.clone())
+        {
+            match pinned.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, vm)) => *vm = node.vm.clone(),
+                None => pinned.push((key, node.vm.clone())),
+            }
+        }
+        remember_pinned(&node.children, pin_key, live, pinned);
+    }
+}
+
+// Collapsed nodes don't materialize their children, so the tree is first built
+// with every group expanded to find which subtrees contain matches. The final
+// pass only expands what the user opened plus the ancestors of matching rows.
+fn build_filtered<T, VM, GID: Eq + Hash + Clone>(
+    items: &[T],
+    expanded: &HashSet<GID>,
+    out: &mut Vec<TableNode<VM, GID>>,
+    builder: &mut impl TableDataBuilder<T, VM, GID>,
+    filter: &TableFilter<VM>,
+) {
+    builder.build_tree(items, Expansion::All, out);
+
+    let mut revealed = expanded.clone();
+    collect_match_ancestors(out, filter, &mut revealed);
+
+    out.clear();
+    builder.build_tree(items, Expansion::Groups(&revealed), out);
+    prune_unmatched(out, filter);
+}
+
+fn collect_match_ancestors<VM, GID: Eq + Hash + Clone>(
+    nodes: &[TableNode<VM, GID>],
+    filter: &TableFilter<VM>,
+    revealed: &mut HashSet<GID>,
+) -> bool {
+    let mut any = false;
+    for node in nodes {
+        let child_matched = collect_match_ancestors(&node.children, filter, revealed);
+        if child_matched && let Some(gid) = &node.group_id {
+            revealed.insert(gid.clone());
+        }
+        any |= child_matched || filter(&node.vm);
+    }
+    any
+}
+
+fn prune_unmatched<VM, GID>(nodes: &mut Vec<TableNode<VM, GID>>, filter: &TableFilter<VM>) {
+    nodes.retain_mut(|node| {
+        prune_unmatched(&mut node.children, filter);
+        !node.children.is_empty() || filter(&node.vm)
+    });
 }
 
 fn flatten_to_target<VM: Clone, GID>(nodes: &mut Vec<TableNode<VM, GID>>, target: &mut Vec<VM>) {
@@ -166,3 +423,180 @@ fn flatten_recursive<VM: Clone, GID>(nodes: Vec<TableNode<VM, GID>>, target: &mu
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Row {
+        id: u32,
+        parent: u32,
+        dead: bool,
+    }
+
+    fn row(id: u32, parent: u32) -> Row {
+        Row {
+            id,
+            parent,
+            dead: false,
+        }
+    }
+
+    // Nests rows under their parent id; 0 stands for no parent.
+    #[derive(Default)]
+    struct ParentBuilder {
+        builds: usize,
+    }
+
+    impl TableDataBuilder<Row, Row, u32> for ParentBuilder {
+        fn build_tree(
+            &mut self,
+            items: &[Row],
+            expanded: Expansion<'_, u32>,
+            out: &mut Vec<TableNode<Row, u32>>,
+        ) {
+            self.builds += 1;
+            out.extend(children_of(items, 0, expanded, 0));
+        }
+    }
+
+    fn children_of(
+        items: &[Row],
+        parent: u32,
+        expanded: Expansion<'_, u32>,
+        level: u8,
+    ) -> Vec<TableNode<Row, u32>> {
+        items
+            .iter()
+            .filter(|r| r.parent == parent)
+            .map(|r| {
+                let has_children = items.iter().any(|c| c.parent == r.id);
+                let is_expanded = has_children && expanded.contains(&r.id);
+                TableNode {
+                    vm: r.clone(),
+                    group_id: Some(r.id),
+                    has_children,
+                    is_expanded,
+                    level,
+                    children: if is_expanded {
+                        children_of(items, r.id, expanded, level + 1)
+                    } else {
+                        Vec::new()
+                    },
+                }
+            })
+            .collect()
+    }
+
+    type Flow = TableFlowState<Row, Row, u32, u32, ()>;
+
+    fn flow(items: Vec<Row>) -> Flow {
+        let mut flow = Flow::new(SortState {
+            field_id: None,
+            descending: false,
+        });
+        flow.set_items(items);
+        flow
+    }
+
+    fn render(flow: &mut Flow, builder: &mut ParentBuilder) -> Vec<Row> {
+        let mut rows = Vec::new();
+        flow.update_view_model(&mut rows, builder, |_, _| {}, |r| r.id, |r| r.dead = true);
+        rows
+    }
+
+    fn ids(rows: &[Row]) -> Vec<u32> {
+        rows.iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn collapsed_groups_hide_their_children() {
+        let mut flow = flow(vec![row(1, 0), row(2, 1), row(3, 0)]);
+        let mut builder = ParentBuilder::default();
+        assert_eq!(ids(&render(&mut flow, &mut builder)), [1, 3]);
+
+        flow.toggle_expand(1);
+        assert_eq!(ids(&render(&mut flow, &mut builder)), [1, 2, 3]);
+    }
+
+    #[test]
+    fn filter_reveals_matches_nested_in_collapsed_groups() {
+        let mut flow = flow(vec![row(1, 0), row(2, 1), row(3, 2), row(4, 0)]);
+        flow.set_filter(|r: &Row| r.id == 3);
+        let mut builder = ParentBuilder::default();
+
+        assert_eq!(ids(&render(&mut flow, &mut builder)), [1, 2, 3]);
+        // One fully expanded pass to find the matches and the final pass, whatever the depth.
+        assert_eq!(builder.builds, 2);
+
+        flow.clear_filter();
+        assert_eq!(ids(&render(&mut flow, &mut builder)), [1, 4]);
+    }
+
+    fn selected(flow: &Flow) -> Vec<u32> {
+        flow.selected_ids().copied().collect()
+    }
+
+    #[test]
+    fn toggle_selected_adds_and_removes_rows() {
+        let mut flow = flow(vec![row(1, 0), row(2, 0), row(3, 0)]);
+        flow.select(1, 0);
+        flow.toggle_selected(3, 2);
+        assert_eq!(selected(&flow), [1, 3]);
+
+        flow.toggle_selected(1, 0);
+        assert_eq!(selected(&flow), [3]);
+    }
+
+    #[test]
+    fn select_range_spans_from_the_anchor_in_both_directions() {
+        let rows: Vec<Row> = (1..=5).map(|id| row(id, 0)).collect();
+        let mut flow = flow(rows.clone());
+        flow.select(2, 1);
+
+        flow.select_range(3, &rows, |r| r.id);
+        assert_eq!(selected(&flow), [2, 3, 4]);
+
+        // The anchor stays put, so the next shift-click replaces the range.
+        flow.select_range(0, &rows, |r| r.id);
+        assert_eq!(selected(&flow), [1, 2]);
+    }
+
+    #[test]
+    fn selected_children_of_an_expanded_group_are_not_ghosted() {
+        let mut flow = flow(vec![row(1, 0), row(2, 1), row(3, 1)]);
+        let mut builder = ParentBuilder::default();
+        flow.toggle_expand(1);
+        let rows = render(&mut flow, &mut builder);
+
+        flow.select(2, 1);
+        flow.select_range(2, &rows, |r| r.id);
+        let rows = render(&mut flow, &mut builder);
+        assert_eq!(ids(&rows), [1, 2, 3]);
+        assert!(rows.iter().all(|r| !r.dead));
+    }
+
+    #[test]
+    fn selected_root_keeps_its_index_when_rows_reorder() {
+        let mut flow = flow(vec![row(1, 0), row(2, 0), row(3, 0)]);
+        let mut builder = ParentBuilder::default();
+        flow.select(3, 2);
+
+        flow.set_items(vec![row(3, 0), row(1, 0), row(2, 0)]);
+        assert_eq!(ids(&render(&mut flow, &mut builder)), [1, 2, 3]);
+    }
+
+    #[test]
+    fn vanished_selected_row_comes_back_as_a_dead_ghost() {
+        let mut flow = flow(vec![row(1, 0), row(2, 0)]);
+        let mut builder = ParentBuilder::default();
+        flow.select(2, 1);
+        render(&mut flow, &mut builder);
+
+        flow.set_items(vec![row(1, 0)]);
+        let rows = render(&mut flow, &mut builder);
+        assert_eq!(ids(&rows), [1, 2]);
+        assert!(rows[1].dead);
+    }
+}
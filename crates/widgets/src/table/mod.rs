@@ -1,5 +1,7 @@
+pub mod export;
 pub mod flow;
 pub mod layout;
+pub mod query;
 pub mod ui_cache;
 pub mod view;
 pub mod window;
@@ -0,0 +1,241 @@
+pub trait QueryRow {
+    fn contains_text(&self, needle: &str) -> bool;
+
+    fn id(&self) -> Option<u64> {
+        None
+    }
+
+    fn context(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether the row belongs to the context `key` names (already lowercased).
+    fn in_context(&self, key: &str) -> bool {
+        self.context()
+            .is_some_and(|ctx| ctx.to_lowercase().starts_with(key))
+    }
+
+    fn metric(&self, _field: &str) -> Option<f64> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+}
+
+impl CompareOp {
+    fn apply(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Self::Gt => lhs > rhs,
+            Self::Ge => lhs >= rhs,
+            Self::Lt => lhs < rhs,
+            Self::Le => lhs <= rhs,
+            Self::Eq => (lhs - rhs).abs() < f64::EPSILON,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryTerm {
+    Text(String),
+    Id(u64),
+    Context(String),
+    Compare {
+        field: String,
+        op: CompareOp,
+        value: f64,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableQuery {
+    terms: Vec<QueryTerm>,
+}
+
+impl TableQuery {
+    pub fn parse(input: &str) -> Self {
+        Self {
+            terms: input.split_whitespace().map(parse_term).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn terms(&self) -> &[QueryTerm] {
+        &self.terms
+    }
+
+    pub fn matches(&self, row: &impl QueryRow) -> bool {
+        self.terms.iter().all(|term| match term {
+            QueryTerm::Text(needle) => row.contains_text(needle),
+            QueryTerm::Id(id) => row.id() == Some(*id),
+            QueryTerm::Context(key) => row.in_context(key),
+            QueryTerm::Compare { field, op, value } => row
+                .metric(field)
+                .is_some_and(|metric| op.apply(metric, *value)),
+        })
+    }
+}
+
+pub fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(needle)
+}
+
+fn parse_term(token: &str) -> QueryTerm {
+    if let Some((key, value)) = token.split_once(':')
+        && !value.is_empty()
+    {
+        match key.to_lowercase().as_str() {
+            "pid" | "id" => {
+                if let Ok(id) = value.parse() {
+                    return QueryTerm::Id(id);
+                }
+            }
+            "ctx" | "context" => return QueryTerm::Context(value.to_lowercase()),
+            "name" => return QueryTerm::Text(value.to_lowercase()),
+            _ => {}
+        }
+    }
+
+    if let Some(pos) = token.find(['>', '<', '='])
+        && pos > 0
+    {
+        let (field, rest) = token.split_at(pos);
+        let (op, rest) = match rest.as_bytes() {
+            [b'>', b'=', ..] => (CompareOp::Ge, &rest[2..]),
+            [b'<', b'=', ..] => (CompareOp::Le, &rest[2..]),
+            [b'>', ..] => (CompareOp::Gt, &rest[1..]),
+            [b'<', ..] => (CompareOp::Lt, &rest[1..]),
+            _ => (CompareOp::Eq, &rest[1..]),
+        };
+        if let Some(value) = parse_value(rest) {
+            return QueryTerm::Compare {
+                field: field.to_lowercase(),
+                op,
+                value,
+            };
+        }
+    }
+
+    QueryTerm::Text(token.to_lowercase())
+}
+
+fn parse_value(raw: &str) -> Option<f64> {
+    let raw = raw.trim_end_matches('%');
+    let split = raw
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(raw.len());
+    let (number, unit) = raw.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier = match unit.to_lowercase().as_str() {
+        "" => 1.0,
+        "b" => 1.0,
+        "k" | "kb" | "kib" => 1024.0,
+        "m" | "mb" | "mib" => 1024.0 * 1024.0,
+        "g" | "gb" | "gib" => 1024.0 * 1024.0 * 1024.0,
+        "t" | "tb" | "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+
+    Some(number * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Row {
+        name: &'static str,
+        pid: u64,
+        ctx: &'static str,
+        cpu: f64,
+        mem: f64,
+    }
+
+    impl QueryRow for Row {
+        fn contains_text(&self, needle: &str) -> bool {
+            contains_ignore_case(self.name, needle)
+        }
+
+        fn id(&self) -> Option<u64> {
+            Some(self.pid)
+        }
+
+        fn context(&self) -> Option<&str> {
+            Some(self.ctx)
+        }
+
+        fn metric(&self, field: &str) -> Option<f64> {
+            match field {
+                "cpu" => Some(self.cpu),
+                "mem" => Some(self.mem),
+                _ => None,
+            }
+        }
+    }
+
+    fn row() -> Row {
+        Row {
+            name: "Firefox",
+            pid: 1234,
+            ctx: "wsl/distro/Ubuntu",
+            cpu: 25.0,
+            mem: 2.0 * 1024.0 * 1024.0 * 1024.0,
+        }
+    }
+
+    #[test]
+    fn parses_terms() {
+        let query = TableQuery::parse("fire pid:1234 cpu>=20 mem>1GB ctx:wsl/distro/Ubuntu");
+        assert_eq!(
+            query.terms(),
+            &[
+                QueryTerm::Text("fire".into()),
+                QueryTerm::Id(1234),
+                QueryTerm::Compare {
+                    field: "cpu".into(),
+                    op: CompareOp::Ge,
+                    value: 20.0,
+                },
+                QueryTerm::Compare {
+                    field: "mem".into(),
+                    op: CompareOp::Gt,
+                    value: 1024.0 * 1024.0 * 1024.0,
+                },
+                QueryTerm::Context("wsl/distro/ubuntu".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_terms_fall_back_to_text() {
+        let query = TableQuery::parse("pid:abc cpu>lots");
+        assert_eq!(
+            query.terms(),
+            &[
+                QueryTerm::Text("pid:abc".into()),
+                QueryTerm::Text("cpu>lots".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_all_terms() {
+        let row = row();
+        assert!(TableQuery::parse("").matches(&row));
+        assert!(TableQuery::parse("FIRE cpu>20 mem>1GB").matches(&row));
+        assert!(TableQuery::parse("ctx:wsl/distro").matches(&row));
+        assert!(!TableQuery::parse("fire cpu>30").matches(&row));
+        assert!(!TableQuery::parse("pid:1").matches(&row));
+        assert!(!TableQuery::parse("disk>0").matches(&row));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::table::flow::{SortState, TableDataBuilder, TableFlowState, TableNode};
+use crate::table::flow::{SelectMode, SortState, TableDataBuilder, TableFlowState, TableNode};
 use crate::table::layout::TableLayout;
 use crate::table::window::WindowedRows;
 use std::hash::Hash;
@@ -45,6 +45,25 @@ where
         }
     }
 
+    pub fn select(&mut self, id: ID, idx: usize, mode: SelectMode, get_id: impl Fn(&VM) -> ID) {
+        match mode {
+            SelectMode::Replace => self.flow.select(id, idx),
+            SelectMode::Toggle => self.flow.toggle_selected(id, idx),
+            SelectMode::Range => self.flow.select_range(idx, &self.rows.items, get_id),
+        }
+    }
+
+    pub fn mark_selection(
+        &mut self,
+        get_id: impl Fn(&VM) -> ID,
+        mut mark: impl FnMut(&mut VM, bool),
+    ) {
+        for vm in &mut self.rows.items {
+            let selected = self.flow.is_selected(&get_id(vm));
+            mark(vm, selected);
+        }
+    }
+
     pub fn patch_column_width(
         &mut self,
         col_id: &COLID,